@@ -5,7 +5,9 @@ extern crate rand;
 extern crate rand_pcg;
 extern crate test;
 
-use evalexpr::{build_operator_tree, DefaultNumericTypes};
+use evalexpr::{
+    build_operator_tree, ContextWithMutableVariables, DefaultNumericTypes, HashMapContext, Value,
+};
 use rand::{distributions::Uniform, seq::SliceRandom, Rng, SeedableRng};
 use rand_pcg::Pcg32;
 use std::{fmt::Write, hint::black_box};
@@ -138,3 +140,18 @@ fn bench_evaluate_large_tuple_expression(bencher: &mut Bencher) {
 
     bencher.iter(|| large_tuple_expression.eval().unwrap());
 }
+
+#[bench]
+fn bench_evaluate_builtin_function_calls(bencher: &mut Bencher) {
+    let expression = build_operator_tree::<DefaultNumericTypes>("math::sin(x)").unwrap();
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+
+    bencher.iter(|| {
+        for i in 0..1_000 {
+            context
+                .set_value("x".into(), Value::from_float(i as f64))
+                .unwrap();
+            black_box(expression.eval_with_context(&context).unwrap());
+        }
+    });
+}
@@ -0,0 +1,223 @@
+//! The `batch` module contains helpers to evaluate an expression once per row of some data source, e.g. the records of a CSV file.
+//!
+//! All rows are evaluated with a single [`HashMapContext`] that is reused between rows.
+//! Before each row is evaluated, the variables of the previous row are removed from the context, while its functions are kept.
+//!
+//! # Example
+//!
+//! ```rust
+//! use evalexpr::{batch::eval_rows, *};
+//! use std::collections::HashMap;
+//!
+//! let node = build_operator_tree::<DefaultNumericTypes>("a + b").unwrap();
+//! let rows = vec![
+//!     HashMap::from([("a", Value::from_int(1)), ("b", Value::from_int(2))]),
+//!     HashMap::from([("a", Value::from_int(3)), ("b", Value::from_int(4))]),
+//! ];
+//!
+//! let results: Vec<_> = eval_rows(&node, rows).collect();
+//! assert_eq!(results, vec![Ok(Value::from_int(3)), Ok(Value::from_int(7))]);
+//! ```
+
+use crate::{
+    context::{ContextWithMutableVariables, HashMapContext},
+    error::EvalexprResultValue,
+    tree::Node,
+    value::{numeric_types::EvalexprNumericTypes, Value},
+};
+
+/// An iterator evaluating an expression once per row.
+///
+/// It is created by [`eval_rows`], [`eval_rows_with_context`] or [`eval_records`].
+pub struct EvalRows<'node, NumericTypes: EvalexprNumericTypes, Rows> {
+    node: &'node Node<NumericTypes>,
+    context: HashMapContext<NumericTypes>,
+    rows: Rows,
+}
+
+impl<NumericTypes: EvalexprNumericTypes, Rows> EvalRows<'_, NumericTypes, Rows> {
+    /// Returns the context used to evaluate the rows.
+    ///
+    /// It contains the variables of the row that was evaluated last.
+    pub fn context(&self) -> &HashMapContext<NumericTypes> {
+        &self.context
+    }
+
+    /// Consumes the iterator and returns the context used to evaluate the rows.
+    pub fn into_context(self) -> HashMapContext<NumericTypes> {
+        self.context
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes, Rows, Row, Key> Iterator
+    for EvalRows<'_, NumericTypes, Rows>
+where
+    Rows: Iterator<Item = Row>,
+    Row: IntoIterator<Item = (Key, Value<NumericTypes>)>,
+    Key: Into<String>,
+{
+    type Item = EvalexprResultValue<NumericTypes>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+
+        self.context.clear_variables();
+        for (identifier, value) in row {
+            if let Err(error) = self.context.set_value(identifier.into(), value) {
+                return Some(Err(error));
+            }
+        }
+
+        Some(self.node.eval_with_context(&self.context))
+    }
+}
+
+/// Evaluates the given node once for each row.
+///
+/// Each row is a collection of `(identifier, value)` pairs, e.g. a `HashMap<String, Value>`, which are assigned as variables before the row is evaluated.
+pub fn eval_rows<NumericTypes: EvalexprNumericTypes, Rows: IntoIterator>(
+    node: &Node<NumericTypes>,
+    rows: Rows,
+) -> EvalRows<'_, NumericTypes, Rows::IntoIter> {
+    eval_rows_with_context(node, HashMapContext::new(), rows)
+}
+
+/// Evaluates the given node once for each row, like [`eval_rows`].
+///
+/// The rows are evaluated with the given context, so any functions it contains can be called from the expression.
+/// Its variables are removed before the first row is evaluated.
+pub fn eval_rows_with_context<NumericTypes: EvalexprNumericTypes, Rows: IntoIterator>(
+    node: &Node<NumericTypes>,
+    context: HashMapContext<NumericTypes>,
+    rows: Rows,
+) -> EvalRows<'_, NumericTypes, Rows::IntoIter> {
+    EvalRows {
+        node,
+        context,
+        rows: rows.into_iter(),
+    }
+}
+
+/// Evaluates the given node once for each record.
+///
+/// Each record is a collection of values, which are assigned to the variables named by the header in order, like the columns of a CSV file.
+/// Surplus values or header entries are ignored.
+pub fn eval_records<
+    'node,
+    'header,
+    NumericTypes: EvalexprNumericTypes,
+    Records: IntoIterator<Item = Record>,
+    Record: IntoIterator<Item = Value<NumericTypes>>,
+>(
+    node: &'node Node<NumericTypes>,
+    header: &'header [String],
+    records: Records,
+) -> EvalRows<
+    'node,
+    NumericTypes,
+    impl Iterator<Item = impl Iterator<Item = (String, Value<NumericTypes>)> + 'header> + 'header,
+>
+where
+    Records::IntoIter: 'header,
+    Record::IntoIter: 'header,
+{
+    eval_rows(
+        node,
+        records
+            .into_iter()
+            .map(move |record| header.iter().cloned().zip(record)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        batch::{eval_records, eval_rows, eval_rows_with_context},
+        build_operator_tree, Context, ContextWithMutableFunctions, DefaultNumericTypes,
+        EvalexprError, Function, HashMapContext, Value,
+    };
+
+    #[test]
+    fn test_eval_rows() {
+        let node = build_operator_tree::<DefaultNumericTypes>("a + b").unwrap();
+        let rows = vec![
+            HashMap::from([("a", Value::from_int(1)), ("b", Value::from_int(2))]),
+            HashMap::from([("a", Value::from_int(3)), ("b", Value::from_int(4))]),
+            HashMap::from([("a", Value::from_float(0.5)), ("b", Value::from_int(5))]),
+        ];
+
+        let mut results = eval_rows(&node, rows);
+        assert_eq!(results.next(), Some(Ok(Value::from_int(3))));
+        assert_eq!(results.next(), Some(Ok(Value::from_int(7))));
+        assert_eq!(results.next(), Some(Ok(Value::from_float(5.5))));
+        assert_eq!(results.next(), None);
+        assert_eq!(
+            results.context().get_value("a"),
+            Some(&Value::from_float(0.5))
+        );
+    }
+
+    #[test]
+    fn test_eval_rows_missing_variable() {
+        let node = build_operator_tree::<DefaultNumericTypes>("a + b").unwrap();
+        let rows = vec![
+            vec![("a", Value::from_int(1)), ("b", Value::from_int(2))],
+            vec![("a", Value::from_int(3))],
+        ];
+
+        let results: Vec<_> = eval_rows(&node, rows).collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(Value::from_int(3)),
+                Err(EvalexprError::VariableIdentifierNotFound("b".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eval_rows_with_context() {
+        let node = build_operator_tree::<DefaultNumericTypes>("double(a)").unwrap();
+        let mut context = HashMapContext::new();
+        context
+            .set_function(
+                "double".into(),
+                Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
+            )
+            .unwrap();
+        let rows = (1..=3).map(|a| [("a", Value::from_int(a))]);
+
+        let results: Vec<_> = eval_rows_with_context(&node, context, rows).collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(Value::from_int(2)),
+                Ok(Value::from_int(4)),
+                Ok(Value::from_int(6))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eval_records() {
+        let node = build_operator_tree::<DefaultNumericTypes>("a + b").unwrap();
+        let header = vec!["a".to_string(), "b".to_string()];
+        let records = vec![
+            vec![Value::from_int(1), Value::from_int(2)],
+            vec![Value::from_int(3), Value::from_int(4)],
+            vec![Value::from("x"), Value::from("y")],
+        ];
+
+        let results: Vec<_> = eval_records(&node, &header, records).collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(Value::from_int(3)),
+                Ok(Value::from_int(7)),
+                Ok(Value::from("xy"))
+            ]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! The `config` module contains the `ParseConfig` struct that allows to customise how expression strings are parsed,
+//! and the `OverflowMode` enum that defines how integer overflow is handled during evaluation.
+
+use std::collections::HashMap;
+
+/// Options that change how an expression string is parsed into an operator tree.
+///
+/// The default configuration parses expressions as [`build_operator_tree`](crate::build_operator_tree) does.
+///
+/// # Example
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let config = ParseConfig::new().with_newline_as_semicolon(true);
+/// let tree = build_operator_tree_with_config::<DefaultNumericTypes>("a = 2\na * 3", &config).unwrap();
+/// assert_eq!(tree.eval(), Ok(Value::from_int(6)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseConfig {
+    newline_as_semicolon: bool,
+    keyword_operators: bool,
+    percent_literals: bool,
+    integer_division_operator: bool,
+    max_nesting_depth: Option<usize>,
+    boolean_literals: HashMap<String, bool>,
+}
+
+impl ParseConfig {
+    /// Constructs the default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// If `true`, a newline acts as a semicolon, i.e. it separates expressions of an expression chain.
+    ///
+    /// Newlines within parentheses or string literals are not affected.
+    /// A newline is also ignored if the expression before it is incomplete, e.g. if the line ends with an operator, so long expressions can still be spread over multiple lines.
+    /// Defaults to `false`.
+    pub fn with_newline_as_semicolon(mut self, newline_as_semicolon: bool) -> Self {
+        self.newline_as_semicolon = newline_as_semicolon;
+        self
+    }
+
+    /// Returns `true` if a newline acts as a semicolon.
+    pub fn newline_as_semicolon(&self) -> bool {
+        self.newline_as_semicolon
+    }
+
+    /// If `true`, the keywords `and`, `or` and `not` are parsed as the boolean operators `&&`, `||` and `!`.
+    ///
+    /// The keywords then cannot be used as variable or function identifiers anymore.
+    /// Defaults to `false`.
+    pub fn with_keyword_operators(mut self, keyword_operators: bool) -> Self {
+        self.keyword_operators = keyword_operators;
+        self
+    }
+
+    /// Returns `true` if the keywords `and`, `or` and `not` are parsed as boolean operators.
+    pub fn keyword_operators(&self) -> bool {
+        self.keyword_operators
+    }
+
+    /// If `true`, a number directly followed by a percent sign like `5%` is parsed as the float denoted by the percentage, e.g. `0.05`.
+    ///
+    /// The percent sign is still parsed as the modulo operator if it is separated from the number by whitespace like in `10 % 3`,
+    /// or if it is followed by a value like in `10%3`.
+    /// A sign after the percent sign is not part of a value, so `10%-3` is parsed as `0.1 - 3`.
+    /// Defaults to `false`.
+    pub fn with_percent_literals(mut self, percent_literals: bool) -> Self {
+        self.percent_literals = percent_literals;
+        self
+    }
+
+    /// Returns `true` if a number directly followed by a percent sign is parsed as a percentage.
+    pub fn percent_literals(&self) -> bool {
+        self.percent_literals
+    }
+
+    /// If `true`, a double slash `//` is parsed as the integer division operator, which floors the quotient of two numbers and results in an integer,
+    /// e.g. `7.5 // 2` evaluates to `3`.
+    ///
+    /// End-of-line comments then cannot be used anymore, while inline comments `/* */` are not affected.
+    /// Defaults to `false`.
+    pub fn with_integer_division_operator(mut self, integer_division_operator: bool) -> Self {
+        self.integer_division_operator = integer_division_operator;
+        self
+    }
+
+    /// Returns `true` if a double slash is parsed as the integer division operator.
+    pub fn integer_division_operator(&self) -> bool {
+        self.integer_division_operator
+    }
+
+    /// Limits how deeply parentheses, brackets and curly braces as well as operators may be nested,
+    /// e.g. `((1))` has a nesting depth of two, and so do `-(1 + 2)` and `1 + 2 + 3`.
+    ///
+    /// Expressions that are nested deeper are rejected with an `EvalexprError::RecursionLimitExceeded` while the operator tree is built,
+    /// which protects against exhausting the stack with untrusted input.
+    /// Defaults to `None`, i.e. no limit.
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: Option<usize>) -> Self {
+        self.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Returns the maximum nesting depth of parentheses, brackets, curly braces and operators, or `None` if it is not limited.
+    pub fn max_nesting_depth(&self) -> Option<usize> {
+        self.max_nesting_depth
+    }
+
+    /// Adds a literal that is parsed as the given boolean value, in addition to `true` and `false`.
+    ///
+    /// This allows to recognise alternative spellings like `TRUE`, `yes` or `off`.
+    /// Literals are matched case-sensitively, so each capitalisation needs to be added separately.
+    /// Note that a boolean literal cannot be used as a variable or function identifier anymore.
+    pub fn with_boolean_literal(mut self, literal: impl Into<String>, value: bool) -> Self {
+        self.boolean_literals.insert(literal.into(), value);
+        self
+    }
+
+    /// Returns the boolean value of the given literal if it was added with [`with_boolean_literal`](ParseConfig::with_boolean_literal).
+    ///
+    /// The literals `true` and `false` are always parsed as booleans and are not returned by this method unless they were added explicitly.
+    pub fn boolean_literal(&self, literal: &str) -> Option<bool> {
+        self.boolean_literals.get(literal).copied()
+    }
+}
+
+/// Defines how the arithmetic operators handle integer overflow.
+///
+/// The overflow mode is defined by the context an expression is evaluated with, see [`Context::overflow_mode`](crate::Context::overflow_mode).
+/// It is consulted by the operators `+`, `-` (both binary and unary) and `*` and their assignment variants.
+/// Division and remainder always result in an error on overflow, which only happens when dividing the smallest integer by `-1`.
+///
+/// # Example
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+/// context.set_overflow_mode(OverflowMode::Saturating);
+/// assert_eq!(eval_with_context("9223372036854775807 + 1", &context), Ok(Value::from_int(i64::MAX)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowMode {
+    /// Overflowing operations result in an error, like `EvalexprError::AdditionError`.
+    #[default]
+    Error,
+    /// Overflowing operations are computed with floats instead and result in a float.
+    PromoteToFloat,
+    /// Overflowing operations wrap around at the boundary of the integer type.
+    Wrapping,
+    /// Overflowing operations result in the smallest or largest integer, whichever is closer to the exact result.
+    Saturating,
+}
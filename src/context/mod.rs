@@ -4,9 +4,10 @@
 //! This crate implements two basic variants, the `EmptyContext`, that returns `None` for each identifier and cannot be manipulated, and the `HashMapContext`, that stores its mappings in hash maps.
 //! The HashMapContext is type-safe and returns an error if the user tries to assign a value of a different type than before to an identifier.
 
-use std::{collections::HashMap, iter, marker::PhantomData};
+use std::{borrow::Cow, collections::HashMap, fmt, iter, marker::PhantomData};
 
 use crate::{
+    config::OverflowMode,
     error::EvalexprResultValue,
     function::Function,
     value::{
@@ -27,6 +28,15 @@ pub trait Context {
     /// Returns the value that is linked to the given identifier.
     fn get_value(&self, identifier: &str) -> Option<&Value<Self::NumericTypes>>;
 
+    /// Returns the value that is linked to the given identifier, either borrowed or owned.
+    ///
+    /// This allows contexts to return values that are computed on access instead of stored.
+    /// Evaluation reads variables through this method.
+    /// The default implementation borrows the value returned by [`get_value`](Context::get_value).
+    fn get_value_cow(&self, identifier: &str) -> Option<Cow<'_, Value<Self::NumericTypes>>> {
+        self.get_value(identifier).map(Cow::Borrowed)
+    }
+
     /// Calls the function that is linked to the given identifier with the given argument.
     /// If no function with the given identifier is found, this method returns `EvalexprError::FunctionIdentifierNotFound`.
     fn call_function(
@@ -35,9 +45,54 @@ pub trait Context {
         argument: &Value<Self::NumericTypes>,
     ) -> EvalexprResultValue<Self::NumericTypes>;
 
+    /// Calls the function that is linked to the given identifier with the given argument,
+    /// passing the given context to functions created with [`Function::new_with_context`].
+    ///
+    /// Evaluation calls functions through this method with the context the expression is evaluated with,
+    /// which differs from `self` if this context is wrapped by another one, e.g. a [`ContextStack`].
+    /// The default implementation ignores the given context and calls [`call_function`](Context::call_function).
+    fn call_function_with_context(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+        _context: &dyn Context<NumericTypes = Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.call_function(identifier, argument)
+    }
+
+    /// Returns the function that is linked to the given identifier, if this context stores its functions as [`Function`]s.
+    ///
+    /// It is used by [`Node::validate_against_context`](crate::Node::validate_against_context) to check the amounts of arguments of function calls.
+    /// The default implementation returns `None`.
+    fn get_function(&self, _identifier: &str) -> Option<&Function<Self::NumericTypes>> {
+        None
+    }
+
+    /// Returns the identifiers of all variables known to this context.
+    ///
+    /// They are used by [`EvalexprError::suggestion`] to suggest a similar identifier if an unknown variable identifier is used in an expression.
+    /// The default implementation returns no identifiers.
+    fn known_identifiers(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the identifiers of all functions known to this context, excluding builtin functions.
+    ///
+    /// They are used by [`EvalexprError::suggestion`] to suggest a similar identifier if an unknown function identifier is used in an expression.
+    /// The default implementation returns no identifiers.
+    fn known_function_identifiers(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Checks if builtin functions are disabled.
     fn are_builtin_functions_disabled(&self) -> bool;
 
+    /// Returns how the arithmetic operators handle integer overflow when evaluating with this context.
+    /// The default implementation returns `OverflowMode::Error`.
+    fn overflow_mode(&self) -> OverflowMode {
+        OverflowMode::Error
+    }
+
     /// Disables builtin functions if `disabled` is `true`, and enables them otherwise.
     /// If the context does not support enabling or disabling builtin functions, an error is returned.
     fn set_builtin_functions_disabled(
@@ -88,6 +143,19 @@ pub trait IterateVariablesContext: Context {
     fn iter_variable_names(&self) -> Self::VariableNameIterator<'_>;
 }
 
+/// A context that allows to iterate over the identifiers of its functions.
+///
+/// Builtin functions are not included.
+pub trait IterateFunctionsContext: Context {
+    /// The iterator type for iterating over function names.
+    type FunctionNameIterator<'a>: Iterator<Item = String>
+    where
+        Self: 'a;
+
+    /// Returns an iterator over function names.
+    fn iter_function_names(&self) -> Self::FunctionNameIterator<'_>;
+}
+
 /*/// A context that allows to retrieve functions programmatically.
 pub trait GetFunctionContext: Context {
     /// Returns the function that is linked to the given identifier.
@@ -156,6 +224,17 @@ impl<NumericTypes: EvalexprNumericTypes> IterateVariablesContext for EmptyContex
     }
 }
 
+impl<NumericTypes: EvalexprNumericTypes> IterateFunctionsContext for EmptyContext<NumericTypes> {
+    type FunctionNameIterator<'a>
+        = iter::Empty<String>
+    where
+        Self: 'a;
+
+    fn iter_function_names(&self) -> Self::FunctionNameIterator<'_> {
+        iter::empty()
+    }
+}
+
 impl<NumericTypes> Default for EmptyContext<NumericTypes> {
     fn default() -> Self {
         Self(PhantomData)
@@ -225,6 +304,19 @@ impl<NumericTypes: EvalexprNumericTypes> IterateVariablesContext
     }
 }
 
+impl<NumericTypes: EvalexprNumericTypes> IterateFunctionsContext
+    for EmptyContextWithBuiltinFunctions<NumericTypes>
+{
+    type FunctionNameIterator<'a>
+        = iter::Empty<String>
+    where
+        Self: 'a;
+
+    fn iter_function_names(&self) -> Self::FunctionNameIterator<'_> {
+        iter::empty()
+    }
+}
+
 impl<NumericTypes> Default for EmptyContextWithBuiltinFunctions<NumericTypes> {
     fn default() -> Self {
         Self(PhantomData)
@@ -245,6 +337,18 @@ pub struct HashMapContext<NumericTypes: EvalexprNumericTypes = DefaultNumericTyp
 
     /// True if builtin functions are disabled.
     without_builtin_functions: bool,
+
+    /// How the arithmetic operators handle integer overflow.
+    #[cfg_attr(feature = "serde", serde(default))]
+    overflow_mode: OverflowMode,
+
+    /// True if identifiers are matched case-insensitively.
+    #[cfg_attr(feature = "serde", serde(default))]
+    case_insensitive: bool,
+
+    /// Maps the lowercase identifiers of the variables to the identifiers they are stored with, if identifiers are matched case-insensitively.
+    #[cfg_attr(feature = "serde", serde(default))]
+    variable_identifiers: HashMap<String, String>,
 }
 
 impl<NumericTypes: EvalexprNumericTypes> HashMapContext<NumericTypes> {
@@ -253,6 +357,48 @@ impl<NumericTypes: EvalexprNumericTypes> HashMapContext<NumericTypes> {
         Default::default()
     }
 
+    /// Constructs a `HashMapContext` with no mappings that matches the identifiers of variables and functions case-insensitively.
+    ///
+    /// Variables keep the casing with which they were first assigned, which is the casing returned when iterating over them.
+    /// Functions are stored with lowercase identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new_case_insensitive();
+    /// context.set_value("Price".into(), Value::from_int(5)).unwrap();
+    /// assert_eq!(eval_with_context_mut("PRICE = price * 2; Price", &mut context), Ok(Value::from_int(10)));
+    /// assert_eq!(context.iter_variable_names().collect::<Vec<_>>(), vec!["Price".to_string()]);
+    /// ```
+    pub fn new_case_insensitive() -> Self {
+        Self {
+            case_insensitive: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the identifier the variable with the given identifier is stored with, or `None` if there is no such variable.
+    fn stored_variable_identifier<'a>(&'a self, identifier: &'a str) -> Option<&'a str> {
+        if self.case_insensitive {
+            self.variable_identifiers
+                .get(&identifier.to_lowercase())
+                .map(String::as_str)
+        } else {
+            Some(identifier)
+        }
+    }
+
+    /// Returns the identifier the function with the given identifier is stored with.
+    fn stored_function_identifier<'a>(&self, identifier: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive {
+            Cow::Owned(identifier.to_lowercase())
+        } else {
+            Cow::Borrowed(identifier)
+        }
+    }
+
     /// Removes all variables from the context.
     /// This allows to reuse the context without allocating a new HashMap.
     ///
@@ -268,7 +414,29 @@ impl<NumericTypes: EvalexprNumericTypes> HashMapContext<NumericTypes> {
     /// assert_eq!(context.get_value("abc"), None);
     /// ```
     pub fn clear_variables(&mut self) {
-        self.variables.clear()
+        self.variables.clear();
+        self.variable_identifiers.clear();
+    }
+
+    /// Returns true if a function with the given identifier is linked in the context.
+    ///
+    /// Builtin functions are not taken into account.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// context
+    ///     .set_function("f".into(), Function::new(|argument| Ok(argument.clone())))
+    ///     .unwrap();
+    /// assert!(context.contains_function("f"));
+    /// assert!(!context.contains_function("g"));
+    /// ```
+    pub fn contains_function(&self, identifier: &str) -> bool {
+        self.functions
+            .contains_key(self.stored_function_identifier(identifier).as_ref())
     }
 
     /// Removes all functions from the context.
@@ -295,13 +463,60 @@ impl<NumericTypes: EvalexprNumericTypes> HashMapContext<NumericTypes> {
         self.clear_variables();
         self.clear_functions();
     }
+
+    /// Sets how the arithmetic operators handle integer overflow when evaluating with this context.
+    /// By default, overflow results in an error.
+    pub fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+
+    /// Takes a snapshot of the variables of the context.
+    /// The variables can later be reset to the state of the snapshot using [`restore`](HashMapContext::restore).
+    ///
+    /// This allows to evaluate a script transactionally, by restoring the snapshot if the script fails after some of its assignments have already been executed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// context.set_value("a".into(), Value::from_int(1)).unwrap();
+    /// let snapshot = context.snapshot();
+    /// assert!(eval_with_context_mut("a = 2; b = 3; c", &mut context).is_err());
+    /// assert_eq!(context.get_value("a"), Some(&Value::from_int(2)));
+    /// context.restore(snapshot);
+    /// assert_eq!(context.get_value("a"), Some(&Value::from_int(1)));
+    /// assert_eq!(context.get_value("b"), None);
+    /// ```
+    pub fn snapshot(&self) -> ContextSnapshot<NumericTypes> {
+        ContextSnapshot {
+            variables: self.variables.clone(),
+        }
+    }
+
+    /// Resets the variables of the context to the state of the given snapshot.
+    /// Variables that were assigned after the snapshot was taken are removed.
+    ///
+    /// Functions and whether builtin functions are disabled are not part of the snapshot and stay unchanged.
+    pub fn restore(&mut self, snapshot: ContextSnapshot<NumericTypes>) {
+        self.variables = snapshot.variables;
+        if self.case_insensitive {
+            self.variable_identifiers = self
+                .variables
+                .keys()
+                .map(|identifier| (identifier.to_lowercase(), identifier.clone()))
+                .collect();
+        }
+    }
 }
 
 impl<NumericTypes: EvalexprNumericTypes> Context for HashMapContext<NumericTypes> {
     type NumericTypes = NumericTypes;
 
     fn get_value(&self, identifier: &str) -> Option<&Value<Self::NumericTypes>> {
-        self.variables.get(identifier)
+        self.stored_variable_identifier(identifier)
+            .and_then(|identifier| self.variables.get(identifier))
     }
 
     fn call_function(
@@ -309,8 +524,17 @@ impl<NumericTypes: EvalexprNumericTypes> Context for HashMapContext<NumericTypes
         identifier: &str,
         argument: &Value<Self::NumericTypes>,
     ) -> EvalexprResultValue<Self::NumericTypes> {
-        if let Some(function) = self.functions.get(identifier) {
-            function.call(argument)
+        self.call_function_with_context(identifier, argument, self)
+    }
+
+    fn call_function_with_context(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+        context: &dyn Context<NumericTypes = Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        if let Some(function) = self.get_function(identifier) {
+            function.call(argument, context)
         } else {
             Err(EvalexprError::FunctionIdentifierNotFound(
                 identifier.to_string(),
@@ -318,10 +542,27 @@ impl<NumericTypes: EvalexprNumericTypes> Context for HashMapContext<NumericTypes
         }
     }
 
+    fn get_function(&self, identifier: &str) -> Option<&Function<Self::NumericTypes>> {
+        self.functions
+            .get(self.stored_function_identifier(identifier).as_ref())
+    }
+
+    fn known_identifiers(&self) -> Vec<String> {
+        self.variables.keys().cloned().collect()
+    }
+
+    fn known_function_identifiers(&self) -> Vec<String> {
+        self.functions.keys().cloned().collect()
+    }
+
     fn are_builtin_functions_disabled(&self) -> bool {
         self.without_builtin_functions
     }
 
+    fn overflow_mode(&self) -> OverflowMode {
+        self.overflow_mode
+    }
+
     fn set_builtin_functions_disabled(
         &mut self,
         disabled: bool,
@@ -339,6 +580,15 @@ impl<NumericTypes: EvalexprNumericTypes> ContextWithMutableVariables
         identifier: String,
         value: Value<Self::NumericTypes>,
     ) -> EvalexprResult<(), NumericTypes> {
+        let identifier = if self.case_insensitive {
+            self.variable_identifiers
+                .entry(identifier.to_lowercase())
+                .or_insert(identifier)
+                .clone()
+        } else {
+            identifier
+        };
+
         if let Some(existing_value) = self.variables.get_mut(&identifier) {
             if ValueType::from(&existing_value) == ValueType::from(&value) {
                 *existing_value = value;
@@ -362,6 +612,7 @@ impl<NumericTypes: EvalexprNumericTypes> ContextWithMutableFunctions
         identifier: String,
         function: Function<NumericTypes>,
     ) -> EvalexprResult<(), Self::NumericTypes> {
+        let identifier = self.stored_function_identifier(&identifier).into_owned();
         self.functions.insert(identifier, function);
         Ok(())
     }
@@ -391,16 +642,331 @@ impl<NumericTypes: EvalexprNumericTypes> IterateVariablesContext for HashMapCont
     }
 }
 
+impl<NumericTypes: EvalexprNumericTypes> IterateFunctionsContext for HashMapContext<NumericTypes> {
+    type FunctionNameIterator<'a>
+        = std::iter::Cloned<std::collections::hash_map::Keys<'a, String, Function<NumericTypes>>>
+    where
+        Self: 'a;
+
+    fn iter_function_names(&self) -> Self::FunctionNameIterator<'_> {
+        self.functions.keys().cloned()
+    }
+}
+
 impl<NumericTypes: EvalexprNumericTypes> Default for HashMapContext<NumericTypes> {
     fn default() -> Self {
         Self {
             variables: Default::default(),
             functions: Default::default(),
             without_builtin_functions: false,
+            overflow_mode: OverflowMode::Error,
+            case_insensitive: false,
+            variable_identifiers: Default::default(),
+        }
+    }
+}
+
+/// A snapshot of the variables of a [`HashMapContext`], created by [`HashMapContext::snapshot`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextSnapshot<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
+    variables: HashMap<String, Value<NumericTypes>>,
+}
+
+/// A context made up of a stack of other contexts.
+///
+/// Identifiers are resolved by searching the contexts from the top of the stack to the bottom,
+/// such that contexts pushed later shadow the variables and functions of contexts pushed earlier.
+/// This allows to build scope chains, like a context with local variables on top of a context with global variables.
+///
+/// The contexts of the stack cannot be mutated through the stack.
+/// Whether builtin functions are disabled is decided by the stack itself and not by the contained contexts.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let globals: HashMapContext<DefaultNumericTypes> = context_map! { "a" => int 1, "b" => int 2 }.unwrap(); // Do proper error handling here
+/// let locals: HashMapContext<DefaultNumericTypes> = context_map! { "b" => int 3 }.unwrap(); // Do proper error handling here
+///
+/// let mut stack = ContextStack::new();
+/// stack.push(globals);
+/// stack.push(locals);
+/// assert_eq!(eval_with_context("a + b", &stack), Ok(Value::from_int(4)));
+/// ```
+pub struct ContextStack<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
+    contexts: Vec<Box<dyn Context<NumericTypes = NumericTypes>>>,
+
+    /// True if builtin functions are disabled.
+    without_builtin_functions: bool,
+
+    /// How the arithmetic operators handle integer overflow.
+    overflow_mode: OverflowMode,
+}
+
+impl<NumericTypes: EvalexprNumericTypes> ContextStack<NumericTypes> {
+    /// Constructs an empty `ContextStack`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Pushes a context on top of the stack, shadowing all identifiers it defines in the contexts below.
+    pub fn push(&mut self, context: impl Context<NumericTypes = NumericTypes> + 'static) {
+        self.contexts.push(Box::new(context));
+    }
+
+    /// Removes the context on top of the stack and returns it, or `None` if the stack is empty.
+    pub fn pop(&mut self) -> Option<Box<dyn Context<NumericTypes = NumericTypes>>> {
+        self.contexts.pop()
+    }
+
+    /// Returns the amount of contexts in the stack.
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// Returns true if the stack contains no contexts.
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    /// Sets how the arithmetic operators handle integer overflow when evaluating with this stack.
+    /// Like whether builtin functions are disabled, this is decided by the stack itself and not by the contained contexts.
+    pub fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Context for ContextStack<NumericTypes> {
+    type NumericTypes = NumericTypes;
+
+    fn get_value(&self, identifier: &str) -> Option<&Value<Self::NumericTypes>> {
+        self.contexts
+            .iter()
+            .rev()
+            .find_map(|context| context.get_value(identifier))
+    }
+
+    fn get_value_cow(&self, identifier: &str) -> Option<Cow<'_, Value<Self::NumericTypes>>> {
+        self.contexts
+            .iter()
+            .rev()
+            .find_map(|context| context.get_value_cow(identifier))
+    }
+
+    fn call_function(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.call_function_with_context(identifier, argument, self)
+    }
+
+    fn call_function_with_context(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+        evaluating_context: &dyn Context<NumericTypes = Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        for context in self.contexts.iter().rev() {
+            match context.call_function_with_context(identifier, argument, evaluating_context) {
+                Err(EvalexprError::FunctionIdentifierNotFound(not_found_identifier))
+                    if not_found_identifier == identifier => {},
+                result => return result,
+            }
+        }
+
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            identifier.to_string(),
+        ))
+    }
+
+    fn get_function(&self, identifier: &str) -> Option<&Function<Self::NumericTypes>> {
+        self.contexts
+            .iter()
+            .rev()
+            .find_map(|context| context.get_function(identifier))
+    }
+
+    fn known_identifiers(&self) -> Vec<String> {
+        self.contexts
+            .iter()
+            .flat_map(|context| context.known_identifiers())
+            .collect()
+    }
+
+    fn known_function_identifiers(&self) -> Vec<String> {
+        self.contexts
+            .iter()
+            .flat_map(|context| context.known_function_identifiers())
+            .collect()
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.without_builtin_functions
+    }
+
+    fn overflow_mode(&self) -> OverflowMode {
+        self.overflow_mode
+    }
+
+    fn set_builtin_functions_disabled(
+        &mut self,
+        disabled: bool,
+    ) -> EvalexprResult<(), NumericTypes> {
+        self.without_builtin_functions = disabled;
+        Ok(())
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Default for ContextStack<NumericTypes> {
+    fn default() -> Self {
+        Self {
+            contexts: Default::default(),
+            without_builtin_functions: false,
+            overflow_mode: OverflowMode::Error,
         }
     }
 }
 
+impl<NumericTypes: EvalexprNumericTypes> fmt::Debug for ContextStack<NumericTypes> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextStack")
+            .field("len", &self.contexts.len())
+            .field("without_builtin_functions", &self.without_builtin_functions)
+            .field("overflow_mode", &self.overflow_mode)
+            .finish()
+    }
+}
+
+/// A context that records assignments instead of performing them, to preview the side effects of an expression.
+///
+/// Variables and functions are resolved using the wrapped context, which is never mutated.
+/// Assignments are recorded as writes in the order they are executed, and the assigned values are visible to the rest of the expression,
+/// so the expression evaluates to the same value as if it was evaluated with a mutable copy of the wrapped context.
+/// The types of assigned values are not checked against the previous values of the variables.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context: HashMapContext<DefaultNumericTypes> = context_map! { "y" => int 5 }.unwrap(); // Do proper error handling here
+/// let mut dry_run = DryRunContext::new(&context);
+/// assert_eq!(eval_with_context_mut("x = 1; y = y * 2; x + y", &mut dry_run), Ok(Value::from_int(11)));
+/// assert_eq!(dry_run.writes(), &[("x".to_string(), Value::from_int(1)), ("y".to_string(), Value::from_int(10))]);
+/// assert_eq!(context.get_value("x"), None);
+/// assert_eq!(context.get_value("y"), Some(&Value::from_int(5)));
+/// ```
+#[derive(Debug)]
+pub struct DryRunContext<'context, C: Context> {
+    context: &'context C,
+    writes: Vec<(String, Value<C::NumericTypes>)>,
+    assigned_variables: HashMap<String, Value<C::NumericTypes>>,
+}
+
+impl<'context, C: Context> DryRunContext<'context, C> {
+    /// Constructs a `DryRunContext` that resolves identifiers using the given context, without having recorded any writes.
+    pub fn new(context: &'context C) -> Self {
+        Self {
+            context,
+            writes: Vec::new(),
+            assigned_variables: HashMap::new(),
+        }
+    }
+
+    /// Returns the recorded writes as pairs of variable identifier and assigned value, in the order they were executed.
+    pub fn writes(&self) -> &[(String, Value<C::NumericTypes>)] {
+        &self.writes
+    }
+
+    /// Consumes the context and returns the recorded writes.
+    pub fn into_writes(self) -> Vec<(String, Value<C::NumericTypes>)> {
+        self.writes
+    }
+}
+
+impl<C: Context> Context for DryRunContext<'_, C> {
+    type NumericTypes = C::NumericTypes;
+
+    fn get_value(&self, identifier: &str) -> Option<&Value<Self::NumericTypes>> {
+        self.assigned_variables
+            .get(identifier)
+            .or_else(|| self.context.get_value(identifier))
+    }
+
+    fn get_value_cow(&self, identifier: &str) -> Option<Cow<'_, Value<Self::NumericTypes>>> {
+        match self.assigned_variables.get(identifier) {
+            Some(value) => Some(Cow::Borrowed(value)),
+            None => self.context.get_value_cow(identifier),
+        }
+    }
+
+    fn call_function(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.call_function_with_context(identifier, argument, self)
+    }
+
+    fn call_function_with_context(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+        context: &dyn Context<NumericTypes = Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.context
+            .call_function_with_context(identifier, argument, context)
+    }
+
+    fn get_function(&self, identifier: &str) -> Option<&Function<Self::NumericTypes>> {
+        self.context.get_function(identifier)
+    }
+
+    fn known_identifiers(&self) -> Vec<String> {
+        let mut identifiers = self.context.known_identifiers();
+        identifiers.extend(self.assigned_variables.keys().cloned());
+        identifiers
+    }
+
+    fn known_function_identifiers(&self) -> Vec<String> {
+        self.context.known_function_identifiers()
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.context.are_builtin_functions_disabled()
+    }
+
+    fn overflow_mode(&self) -> OverflowMode {
+        self.context.overflow_mode()
+    }
+
+    /// Whether builtin functions are disabled is decided by the wrapped context and cannot be changed.
+    fn set_builtin_functions_disabled(
+        &mut self,
+        disabled: bool,
+    ) -> EvalexprResult<(), Self::NumericTypes> {
+        if disabled == self.are_builtin_functions_disabled() {
+            Ok(())
+        } else {
+            Err(EvalexprError::ContextNotMutable)
+        }
+    }
+}
+
+impl<C: Context> ContextWithMutableVariables for DryRunContext<'_, C> {
+    fn set_value(
+        &mut self,
+        identifier: String,
+        value: Value<Self::NumericTypes>,
+    ) -> EvalexprResult<(), Self::NumericTypes> {
+        self.writes.push((identifier.clone(), value.clone()));
+        self.assigned_variables.insert(identifier, value);
+        Ok(())
+    }
+}
+
 /// This macro provides a convenient syntax for creating a static context.
 ///
 /// # Examples
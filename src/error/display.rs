@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, ops::Range};
 
 use crate::{value::numeric_types::EvalexprNumericTypes, EvalexprError};
 
@@ -42,6 +42,7 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for EvalexprError<NumericT
                 write!(f, "Expected a Value::Boolean, but got {:?}.", actual)
             },
             ExpectedTuple { actual } => write!(f, "Expected a Value::Tuple, but got {:?}.", actual),
+            ExpectedArray { actual } => write!(f, "Expected a Value::Array, but got {:?}.", actual),
             ExpectedFixedLengthTuple {
                 expected_length,
                 actual,
@@ -84,15 +85,67 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for EvalexprError<NumericT
                 "The operator {:?} was called with a wrong combination of types: {:?}",
                 operator, actual
             ),
-            UnmatchedLBrace => write!(f, "Found an unmatched opening parenthesis '('."),
-            UnmatchedRBrace => write!(f, "Found an unmatched closing parenthesis ')'."),
+            BooleanInArithmetic { operator, actual } => write!(
+                f,
+                "The arithmetic operator {} cannot be applied to the boolean {}, as booleans are \
+                 not numbers. Use if({}, 1, 0) to convert it into a number.",
+                operator, actual, actual
+            ),
+            TypeUnknown { operator } => write!(
+                f,
+                "The result type of the operator {:?} cannot be inferred without evaluating it.",
+                operator
+            ),
+            UnmatchedLBrace { span } => write!(
+                f,
+                "Found an unmatched opening parenthesis '('{}.",
+                Position(span)
+            ),
+            UnmatchedRBrace { span } => write!(
+                f,
+                "Found an unmatched closing parenthesis ')'{}.",
+                Position(span)
+            ),
+            UnmatchedLCurlyBrace { span } => write!(
+                f,
+                "Found an unmatched opening curly brace '{{'{}.",
+                Position(span)
+            ),
+            UnmatchedRCurlyBrace { span } => write!(
+                f,
+                "Found an unmatched closing curly brace '}}'{}.",
+                Position(span)
+            ),
+            UnmatchedLBracket { span } => write!(
+                f,
+                "Found an unmatched opening bracket '['{}.",
+                Position(span)
+            ),
+            UnmatchedRBracket { span } => write!(
+                f,
+                "Found an unmatched closing bracket ']'{}.",
+                Position(span)
+            ),
+            UnmatchedQuestionMark { span } => write!(
+                f,
+                "Found a conditional operator '?' without a matching ':'{}.",
+                Position(span)
+            ),
+            UnmatchedColon { span } => write!(
+                f,
+                "Found a ':' without a matching conditional operator '?'{}.",
+                Position(span)
+            ),
             UnmatchedDoubleQuote => write!(f, "Found an unmatched double quote '\"'"),
-            MissingOperatorOutsideOfBrace { .. } => write!(
+            UnmatchedSingleQuote => write!(f, "Found an unmatched single quote \"'\""),
+            MissingOperatorOutsideOfBrace { span } => write!(
                 f,
                 "Found an opening parenthesis that is preceded by something that does not take \
                  any arguments on the right, or found a closing parenthesis that is succeeded by \
-                 something that does not take any arguments on the left."
+                 something that does not take any arguments on the left{}.",
+                Position(span)
             ),
+            #[allow(deprecated)]
             UnmatchedPartialToken { first, second } => {
                 if let Some(second) = second {
                     write!(
@@ -125,12 +178,24 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for EvalexprError<NumericT
             ModulationError { dividend, divisor } => {
                 write!(f, "Error modulating {} % {}", dividend, divisor)
             },
+            ShiftError { value, amount } => {
+                write!(f, "Error shifting {} by {} bits", value, amount)
+            },
             InvalidRegex { regex, message } => write!(
                 f,
                 "Regular expression {:?} is invalid: {:?}",
                 regex, message
             ),
             ContextNotMutable => write!(f, "Cannot manipulate context"),
+            IllegalLambdaParameters => write!(
+                f,
+                "The parameters of a lambda must be an identifier or a parenthesized tuple of identifiers"
+            ),
+            UnappliedLambda => write!(
+                f,
+                "A lambda can only be used as the function argument of map, filter, all or any"
+            ),
+            RecursionLimitExceeded => write!(f, "The expression is nested too deeply"),
             BuiltinFunctionsCannotBeEnabled => {
                 write!(f, "This context does not allow enabling builtin functions")
             },
@@ -154,3 +219,16 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for EvalexprError<NumericT
         }
     }
 }
+
+/// Displays the position of a parse error as ` at position start..end`, or nothing if the position is unknown.
+struct Position<'span>(&'span Option<Range<usize>>);
+
+impl fmt::Display for Position<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if let Some(span) = self.0 {
+            write!(f, " at position {}..{}", span.start, span.end)
+        } else {
+            Ok(())
+        }
+    }
+}
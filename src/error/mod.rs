@@ -5,9 +5,11 @@
 //! The module also contains some helper functions starting with `expect_` that check for a condition and return `Err(_)` if the condition is not fulfilled.
 //! They are meant as shortcuts to not write the same error checking code everywhere.
 
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 
 use crate::{
+    context::Context,
+    function::builtin::{BUILTIN_FUNCTION_IDENTIFIERS, BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS},
     token::PartialToken,
     value::{
         numeric_types::{default_numeric_types::DefaultNumericTypes, EvalexprNumericTypes},
@@ -20,6 +22,9 @@ use crate::{operator::Operator, value::Value};
 // Exclude error display code from test coverage, as the code does not make sense to test.
 #[cfg(not(tarpaulin_include))]
 mod display;
+mod suggestion;
+
+pub(crate) use suggestion::{closest_identifier, levenshtein_distance};
 
 /// Errors used in this crate.
 #[derive(Debug, Clone, PartialEq)]
@@ -85,6 +90,12 @@ pub enum EvalexprError<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
         actual: Value<NumericTypes>,
     },
 
+    /// An array value was expected.
+    ExpectedArray {
+        /// The actual value.
+        actual: Value<NumericTypes>,
+    },
+
     /// A tuple value of a certain length was expected.
     ExpectedFixedLengthTuple {
         /// The expected length.
@@ -139,22 +150,90 @@ pub enum EvalexprError<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
         actual: Vec<ValueType>,
     },
 
+    /// A boolean was used as an operand of an arithmetic operator.
+    /// Booleans are not numbers, so they need to be converted explicitly, e.g. with `if(boolean, 1, 0)`.
+    BooleanInArithmetic {
+        /// The arithmetic operator whose evaluation caused the error.
+        operator: Operator<NumericTypes>,
+        /// The boolean operand.
+        actual: bool,
+    },
+
+    /// The type of the value an expression evaluates to cannot be inferred without evaluating it.
+    TypeUnknown {
+        /// The operator whose result type cannot be inferred.
+        operator: Operator<NumericTypes>,
+    },
+
     /// An opening brace without a matching closing brace was found.
-    UnmatchedLBrace,
+    UnmatchedLBrace {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
 
     /// A closing brace without a matching opening brace was found.
-    UnmatchedRBrace,
+    UnmatchedRBrace {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// An opening curly brace of an array without a matching closing curly brace was found.
+    UnmatchedLCurlyBrace {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// A closing curly brace without a matching opening curly brace of an array was found.
+    UnmatchedRCurlyBrace {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// An opening bracket of an index without a matching closing bracket was found.
+    UnmatchedLBracket {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// A closing bracket without a matching opening bracket of an index was found.
+    UnmatchedRBracket {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// A question mark of a conditional without a matching colon was found.
+    UnmatchedQuestionMark {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
+
+    /// A colon without a matching question mark of a conditional was found.
+    UnmatchedColon {
+        /// The byte range of the unmatched token in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
 
     /// A double quote without a matching second double quote was found.
     UnmatchedDoubleQuote,
 
+    /// A single quote without a matching second single quote was found.
+    UnmatchedSingleQuote,
+
     /// Left of an opening brace or right of a closing brace is a token that does not expect the brace next to it.
     /// For example, writing `4(5)` would yield this error, as the `4` does not have any operands.
-    MissingOperatorOutsideOfBrace,
+    MissingOperatorOutsideOfBrace {
+        /// The byte range of the brace or of the token next to it in the expression string, if known.
+        span: Option<Range<usize>>,
+    },
 
     /// A `PartialToken` is unmatched, such that it cannot be combined into a full `Token`.
     /// This happens if for example a single `=` is found, surrounded by whitespace.
     /// It is not a token, but it is part of the string representation of some tokens.
+    ///
+    /// Since `&` and `|` are tokens of their own, every partial token can be combined into a full `Token`, so this error is not returned anymore.
+    #[deprecated(
+        note = "every partial token can be combined into a full token, so this error is not returned anymore"
+    )]
     UnmatchedPartialToken {
         /// The unmatched partial token.
         first: PartialToken<NumericTypes>,
@@ -208,6 +287,14 @@ pub enum EvalexprError<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
         divisor: Value<NumericTypes>,
     },
 
+    /// A bit shift operation performed by Rust failed, because the shift amount is negative or not less than the bit width of the integer type.
+    ShiftError {
+        /// The value that is shifted.
+        value: Value<NumericTypes>,
+        /// The amount of bits the value is shifted by.
+        amount: Value<NumericTypes>,
+    },
+
     /// A regular expression could not be parsed
     InvalidRegex {
         /// The invalid regular expression
@@ -219,6 +306,16 @@ pub enum EvalexprError<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
     /// A modification was attempted on a `Context` that does not allow modifications.
     ContextNotMutable,
 
+    /// The parameters of a lambda expression are not a single identifier or a parenthesized tuple of identifiers.
+    IllegalLambdaParameters,
+
+    /// A lambda expression was evaluated other than as the function argument of a builtin higher-order function like `map`.
+    UnappliedLambda,
+
+    /// The expression is nested deeper than allowed, either when evaluating it with [`Node::eval_with_context_limited`](crate::Node::eval_with_context_limited)
+    /// or when parsing it with a [`ParseConfig`](crate::ParseConfig) that limits the nesting depth.
+    RecursionLimitExceeded,
+
     /// An escape sequence within a string literal is illegal.
     IllegalEscapeSequence(String),
 
@@ -247,6 +344,9 @@ pub enum EvalexprError<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
     RandNotEnabled,
 
     /// A custom error explained by its message.
+    ///
+    /// Common error types of the standard library as well as boxed errors convert into this variant,
+    /// such that they can be propagated with `?` within custom functions.
     CustomMessage(String),
 }
 
@@ -272,6 +372,94 @@ impl<NumericTypes: EvalexprNumericTypes> EvalexprError<NumericTypes> {
         EvalexprError::WrongFunctionArgumentAmount { actual, expected }
     }
 
+    /// Returns a known identifier that might have been meant instead of the unknown one, e.g. because of a typo,
+    /// if this error is about an unknown variable or function identifier.
+    ///
+    /// Unknown variables are compared with the [`known_identifiers`](Context::known_identifiers) of the given context,
+    /// and unknown functions with its [`known_function_identifiers`](Context::known_function_identifiers) and the builtin functions, unless they are disabled.
+    /// The suggestion is only computed when this method is called,
+    /// such that errors that are handled otherwise, e.g. by the null-coalescing operator, do not cost the search for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let context: HashMapContext<DefaultNumericTypes> = context_map! { "client" => int 1 }.unwrap(); // Do proper error handling here
+    /// let error = eval_with_context("clinet + 1", &context).unwrap_err();
+    /// assert_eq!(error, EvalexprError::VariableIdentifierNotFound("clinet".into()));
+    /// assert_eq!(error.suggestion(&context), Some("client".into()));
+    /// ```
+    pub fn suggestion<C: Context<NumericTypes = NumericTypes> + ?Sized>(
+        &self,
+        context: &C,
+    ) -> Option<String> {
+        match self {
+            EvalexprError::VariableIdentifierNotFound(identifier) => {
+                let known_identifiers = context.known_identifiers();
+                closest_identifier(identifier, known_identifiers.iter().map(String::as_str))
+            },
+            EvalexprError::FunctionIdentifierNotFound(identifier) => {
+                let known_function_identifiers = context.known_function_identifiers();
+                let builtin_function_identifiers: &[&[&str]] =
+                    if context.are_builtin_functions_disabled() {
+                        &[]
+                    } else {
+                        &[
+                            BUILTIN_FUNCTION_IDENTIFIERS,
+                            BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS,
+                        ]
+                    };
+                closest_identifier(
+                    identifier,
+                    known_function_identifiers.iter().map(String::as_str).chain(
+                        builtin_function_identifiers
+                            .iter()
+                            .flat_map(|identifiers| identifiers.iter().copied()),
+                    ),
+                )
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the byte range of the expression string this error refers to, if it is a parse error whose position is known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            EvalexprError::UnmatchedLBrace { span }
+            | EvalexprError::UnmatchedRBrace { span }
+            | EvalexprError::UnmatchedLCurlyBrace { span }
+            | EvalexprError::UnmatchedRCurlyBrace { span }
+            | EvalexprError::UnmatchedLBracket { span }
+            | EvalexprError::UnmatchedRBracket { span }
+            | EvalexprError::UnmatchedQuestionMark { span }
+            | EvalexprError::UnmatchedColon { span }
+            | EvalexprError::MissingOperatorOutsideOfBrace { span } => span.clone(),
+            _ => None,
+        }
+    }
+
+    /// Sets the span of this error to the given span, if it can have a span and does not have one yet.
+    pub(crate) fn with_span(mut self, new_span: Option<Range<usize>>) -> Self {
+        match &mut self {
+            EvalexprError::UnmatchedLBrace { span }
+            | EvalexprError::UnmatchedRBrace { span }
+            | EvalexprError::UnmatchedLCurlyBrace { span }
+            | EvalexprError::UnmatchedRCurlyBrace { span }
+            | EvalexprError::UnmatchedLBracket { span }
+            | EvalexprError::UnmatchedRBracket { span }
+            | EvalexprError::UnmatchedQuestionMark { span }
+            | EvalexprError::UnmatchedColon { span }
+            | EvalexprError::MissingOperatorOutsideOfBrace { span } => {
+                if span.is_none() {
+                    *span = new_span;
+                }
+            },
+            _ => {},
+        }
+        self
+    }
+
     /// Constructs `EvalexprError::TypeError{actual, expected}`.
     pub fn type_error(actual: Value<NumericTypes>, expected: Vec<ValueType>) -> Self {
         EvalexprError::TypeError { actual, expected }
@@ -285,6 +473,11 @@ impl<NumericTypes: EvalexprNumericTypes> EvalexprError<NumericTypes> {
         EvalexprError::WrongTypeCombination { operator, actual }
     }
 
+    /// Constructs `EvalexprError::BooleanInArithmetic{operator, actual}`.
+    pub fn boolean_in_arithmetic(operator: Operator<NumericTypes>, actual: bool) -> Self {
+        EvalexprError::BooleanInArithmetic { operator, actual }
+    }
+
     /// Constructs `EvalexprError::ExpectedString{actual}`.
     pub fn expected_string(actual: Value<NumericTypes>) -> Self {
         EvalexprError::ExpectedString { actual }
@@ -320,6 +513,11 @@ impl<NumericTypes: EvalexprNumericTypes> EvalexprError<NumericTypes> {
         EvalexprError::ExpectedTuple { actual }
     }
 
+    /// Constructs `EvalexprError::ExpectedArray{actual}`.
+    pub fn expected_array(actual: Value<NumericTypes>) -> Self {
+        EvalexprError::ExpectedArray { actual }
+    }
+
     /// Constructs `EvalexprError::ExpectedFixedLenTuple{expected_len, actual}`.
     pub fn expected_fixed_len_tuple(expected_len: usize, actual: Value<NumericTypes>) -> Self {
         EvalexprError::ExpectedFixedLengthTuple {
@@ -355,17 +553,11 @@ impl<NumericTypes: EvalexprNumericTypes> EvalexprError<NumericTypes> {
             ValueType::Float => Self::expected_float(actual),
             ValueType::Boolean => Self::expected_boolean(actual),
             ValueType::Tuple => Self::expected_tuple(actual),
+            ValueType::Array => Self::expected_array(actual),
             ValueType::Empty => Self::expected_empty(actual),
         }
     }
 
-    pub(crate) fn unmatched_partial_token(
-        first: PartialToken<NumericTypes>,
-        second: Option<PartialToken<NumericTypes>>,
-    ) -> Self {
-        EvalexprError::UnmatchedPartialToken { first, second }
-    }
-
     pub(crate) fn addition_error(augend: Value<NumericTypes>, addend: Value<NumericTypes>) -> Self {
         EvalexprError::AdditionError { augend, addend }
     }
@@ -408,6 +600,10 @@ impl<NumericTypes: EvalexprNumericTypes> EvalexprError<NumericTypes> {
         EvalexprError::ModulationError { dividend, divisor }
     }
 
+    pub(crate) fn shift_error(value: Value<NumericTypes>, amount: Value<NumericTypes>) -> Self {
+        EvalexprError::ShiftError { value, amount }
+    }
+
     /// Constructs `EvalexprError::InvalidRegex(regex)`
     pub fn invalid_regex(regex: String, message: String) -> Self {
         EvalexprError::InvalidRegex { regex, message }
@@ -454,6 +650,35 @@ pub fn expect_number_or_string<NumericTypes: EvalexprNumericTypes>(
 
 impl<NumericTypes: EvalexprNumericTypes> std::error::Error for EvalexprError<NumericTypes> {}
 
+/// Implements `From` for the given error types, converting them into an `EvalexprError::CustomMessage` with their display message.
+///
+/// A blanket implementation for all types implementing `std::error::Error` is not possible,
+/// because it would conflict with the reflexive `From` implementation of `EvalexprError` itself.
+macro_rules! impl_from_error {
+    ($($error:ty),* $(,)?) => {
+        $(
+            impl<NumericTypes: EvalexprNumericTypes> From<$error> for EvalexprError<NumericTypes> {
+                fn from(error: $error) -> Self {
+                    EvalexprError::CustomMessage(error.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_error!(
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::num::TryFromIntError,
+    std::str::ParseBoolError,
+    std::str::Utf8Error,
+    std::string::FromUtf8Error,
+    std::fmt::Error,
+    std::io::Error,
+    Box<dyn std::error::Error>,
+    Box<dyn std::error::Error + Send + Sync>,
+);
+
 /// Standard result type used by this crate.
 pub type EvalexprResult<T, NumericTypes = DefaultNumericTypes> =
     Result<T, EvalexprError<NumericTypes>>;
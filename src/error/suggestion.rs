@@ -0,0 +1,77 @@
+//! Helpers to suggest a known identifier when an unknown one was used, e.g. because of a typo.
+
+/// Returns the candidate that is closest to the given identifier in terms of the Levenshtein distance.
+///
+/// Candidates are only considered if they are close enough to be a plausible typo,
+/// i.e. if their distance is at most a third of the length of the identifier (but at least one).
+/// Ties are broken by choosing the lexicographically smallest candidate, so the result does not depend on the order of the candidates.
+pub(crate) fn closest_identifier<'candidate>(
+    identifier: &str,
+    candidates: impl IntoIterator<Item = &'candidate str>,
+) -> Option<String> {
+    let identifier_len = identifier.chars().count();
+    let max_distance = (identifier_len / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != identifier)
+        .map(|candidate| (levenshtein_distance(identifier, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance && *distance < identifier_len)
+        .min()
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Computes the Levenshtein distance between the given strings, counting characters.
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    let mut current_row = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current_row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_identifier, levenshtein_distance};
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("abc", ""), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("clinet", "client"), 2);
+        assert_eq!(levenshtein_distance("äöü", "aöü"), 1);
+    }
+
+    #[test]
+    fn test_closest_identifier() {
+        assert_eq!(
+            closest_identifier("clinet", ["client", "server", "clients"]),
+            Some("client".to_string())
+        );
+        assert_eq!(
+            closest_identifier("maxx", ["min", "max", "len"]),
+            Some("max".to_string())
+        );
+        assert_eq!(closest_identifier("a", ["b", "c"]), None);
+        assert_eq!(closest_identifier("abc", ["xyz"]), None);
+        assert_eq!(closest_identifier("abc", ["abc"]), None);
+        assert_eq!(closest_identifier("abc", []), None);
+        assert_eq!(
+            closest_identifier("abcd", ["abce", "abcf"]),
+            Some("abce".to_string())
+        );
+    }
+}
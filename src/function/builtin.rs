@@ -1,8 +1,21 @@
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, SecondsFormat};
+#[cfg(feature = "hash")]
+use md5::Md5;
 #[cfg(feature = "regex")]
 use regex::Regex;
+#[cfg(feature = "hash")]
+use sha2::{Digest, Sha256};
+use std::{cmp::Ordering, fmt::Display};
 
 use crate::{
-    value::numeric_types::{EvalexprFloat, EvalexprInt, EvalexprNumericTypes},
+    context::Context,
+    error::{levenshtein_distance, EvalexprResult, EvalexprResultValue},
+    operator::{call_function, values_equal},
+    value::{
+        numeric_types::{EvalexprFloat, EvalexprInt, EvalexprNumericTypes},
+        TupleType,
+    },
     EvalexprError, Function, Value, ValueType,
 };
 
@@ -30,6 +43,857 @@ fn float_is<NumericTypes: EvalexprNumericTypes>(
     }))
 }
 
+/// Rounds the first argument to the amount of decimal digits given as second argument, using the given rounding function.
+///
+/// A negative amount of digits rounds to tens, hundreds and so on.
+/// A finite number whose rounded value is not finite results in an `EvalexprError::CustomMessage`.
+fn round_to_digits<NumericTypes: EvalexprNumericTypes>(
+    name: &'static str,
+    round: fn(&NumericTypes::Float) -> NumericTypes::Float,
+) -> Option<Function<NumericTypes>> {
+    Some(Function::new(move |argument: &Value<NumericTypes>| {
+        let arguments = argument.as_fixed_len_tuple(2)?;
+        let (x, digits) = (arguments[0].as_number()?, arguments[1].as_int()?);
+        let digits = NumericTypes::int_as_float(&digits);
+        let zero = float_constant::<NumericTypes>(0)?;
+        let factor = float_constant::<NumericTypes>(10)?.pow(&digits.abs());
+
+        // Dividing by the inverse factor for negative digits avoids the representation error of fractional powers of ten.
+        let rounded = if digits >= zero {
+            let scaled = x.clone() * factor.clone();
+            if !scaled.is_finite() {
+                // The number has no decimal digits at this precision.
+                return Ok(Value::Float(x));
+            }
+            round(&scaled) / factor
+        } else {
+            round(&(x.clone() / factor.clone())) * factor
+        };
+
+        if x.is_finite() && !rounded.is_finite() {
+            return Err(EvalexprError::CustomMessage(format!(
+                "{} cannot represent {} rounded to {} digits",
+                name, x, digits
+            )));
+        }
+        Ok(Value::Float(rounded))
+    }))
+}
+
+fn constant<NumericTypes: EvalexprNumericTypes>(
+    value: fn() -> Value<NumericTypes>,
+) -> Option<Function<NumericTypes>> {
+    Some(Function::new(move |argument: &Value<NumericTypes>| {
+        argument.as_empty()?;
+        Ok(value())
+    }))
+}
+
+/// Formats an integer in the given radix, using lowercase letters for digits above 9.
+/// Negative integers are formatted as their absolute value with a leading minus.
+fn format_int_radix<NumericTypes: EvalexprNumericTypes>(
+    int: &NumericTypes::Int,
+    radix: usize,
+    prefix: &str,
+) -> EvalexprResult<String, NumericTypes> {
+    let zero = NumericTypes::Int::from_usize(0)?;
+    let radix_int = NumericTypes::Int::from_usize(radix)?;
+    let negative = *int < zero;
+
+    // Collect the digits from the least significant one.
+    // The remainder of a negative integer is not positive, so it is negated instead of the integer itself, which might overflow.
+    let mut digits = Vec::new();
+    let mut remaining = int.clone();
+    loop {
+        let remainder = remaining.checked_rem(&radix_int)?;
+        let digit = if negative {
+            remainder.checked_neg()?
+        } else {
+            remainder
+        };
+        digits.push(
+            char::from_digit(digit.into_usize()? as u32, radix as u32)
+                .expect("remainder is smaller than the radix"),
+        );
+        remaining = remaining.checked_div(&radix_int)?;
+        if remaining == zero {
+            break;
+        }
+    }
+
+    let mut result = String::with_capacity(digits.len() + prefix.len() + 1);
+    if negative {
+        result.push('-');
+    }
+    result.push_str(prefix);
+    result.extend(digits.iter().rev());
+    Ok(result)
+}
+
+/// Constructs a function that formats an integer in the given radix.
+/// If a second boolean argument is given and true, the formatted integer is prefixed with the given prefix.
+fn int_to_radix_string<NumericTypes: EvalexprNumericTypes>(
+    radix: usize,
+    prefix: &'static str,
+) -> Option<Function<NumericTypes>> {
+    Some(Function::new(move |argument: &Value<NumericTypes>| {
+        let (int, with_prefix) = if let Value::Tuple(_) = argument {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            (arguments[0].as_int()?, arguments[1].as_boolean()?)
+        } else {
+            (argument.as_int()?, false)
+        };
+        let prefix = if with_prefix { prefix } else { "" };
+        Ok(Value::String(format_int_radix(&int, radix, prefix)?))
+    }))
+}
+
+/// The SI prefixes used by `str::si`, from the smallest to the largest, each being a thousand times the previous one.
+const SI_PREFIXES: [&str; 11] = ["p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E"];
+
+/// The index of the empty prefix in `SI_PREFIXES`.
+const SI_UNIT_INDEX: usize = 4;
+
+/// Formats a number with the SI prefix that results in a magnitude in `[1, 1000)`, e.g. `1500` as `1.5k`.
+/// The magnitude is rounded to the given amount of fractional digits, and trailing zeros are removed.
+/// Magnitudes outside of the range of prefixes, as well as zero, infinity and NaN, are formatted without adjusting the prefix.
+fn format_si<NumericTypes: EvalexprNumericTypes>(
+    number: NumericTypes::Float,
+    digits: usize,
+) -> EvalexprResult<String, NumericTypes> {
+    if !number.is_finite() {
+        return Ok(number.to_string());
+    }
+
+    let zero = float_constant::<NumericTypes>(0)?;
+    let one = float_constant::<NumericTypes>(1)?;
+    let thousand = float_constant::<NumericTypes>(1000)?;
+    let mut magnitude = number.abs();
+    let mut index = SI_UNIT_INDEX;
+    while magnitude >= thousand && index < SI_PREFIXES.len() - 1 {
+        magnitude = magnitude / thousand.clone();
+        index += 1;
+    }
+    while magnitude < one && magnitude != zero && index > 0 {
+        magnitude = magnitude * thousand.clone();
+        index -= 1;
+    }
+
+    let factor = float_constant::<NumericTypes>(10)?.pow(&float_constant(digits)?);
+    let mut magnitude = (magnitude * factor.clone()).round() / factor;
+    // Rounding may carry over into the next prefix, e.g. for 999.999 rounded to two digits.
+    if magnitude >= thousand && index < SI_PREFIXES.len() - 1 {
+        magnitude = magnitude / thousand;
+        index += 1;
+    }
+
+    let mut formatted = format!("{:.*}", digits, magnitude);
+    if formatted.contains('.') {
+        formatted.truncate(formatted.trim_end_matches('0').trim_end_matches('.').len());
+    }
+    let sign = if number < zero { "-" } else { "" };
+    Ok(format!("{}{}{}", sign, formatted, SI_PREFIXES[index]))
+}
+
+/// Converts a possibly negative slice index into an index into a sequence of length `len`.
+/// Negative indices count from the end of the sequence, and indices outside of the sequence are clamped to it.
+fn clamped_slice_index<NumericTypes: EvalexprNumericTypes>(
+    index: &NumericTypes::Int,
+    len: usize,
+) -> usize {
+    if *index >= NumericTypes::Int::from_usize(0).unwrap_or(NumericTypes::Int::MIN) {
+        index.into_usize().map_or(len, |index| index.min(len))
+    } else {
+        index
+            .checked_neg()
+            .and_then(|distance| distance.into_usize())
+            .map_or(0, |distance| len.saturating_sub(distance))
+    }
+}
+
+/// Appends the elements of the given tuple to `flattened`, recursively replacing nested tuples by their elements.
+fn flatten_into<NumericTypes: EvalexprNumericTypes>(
+    tuple: TupleType<NumericTypes>,
+    flattened: &mut TupleType<NumericTypes>,
+) {
+    for element in tuple {
+        match element {
+            Value::Tuple(tuple) => flatten_into(tuple, flattened),
+            element => flattened.push(element),
+        }
+    }
+}
+
+/// Clamps the given float into the range from 0 to 1.
+fn clamp01<NumericTypes: EvalexprNumericTypes>(
+    x: NumericTypes::Float,
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    Ok(x.max(&float_constant(0)?).min(&float_constant(1)?))
+}
+
+/// Converts a small non-negative integer constant into a float.
+fn float_constant<NumericTypes: EvalexprNumericTypes>(
+    constant: usize,
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    Ok(NumericTypes::int_as_float(&NumericTypes::Int::from_usize(
+        constant,
+    )?))
+}
+
+/// The maximum length in bytes of a string that a builtin function constructs from a count or width given by the caller.
+const MAX_BUILTIN_STRING_LENGTH: usize = 1 << 24;
+
+/// Checks the length in bytes of a string that the builtin function with the given name is about to construct.
+/// A length that overflowed, indicated by `None`, or that exceeds `MAX_BUILTIN_STRING_LENGTH` results in an `EvalexprError::CustomMessage`.
+fn checked_string_length<NumericTypes: EvalexprNumericTypes>(
+    name: &str,
+    length: Option<usize>,
+) -> EvalexprResult<usize, NumericTypes> {
+    length
+        .filter(|length| *length <= MAX_BUILTIN_STRING_LENGTH)
+        .ok_or_else(|| {
+            EvalexprError::CustomMessage(format!(
+                "{} cannot construct strings longer than {} bytes",
+                name, MAX_BUILTIN_STRING_LENGTH
+            ))
+        })
+}
+
+/// The maximum amount of fractional digits accepted by builtin formatting functions.
+/// Further digits do not carry any information for a 64-bit float.
+const MAX_FRACTIONAL_DIGITS: usize = 17;
+
+/// Converts an amount of fractional digits given to the builtin formatting function with the given name into a [`usize`].
+/// Amounts greater than `MAX_FRACTIONAL_DIGITS` result in an `EvalexprError::CustomMessage`.
+fn checked_fractional_digits<NumericTypes: EvalexprNumericTypes>(
+    name: &str,
+    digits: &NumericTypes::Int,
+) -> EvalexprResult<usize, NumericTypes> {
+    let digits = digits.into_usize()?;
+    if digits > MAX_FRACTIONAL_DIGITS {
+        return Err(EvalexprError::CustomMessage(format!(
+            "{} supports at most {} fractional digits, but got {}",
+            name, MAX_FRACTIONAL_DIGITS, digits
+        )));
+    }
+    Ok(digits)
+}
+
+/// Computes the logarithm of a number, to the natural base if only the number is given, or to the base given as second argument.
+///
+/// Numbers and bases outside of the domain of the logarithm result in an `EvalexprError::CustomMessage`,
+/// i.e. numbers and bases that are not positive, and a base of one.
+fn logarithm<NumericTypes: EvalexprNumericTypes>(
+    argument: &Value<NumericTypes>,
+) -> EvalexprResultValue<NumericTypes> {
+    let (number, base) = if let Value::Tuple(_) = argument {
+        let arguments = argument.as_fixed_len_tuple(2)?;
+        (arguments[0].as_number()?, Some(arguments[1].as_number()?))
+    } else {
+        (argument.as_number()?, None)
+    };
+
+    let zero = float_constant::<NumericTypes>(0)?;
+    if number <= zero {
+        return Err(EvalexprError::CustomMessage(format!(
+            "math::log is only defined for positive numbers, but got {}",
+            number
+        )));
+    }
+
+    if let Some(base) = base {
+        let one = float_constant::<NumericTypes>(1)?;
+        if base <= zero || base == one {
+            return Err(EvalexprError::CustomMessage(format!(
+                "math::log is only defined for positive bases other than 1, but got {}",
+                base
+            )));
+        }
+        Ok(Value::Float(number.log(&base)))
+    } else {
+        Ok(Value::Float(number.ln()))
+    }
+}
+
+/// Wraps the first argument modularly into the half-open range from the second to the third argument.
+///
+/// If all arguments are integers, the result is an integer, otherwise it is a float.
+/// If any argument is NaN, the result is NaN.
+/// A lower bound that is not less than the upper bound results in an `EvalexprError::CustomMessage`.
+fn wrap_into_range<NumericTypes: EvalexprNumericTypes>(
+    argument: &Value<NumericTypes>,
+) -> EvalexprResultValue<NumericTypes> {
+    let arguments = argument.as_fixed_len_tuple(3)?;
+    if let [Value::Int(x), Value::Int(lo), Value::Int(hi)] = arguments.as_slice() {
+        if lo >= hi {
+            return Err(wrap_range_error(lo, hi));
+        }
+        if x >= lo && x < hi {
+            return Ok(Value::Int(x.clone()));
+        }
+        let Ok(width) = hi.checked_sub(lo) else {
+            // A range that is wider than the largest integer is less than one width away from any integer outside of it,
+            // so wrapping around once suffices, and none of the differences overflow.
+            return Ok(Value::Int(if x >= hi {
+                lo.checked_add(&x.checked_sub(hi)?)?
+            } else {
+                hi.checked_sub(&lo.checked_sub(x)?)?
+            }));
+        };
+        // Both x and the lower bound are reduced modulo the width first, such that their difference cannot overflow.
+        let zero = NumericTypes::Int::from_usize(0)?;
+        let euclidean_rem = |int: &NumericTypes::Int| {
+            let remainder = int.checked_rem(&width)?;
+            if remainder < zero {
+                remainder.checked_add(&width)
+            } else {
+                Ok(remainder)
+            }
+        };
+        let mut offset = euclidean_rem(x)?.checked_sub(&euclidean_rem(lo)?)?;
+        if offset < zero {
+            offset = offset.checked_add(&width)?;
+        }
+        return Ok(Value::Int(lo.checked_add(&offset)?));
+    }
+
+    let (x, lo, hi) = (
+        arguments[0].as_number()?,
+        arguments[1].as_number()?,
+        arguments[2].as_number()?,
+    );
+    if let Some(nan) = [&x, &lo, &hi].into_iter().find(|number| number.is_nan()) {
+        return Ok(Value::Float(nan.clone()));
+    }
+    if lo >= hi {
+        return Err(wrap_range_error(&lo, &hi));
+    }
+    let width = hi.clone() - lo.clone();
+    let mut offset = (x - lo.clone()) % width.clone();
+    if offset < float_constant::<NumericTypes>(0)? {
+        offset = offset + width;
+    }
+    let wrapped = lo.clone() + offset;
+    // Adding a tiny negative offset to the width may round up to the width itself.
+    Ok(Value::Float(if wrapped < hi { wrapped } else { lo }))
+}
+
+/// Constructs the error for a `math::wrap` range whose lower bound is not less than its upper bound.
+fn wrap_range_error<NumericTypes: EvalexprNumericTypes>(
+    lo: &impl Display,
+    hi: &impl Display,
+) -> EvalexprError<NumericTypes> {
+    EvalexprError::CustomMessage(format!(
+        "math::wrap requires the lower bound {} to be less than the upper bound {}",
+        lo, hi
+    ))
+}
+
+/// Returns the given string with its first character in upper case and all other characters in lower case.
+fn capitalize(subject: &str) -> String {
+    let mut characters = subject.chars();
+    match characters.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(characters.as_str().to_lowercase().chars())
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Constructs a function that pads a string with spaces to the amount of characters given as second argument.
+/// The given closure computes the amount of spaces put on the left from the total amount of padding, the rest is put on the right.
+/// Strings that are at least as long as the given width are returned unchanged.
+fn pad_to_width<NumericTypes: EvalexprNumericTypes>(
+    name: &'static str,
+    left_padding: fn(usize) -> usize,
+) -> Option<Function<NumericTypes>> {
+    Some(Function::new(move |argument: &Value<NumericTypes>| {
+        let arguments = argument.as_fixed_len_tuple(2)?;
+        let subject = arguments[0].as_string()?;
+        let width: NumericTypes::Int = arguments[1].as_int()?;
+        let padding = width.into_usize()?.saturating_sub(subject.chars().count());
+        let length = checked_string_length(name, subject.len().checked_add(padding))?;
+        let left = left_padding(padding);
+
+        let mut result = String::with_capacity(length);
+        result.extend(std::iter::repeat(' ').take(left));
+        result.push_str(&subject);
+        result.extend(std::iter::repeat(' ').take(padding - left));
+        Ok(Value::String(result))
+    }))
+}
+
+/// Prefixes each non-empty line of the given string with the given amount of spaces.
+/// An indentation or an indented string longer than `MAX_BUILTIN_STRING_LENGTH` results in an `EvalexprError::CustomMessage`.
+fn indent<NumericTypes: EvalexprNumericTypes>(
+    subject: &str,
+    width: usize,
+) -> EvalexprResult<String, NumericTypes> {
+    checked_string_length("str::indent", Some(width))?;
+    let indented_lines = subject.split('\n').filter(|line| !line.is_empty()).count();
+    checked_string_length(
+        "str::indent",
+        width
+            .checked_mul(indented_lines)
+            .and_then(|indentation| indentation.checked_add(subject.len())),
+    )?;
+
+    let indentation = " ".repeat(width);
+    Ok(subject
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indentation, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Wraps each line of the given string at word boundaries, such that the wrapped lines are at most `width` characters long.
+///
+/// Words are separated by whitespace, which is replaced by a single space or a newline.
+/// Words that are longer than `width` are not broken, but put on a line of their own.
+fn wrap(subject: &str, width: usize) -> String {
+    let mut result = String::with_capacity(subject.len());
+    for (index, line) in subject.split('\n').enumerate() {
+        if index > 0 {
+            result.push('\n');
+        }
+
+        let mut line_width = 0;
+        for word in line.split_whitespace() {
+            let word_width = word.chars().count();
+            if line_width == 0 {
+                line_width = word_width;
+            } else if line_width + 1 + word_width <= width {
+                result.push(' ');
+                line_width += 1 + word_width;
+            } else {
+                result.push('\n');
+                line_width = word_width;
+            }
+            result.push_str(word);
+        }
+    }
+    result
+}
+
+/// Percent-encodes all bytes of the given string except for the unreserved characters of RFC 3986,
+/// i.e. ASCII letters, digits, `-`, `.`, `_` and `~`.
+fn url_encode(subject: &str) -> String {
+    let mut result = String::with_capacity(subject.len());
+    for byte in subject.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(char::from(byte));
+        } else {
+            result.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    result
+}
+
+/// Decodes the percent-encoded bytes of the given string.
+/// Fails if a `%` is not followed by two hexadecimal digits, or if the decoded bytes are not valid UTF-8.
+fn url_decode<NumericTypes: EvalexprNumericTypes>(
+    subject: &str,
+) -> EvalexprResult<String, NumericTypes> {
+    let mut bytes = Vec::with_capacity(subject.len());
+    let mut iter = subject.bytes().enumerate();
+    while let Some((index, byte)) = iter.next() {
+        if byte == b'%' {
+            let byte = subject
+                .get(index + 1..index + 3)
+                .filter(|hex| hex.bytes().all(|digit| digit.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    EvalexprError::CustomMessage(format!(
+                        "url::decode found a malformed percent-encoding at byte {} of {:?}",
+                        index, subject
+                    ))
+                })?;
+            bytes.push(byte);
+            iter.nth(1);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the UTF-8 bytes of the given string with the standard base64 alphabet of RFC 4648, padding the result with `=`.
+fn base64_encode(subject: &str) -> String {
+    let mut result = String::with_capacity((subject.len() + 2) / 3 * 4);
+    for chunk in subject.as_bytes().chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                result.push(char::from(
+                    BASE64_ALPHABET[(bits >> (18 - 6 * index)) as usize & 0x3f],
+                ));
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Decodes the given string from the standard base64 alphabet of RFC 4648.
+/// Fails if the string is not padded to a multiple of four characters, contains characters outside of the alphabet,
+/// or if the decoded bytes are not valid UTF-8.
+fn base64_decode<NumericTypes: EvalexprNumericTypes>(
+    subject: &str,
+) -> EvalexprResult<String, NumericTypes> {
+    let invalid = |index: usize| {
+        EvalexprError::CustomMessage(format!(
+            "base64::decode found invalid base64 at byte {} of {:?}",
+            index, subject
+        ))
+    };
+    if subject.len() % 4 != 0 {
+        return Err(invalid(subject.len()));
+    }
+
+    let input = subject.as_bytes();
+    let padding = input
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|byte| **byte == b'=')
+        .count();
+    let mut bytes = Vec::with_capacity(input.len() / 4 * 3);
+    for (chunk_index, chunk) in input[..input.len() - padding].chunks(4).enumerate() {
+        let mut bits = 0u32;
+        for (index, byte) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET
+                .iter()
+                .position(|digit| digit == byte)
+                .ok_or_else(|| invalid(chunk_index * 4 + index))?;
+            bits |= (value as u32) << (18 - 6 * index);
+        }
+        // Each character holds six bits, so a chunk of n characters holds n - 1 whole bytes.
+        bytes.extend(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Computes the CRC-32 checksum of the UTF-8 bytes of the given string, as used e.g. by zlib and PNG.
+fn crc32(subject: &str) -> u32 {
+    let mut crc = !0u32;
+    for byte in subject.bytes() {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            // The reversed representation of the polynomial 0x04C11DB7.
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Formats a Unix timestamp in seconds as ISO-8601 date and time in UTC, e.g. `2023-11-14T22:13:20Z`.
+#[cfg(feature = "chrono")]
+fn format_iso_timestamp<NumericTypes: EvalexprNumericTypes>(
+    timestamp: &NumericTypes::Int,
+) -> EvalexprResult<String, NumericTypes> {
+    timestamp
+        .to_string()
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map(|date_time| date_time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or_else(|| {
+            EvalexprError::CustomMessage(format!(
+                "time::format_iso expected a timestamp within the supported range of dates, but got {}",
+                timestamp
+            ))
+        })
+}
+
+/// Parses a duration like `1h30m` into its total amount of seconds.
+/// A duration consists of one or more non-negative integers, each directly followed by one of the units `d`, `h`, `m` or `s`.
+fn parse_duration<NumericTypes: EvalexprNumericTypes>(
+    duration: &str,
+) -> EvalexprResult<NumericTypes::Int, NumericTypes> {
+    let invalid_duration = || {
+        EvalexprError::CustomMessage(format!(
+            "duration::parse expected a duration like \"1h30m\", but got {:?}",
+            duration
+        ))
+    };
+    if duration.is_empty() {
+        return Err(invalid_duration());
+    }
+
+    let mut seconds = NumericTypes::Int::from_usize(0)?;
+    let mut remaining = duration;
+    while !remaining.is_empty() {
+        let digits_len = remaining
+            .find(|character: char| !character.is_ascii_digit())
+            .unwrap_or(remaining.len());
+        let (digits, unit) = remaining.split_at(digits_len);
+        let mut unit = unit.chars();
+        let unit_seconds = match unit.next() {
+            Some('d') => 86_400,
+            Some('h') => 3_600,
+            Some('m') => 60,
+            Some('s') => 1,
+            _ => return Err(invalid_duration()),
+        };
+        let amount: NumericTypes::Int = digits.parse().map_err(|_| invalid_duration())?;
+        seconds = seconds
+            .checked_add(&amount.checked_mul(&NumericTypes::Int::from_usize(unit_seconds)?)?)?;
+        remaining = unit.as_str();
+    }
+    Ok(seconds)
+}
+
+/// Formats the given bytes as lowercase hexadecimal string.
+#[cfg(feature = "hash")]
+fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        result.push_str(&format!("{:02x}", byte));
+    }
+    result
+}
+
+/// Converts the elements of the given tuple into floats.
+/// Fails if an element is not a number, or if the tuple has less than `min_len` elements.
+fn tuple_as_floats<NumericTypes: EvalexprNumericTypes>(
+    argument: &Value<NumericTypes>,
+    min_len: usize,
+) -> EvalexprResult<Vec<NumericTypes::Float>, NumericTypes> {
+    let tuple = argument.as_tuple()?;
+    if tuple.len() < min_len {
+        return Err(EvalexprError::wrong_function_argument_amount_range(
+            tuple.len(),
+            min_len..=usize::MAX,
+        ));
+    }
+    tuple.iter().map(Value::as_number).collect()
+}
+
+/// Computes the arithmetic mean of the given non-empty slice of numbers.
+fn mean<NumericTypes: EvalexprNumericTypes>(
+    numbers: &[NumericTypes::Float],
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    let sum = numbers
+        .iter()
+        .cloned()
+        .fold(float_constant::<NumericTypes>(0)?, |sum, number| {
+            sum + number
+        });
+    Ok(sum / float_constant::<NumericTypes>(numbers.len())?)
+}
+
+/// Sorts the given numbers in ascending order.
+/// If one of the numbers is NaN, they are not sorted and the NaN is returned instead.
+fn sort_numbers<NumericTypes: EvalexprNumericTypes>(
+    numbers: &mut [NumericTypes::Float],
+) -> Option<NumericTypes::Float> {
+    if let Some(nan) = numbers.iter().find(|number| number.is_nan()) {
+        return Some(nan.clone());
+    }
+
+    numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    None
+}
+
+/// Computes the median of the given non-empty vector of numbers, which is the mean of the two middle numbers if their amount is even.
+/// If one of the numbers is NaN, the result is NaN.
+fn median<NumericTypes: EvalexprNumericTypes>(
+    mut numbers: Vec<NumericTypes::Float>,
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    if let Some(nan) = sort_numbers::<NumericTypes>(&mut numbers) {
+        return Ok(nan);
+    }
+
+    let middle = numbers.len() / 2;
+    if numbers.len() % 2 == 1 {
+        Ok(numbers[middle].clone())
+    } else {
+        Ok((numbers[middle - 1].clone() + numbers[middle].clone())
+            / float_constant::<NumericTypes>(2)?)
+    }
+}
+
+/// Computes the `percentile`-th percentile of the given non-empty vector of numbers,
+/// interpolating linearly between the two closest ranks.
+/// If one of the numbers is NaN, the result is NaN.
+fn percentile<NumericTypes: EvalexprNumericTypes>(
+    mut numbers: Vec<NumericTypes::Float>,
+    percentile: NumericTypes::Float,
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    let hundred = float_constant::<NumericTypes>(100)?;
+    if !(percentile >= float_constant::<NumericTypes>(0)? && percentile <= hundred) {
+        return Err(EvalexprError::CustomMessage(format!(
+            "stats::percentile requires a percentile between 0 and 100, but got {}",
+            percentile
+        )));
+    }
+    if let Some(nan) = sort_numbers::<NumericTypes>(&mut numbers) {
+        return Ok(nan);
+    }
+
+    let rank = percentile / hundred * float_constant::<NumericTypes>(numbers.len() - 1)?;
+    let lower_rank = rank.floor();
+    let lower_index = NumericTypes::float_as_int(&lower_rank).into_usize()?;
+    let lower = numbers[lower_index].clone();
+    if let Some(upper) = numbers.get(lower_index + 1) {
+        Ok(lower.clone() + (upper.clone() - lower) * (rank - lower_rank))
+    } else {
+        Ok(lower)
+    }
+}
+
+/// Computes the sample variance of the given slice of at least two numbers.
+fn variance<NumericTypes: EvalexprNumericTypes>(
+    numbers: &[NumericTypes::Float],
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    let mean = mean(numbers)?;
+    let sum_of_squares =
+        numbers
+            .iter()
+            .cloned()
+            .fold(float_constant::<NumericTypes>(0)?, |sum, number| {
+                let deviation = number - mean.clone();
+                sum + deviation.clone() * deviation
+            });
+    Ok(sum_of_squares / float_constant::<NumericTypes>(numbers.len() - 1)?)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Extremum {
+    Min,
+    Max,
+}
+
+/// How NaN arguments are treated when computing the minimum or maximum of numbers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NanPolicy {
+    /// If any argument is NaN, the result is NaN.
+    Propagate,
+    /// NaN arguments are ignored, unless all arguments are NaN.
+    Ignore,
+}
+
+/// Returns the index of the smallest or largest of the numbers in the given tuple, or the first such index if there are multiple.
+/// Two integers are compared as integers, and all other pairs of numbers as floats.
+/// NaN elements are ignored unless all elements are NaN, in which case the first index is returned.
+fn arg_extremum<NumericTypes: EvalexprNumericTypes>(
+    name: &str,
+    argument: &Value<NumericTypes>,
+    extremum: Extremum,
+) -> EvalexprResultValue<NumericTypes> {
+    let tuple = argument.as_tuple()?;
+    let mut result: Option<(usize, &Value<NumericTypes>)> = None;
+    for (index, element) in tuple.iter().enumerate() {
+        let number = element.as_number()?;
+        let is_extremum = match result {
+            None => true,
+            Some((_, Value::Int(current))) if matches!(element, Value::Int(_)) => {
+                let int = element.as_int()?;
+                match extremum {
+                    Extremum::Min => int < *current,
+                    Extremum::Max => int > *current,
+                }
+            },
+            Some((_, current)) => {
+                let current = current.as_number()?;
+                if current.is_nan() {
+                    !number.is_nan()
+                } else {
+                    match extremum {
+                        Extremum::Min => number < current,
+                        Extremum::Max => number > current,
+                    }
+                }
+            },
+        };
+        if is_extremum {
+            result = Some((index, element));
+        }
+    }
+    let (index, _) = result.ok_or_else(|| empty_tuple_error(name))?;
+    Ok(Value::Int(NumericTypes::Int::from_usize(index)?))
+}
+
+/// Constructs the error for a builtin function with the given name that requires a non-empty tuple, but got an empty one.
+fn empty_tuple_error<NumericTypes: EvalexprNumericTypes>(
+    name: &str,
+) -> EvalexprError<NumericTypes> {
+    EvalexprError::CustomMessage(format!("{} requires a non-empty tuple", name))
+}
+
+/// Computes the minimum or maximum of the numbers in the given tuple.
+///
+/// The result is an integer if the extremum is an integer argument, and a float otherwise.
+/// If an integer and a float argument are equal, the float is returned.
+fn extremum<NumericTypes: EvalexprNumericTypes>(
+    name: &str,
+    argument: &Value<NumericTypes>,
+    extremum: Extremum,
+    nan_policy: NanPolicy,
+) -> EvalexprResultValue<NumericTypes> {
+    let arguments = argument.as_tuple()?;
+    let mut extremum_int: Option<NumericTypes::Int> = None;
+    let mut extremum_float: Option<NumericTypes::Float> = None;
+    let mut nan = None;
+
+    for argument in arguments {
+        match argument {
+            Value::Float(float) if float.is_nan() => nan = Some(float),
+            Value::Float(float) => {
+                extremum_float = Some(match (extremum_float, extremum) {
+                    (None, _) => float,
+                    (Some(current), Extremum::Min) => current.min(&float),
+                    (Some(current), Extremum::Max) => current.max(&float),
+                })
+            },
+            Value::Int(int) => {
+                extremum_int = Some(match (extremum_int, extremum) {
+                    (None, _) => int,
+                    (Some(current), Extremum::Min) => current.min(int),
+                    (Some(current), Extremum::Max) => current.max(int),
+                })
+            },
+            argument => return Err(EvalexprError::expected_number(argument)),
+        }
+    }
+
+    match (nan, nan_policy, extremum_int, extremum_float) {
+        (Some(nan), NanPolicy::Propagate, _, _) | (Some(nan), _, None, None) => {
+            Ok(Value::Float(nan))
+        },
+        (_, _, Some(int), Some(float)) => {
+            let int_as_float = NumericTypes::int_as_float(&int);
+            let int_is_extremum = match extremum {
+                Extremum::Min => int_as_float < float,
+                Extremum::Max => int_as_float > float,
+            };
+
+            if int_is_extremum {
+                Ok(Value::Int(int))
+            } else {
+                Ok(Value::Float(float))
+            }
+        },
+        (_, _, Some(int), None) => Ok(Value::Int(int)),
+        (_, _, None, Some(float)) => Ok(Value::Float(float)),
+        (None, _, None, None) => Err(empty_tuple_error(name)),
+    }
+}
+
 macro_rules! int_function {
     ($func:ident) => {
         Some(Function::new(|argument| {
@@ -45,15 +909,163 @@ macro_rules! int_function {
             Ok(Value::Int(a.$func(&b)))
         }))
     };
+    ($func:ident, 2, fallible) => {
+        Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(2)?;
+            let (a, b): (NumericTypes::Int, NumericTypes::Int) =
+                (tuple[0].as_int()?, tuple[1].as_int()?);
+            a.$func(&b).map(Value::Int)
+        }))
+    };
 }
 
+/// The identifiers of all builtin functions, used to suggest similar identifiers for unknown functions.
+/// Every identifier here must be resolved by [`builtin_function`].
+pub(crate) const BUILTIN_FUNCTION_IDENTIFIERS: &[&str] = &[
+    "math::ln",
+    "math::log",
+    "math::log2",
+    "math::log10",
+    "math::exp",
+    "math::exp2",
+    "math::pow",
+    "math::cos",
+    "math::acos",
+    "math::cosh",
+    "math::acosh",
+    "math::sin",
+    "math::asin",
+    "math::sinh",
+    "math::asinh",
+    "math::tan",
+    "math::atan",
+    "math::tanh",
+    "math::atanh",
+    "math::atan2",
+    "math::sqrt",
+    "math::cbrt",
+    "math::hypot",
+    "floor",
+    "round",
+    "ceil",
+    "round_to",
+    "floor_to",
+    "ceil_to",
+    "math::is_nan",
+    "math::is_finite",
+    "math::is_infinite",
+    "math::is_normal",
+    "math::int_min",
+    "math::int_max",
+    "math::float_min",
+    "math::float_max",
+    "math::inf",
+    "math::nan",
+    "math::abs",
+    "math::normalize",
+    "math::wrap",
+    "math::clamp01",
+    "math::gamma",
+    "stats::mean",
+    "stats::weighted_mean",
+    "stats::median",
+    "stats::percentile",
+    "stats::variance",
+    "stats::stdev",
+    "stats::zscore",
+    "stats::sma",
+    "stats::ema",
+    "finance::percent_of",
+    "finance::pct_change",
+    "finance::bps",
+    "finance::compound",
+    "typeof",
+    "is_valid",
+    "strict_eq",
+    "min",
+    "max",
+    "math::fmin",
+    "math::fmax",
+    "argmin",
+    "argmax",
+    "if",
+    "contains",
+    "contains_any",
+    "in",
+    "len",
+    "tuple::len",
+    "tuple::count",
+    "with_index",
+    "slice",
+    "zip",
+    "enumerate",
+    "flatten",
+    "chunk",
+    "lookup",
+    "nearest",
+    "clamp_int",
+    #[cfg(feature = "regex")]
+    "str::regex_matches",
+    #[cfg(feature = "regex")]
+    "str::regex_replace",
+    "str::to_lowercase",
+    "str::to_uppercase",
+    "str::capitalize",
+    "str::title_case",
+    "str::trim",
+    "str::trim_matches",
+    "str::trim_start_matches",
+    "str::trim_end_matches",
+    "str::from",
+    "str::substring",
+    "substring",
+    "str::char_at",
+    "str::len",
+    "str::split_once",
+    "str::count",
+    "str::repeat",
+    "str::indent",
+    "str::wrap",
+    "str::ljust",
+    "str::rjust",
+    "str::center",
+    "str::hex",
+    "str::oct",
+    "str::bin",
+    "str::si",
+    "str::percent",
+    "str::similarity",
+    "url::encode",
+    "url::decode",
+    "base64::encode",
+    "base64::decode",
+    "hash::crc32",
+    #[cfg(feature = "hash")]
+    "hash::md5",
+    #[cfg(feature = "hash")]
+    "hash::sha256",
+    "duration::parse",
+    "time::in_range",
+    "time::add_days",
+    #[cfg(feature = "chrono")]
+    "time::format_iso",
+    #[cfg(feature = "rand")]
+    "random",
+    "bitand",
+    "bitor",
+    "bitxor",
+    "bitnot",
+    "shl",
+    "shr",
+];
+
 pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
     identifier: &str,
 ) -> Option<Function<NumericTypes>> {
     match identifier {
         // Log
         "math::ln" => simple_math!(ln),
-        "math::log" => simple_math!(log, 2),
+        "math::log" => Some(Function::new(logarithm)),
         "math::log2" => simple_math!(log2),
         "math::log10" => simple_math!(log10),
         // Exp
@@ -86,11 +1098,21 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
         "floor" => simple_math!(floor),
         "round" => simple_math!(round),
         "ceil" => simple_math!(ceil),
+        "round_to" => round_to_digits("round_to", NumericTypes::Float::round),
+        "floor_to" => round_to_digits("floor_to", NumericTypes::Float::floor),
+        "ceil_to" => round_to_digits("ceil_to", NumericTypes::Float::ceil),
         // Float special values
         "math::is_nan" => float_is(NumericTypes::Float::is_nan),
         "math::is_finite" => float_is(NumericTypes::Float::is_finite),
         "math::is_infinite" => float_is(NumericTypes::Float::is_infinite),
         "math::is_normal" => float_is(NumericTypes::Float::is_normal),
+        // Numeric limits
+        "math::int_min" => constant(|| Value::Int(NumericTypes::Int::MIN)),
+        "math::int_max" => constant(|| Value::Int(NumericTypes::Int::MAX)),
+        "math::float_min" => constant(|| Value::Float(NumericTypes::Float::MIN_FINITE)),
+        "math::float_max" => constant(|| Value::Float(NumericTypes::Float::MAX_FINITE)),
+        "math::inf" => constant(|| Value::Float(NumericTypes::Float::INFINITY)),
+        "math::nan" => constant(|| Value::Float(NumericTypes::Float::NAN)),
         // Absolute value
         "math::abs" => Some(Function::new(|argument| match argument {
             Value::Float(num) => Ok(Value::Float(
@@ -101,6 +1123,177 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
             )),
             _ => Err(EvalexprError::expected_number(argument.clone())),
         })),
+        "math::normalize" => Some(Function::new(|argument: &Value<NumericTypes>| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let (x, lo, hi) = (
+                arguments[0].as_number()?,
+                arguments[1].as_number()?,
+                arguments[2].as_number()?,
+            );
+            if lo == hi {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "math::normalize requires a non-empty range, but the lower and upper bound are both {}",
+                    lo
+                )));
+            }
+            let clamped = x.max(&lo.min(&hi)).min(&lo.max(&hi));
+            Ok(Value::Float((clamped - lo.clone()) / (hi - lo)))
+        })),
+        "math::wrap" => Some(Function::new(wrap_into_range)),
+        "math::clamp01" => Some(Function::new(|argument| {
+            Ok(Value::Float(clamp01(argument.as_number()?)?))
+        })),
+        "math::gamma" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (x, gamma) = (arguments[0].as_number()?, arguments[1].as_number()?);
+            if gamma == float_constant::<NumericTypes>(0)? {
+                return Err(EvalexprError::CustomMessage(
+                    "math::gamma is undefined for a gamma of zero".to_string(),
+                ));
+            }
+            Ok(Value::Float(
+                clamp01(x)?.pow(&(float_constant::<NumericTypes>(1)? / gamma)),
+            ))
+        })),
+        // Statistics
+        "stats::mean" => Some(Function::new(|argument| {
+            Ok(Value::Float(mean(&tuple_as_floats(argument, 1)?)?))
+        })),
+        "stats::weighted_mean" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let values = tuple_as_floats(&arguments[0], 1)?;
+            let weights = tuple_as_floats(
+                &Value::Tuple(arguments[1].as_fixed_len_tuple(values.len())?),
+                1,
+            )?;
+            let zero = float_constant::<NumericTypes>(0)?;
+            let total_weight = weights
+                .iter()
+                .cloned()
+                .fold(zero.clone(), |sum, weight| sum + weight);
+            if total_weight == zero {
+                return Err(EvalexprError::CustomMessage(
+                    "stats::weighted_mean is undefined for a total weight of zero".to_string(),
+                ));
+            }
+            let weighted_sum = values
+                .into_iter()
+                .zip(weights)
+                .fold(zero, |sum, (value, weight)| sum + value * weight);
+            Ok(Value::Float(weighted_sum / total_weight))
+        })),
+        "stats::median" => Some(Function::new(|argument| {
+            Ok(Value::Float(median(tuple_as_floats(argument, 1)?)?))
+        })),
+        "stats::percentile" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            Ok(Value::Float(percentile(
+                tuple_as_floats(&arguments[0], 1)?,
+                arguments[1].as_number()?,
+            )?))
+        })),
+        "stats::variance" => Some(Function::new(|argument| {
+            Ok(Value::Float(variance(&tuple_as_floats(argument, 2)?)?))
+        })),
+        "stats::stdev" => Some(Function::new(|argument| {
+            Ok(Value::Float(
+                variance::<NumericTypes>(&tuple_as_floats(argument, 2)?)?.sqrt(),
+            ))
+        })),
+        "stats::zscore" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let (value, mean, stdev) = (
+                arguments[0].as_number()?,
+                arguments[1].as_number()?,
+                arguments[2].as_number()?,
+            );
+            if stdev == float_constant::<NumericTypes>(0)? {
+                return Err(EvalexprError::CustomMessage(
+                    "stats::zscore is undefined for a standard deviation of zero".to_string(),
+                ));
+            }
+            Ok(Value::Float((value - mean) / stdev))
+        })),
+        "stats::sma" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let numbers = tuple_as_floats(&arguments[0], 1)?;
+            let window: NumericTypes::Int = arguments[1].as_int()?;
+            let window = window
+                .into_usize()
+                .ok()
+                .filter(|window| (1..=numbers.len()).contains(window))
+                .ok_or_else(|| {
+                    EvalexprError::CustomMessage(format!(
+                        "stats::sma requires a window between 1 and the length {} of the tuple, but got {}",
+                        numbers.len(),
+                        window
+                    ))
+                })?;
+            numbers
+                .windows(window)
+                .map(|window| mean(window).map(Value::Float))
+                .collect::<EvalexprResult<_, _>>()
+                .map(Value::Tuple)
+        })),
+        "stats::ema" => Some(Function::new(|argument: &Value<NumericTypes>| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let numbers = tuple_as_floats(&arguments[0], 1)?;
+            let alpha = arguments[1].as_number()?;
+            let one = float_constant::<NumericTypes>(1)?;
+            if !(alpha > float_constant::<NumericTypes>(0)? && alpha <= one) {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "stats::ema requires a smoothing factor greater than 0 and at most 1, but got {}",
+                    alpha
+                )));
+            }
+
+            let mut average = numbers[0].clone();
+            let mut averages = Vec::with_capacity(numbers.len());
+            averages.push(Value::Float(average.clone()));
+            for number in numbers.into_iter().skip(1) {
+                average = alpha.clone() * number + (one.clone() - alpha.clone()) * average;
+                averages.push(Value::Float(average.clone()));
+            }
+            Ok(Value::Tuple(averages))
+        })),
+        // Finance
+        "finance::percent_of" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (value, percent) = (arguments[0].as_number()?, arguments[1].as_number()?);
+            Ok(Value::Float(
+                value * percent / float_constant::<NumericTypes>(100)?,
+            ))
+        })),
+        "finance::pct_change" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (old, new) = (arguments[0].as_number()?, arguments[1].as_number()?);
+            if old == float_constant::<NumericTypes>(0)? {
+                return Err(EvalexprError::CustomMessage(
+                    "finance::pct_change is undefined for an old value of zero".to_string(),
+                ));
+            }
+            Ok(Value::Float(
+                (new - old.clone()) / old * float_constant::<NumericTypes>(100)?,
+            ))
+        })),
+        "finance::bps" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (value, basis_points) = (arguments[0].as_number()?, arguments[1].as_number()?);
+            Ok(Value::Float(
+                value * basis_points / float_constant::<NumericTypes>(10_000)?,
+            ))
+        })),
+        "finance::compound" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let (principal, rate, periods) = (
+                arguments[0].as_number()?,
+                arguments[1].as_number()?,
+                arguments[2].as_number()?,
+            );
+            Ok(Value::Float(
+                principal * (float_constant::<NumericTypes>(1)? + rate).pow(&periods),
+            ))
+        })),
         // Other
         "typeof" => Some(Function::new(move |argument| {
             Ok(match argument {
@@ -109,53 +1302,40 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
                 Value::Int(_) => "int",
                 Value::Boolean(_) => "boolean",
                 Value::Tuple(_) => "tuple",
+                Value::Array(_) => "array",
                 Value::Empty => "empty",
             }
             .into())
         })),
+        "is_valid" => Some(Function::new(|argument: &Value<NumericTypes>| {
+            Ok(Value::Boolean(match argument {
+                Value::Float(float) => float.is_finite(),
+                Value::Int(_) => true,
+                _ => false,
+            }))
+        })),
+        "strict_eq" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            // Values of different types are never equal, even if they represent the same number.
+            Ok(Value::Boolean(arguments[0] == arguments[1]))
+        })),
         "min" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut min_int = NumericTypes::Int::MAX;
-            let mut min_float = NumericTypes::Float::MAX;
-            debug_assert!(min_float.is_infinite());
-
-            for argument in arguments {
-                if let Value::Float(float) = argument {
-                    min_float = min_float.min(&float);
-                } else if let Value::Int(int) = argument {
-                    min_int = min_int.min(int);
-                } else {
-                    return Err(EvalexprError::expected_number(argument));
-                }
-            }
-
-            if (NumericTypes::int_as_float(&min_int)) < min_float {
-                Ok(Value::Int(min_int))
-            } else {
-                Ok(Value::Float(min_float))
-            }
+            extremum("min", argument, Extremum::Min, NanPolicy::Propagate)
         })),
         "max" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut max_int = NumericTypes::Int::MIN;
-            let mut max_float = NumericTypes::Float::MIN;
-            debug_assert!(max_float.is_infinite());
-
-            for argument in arguments {
-                if let Value::Float(float) = argument {
-                    max_float = max_float.max(&float);
-                } else if let Value::Int(int) = argument {
-                    max_int = max_int.max(int);
-                } else {
-                    return Err(EvalexprError::expected_number(argument));
-                }
-            }
-
-            if (NumericTypes::int_as_float(&max_int)) > max_float {
-                Ok(Value::Int(max_int))
-            } else {
-                Ok(Value::Float(max_float))
-            }
+            extremum("max", argument, Extremum::Max, NanPolicy::Propagate)
+        })),
+        "math::fmin" => Some(Function::new(|argument| {
+            extremum("math::fmin", argument, Extremum::Min, NanPolicy::Ignore)
+        })),
+        "math::fmax" => Some(Function::new(|argument| {
+            extremum("math::fmax", argument, Extremum::Max, NanPolicy::Ignore)
+        })),
+        "argmin" => Some(Function::new(|argument| {
+            arg_extremum("argmin", argument, Extremum::Min)
+        })),
+        "argmax" => Some(Function::new(|argument| {
+            arg_extremum("argmax", argument, Extremum::Max)
         })),
         "if" => Some(Function::new(|argument| {
             let mut arguments = argument.as_fixed_len_tuple(3)?;
@@ -216,18 +1396,167 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
                 Err(EvalexprError::expected_tuple(arguments[0].clone()))
             }
         })),
+        "in" => Some(Function::new(|argument| {
+            let arguments = argument.as_ranged_len_tuple(2..=usize::MAX)?;
+            Ok(Value::Boolean(
+                arguments[1..]
+                    .iter()
+                    .any(|element| values_equal(element, &arguments[0])),
+            ))
+        })),
         "len" => Some(Function::new(|argument| {
             if let Ok(subject) = argument.as_string() {
                 Ok(Value::Int(NumericTypes::Int::from_usize(subject.len())?))
             } else if let Ok(subject) = argument.as_tuple() {
                 Ok(Value::Int(NumericTypes::Int::from_usize(subject.len())?))
+            } else if let Ok(subject) = argument.as_array() {
+                Ok(Value::Int(NumericTypes::Int::from_usize(subject.len())?))
             } else {
                 Err(EvalexprError::type_error(
                     argument.clone(),
-                    vec![ValueType::String, ValueType::Tuple],
+                    vec![ValueType::String, ValueType::Tuple, ValueType::Array],
                 ))
             }
         })),
+        "tuple::len" => Some(Function::new(|argument| {
+            let tuple = argument.as_tuple()?;
+            Ok(Value::Int(NumericTypes::Int::from_usize(tuple.len())?))
+        })),
+        "tuple::count" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let tuple = arguments[0].as_tuple()?;
+            let count = tuple
+                .iter()
+                .filter(|element| values_equal(element, &arguments[1]))
+                .count();
+            Ok(Value::Int(NumericTypes::Int::from_usize(count)?))
+        })),
+        "with_index" => Some(Function::new(|argument| {
+            let mut arguments = argument.as_fixed_len_tuple(3)?;
+            let mut tuple = arguments[0].as_tuple()?;
+            let index: NumericTypes::Int = arguments[1].as_int()?;
+            let index = index
+                .into_usize()
+                .map_err(|_| EvalexprError::OutOfBoundsAccess)?;
+            let element = tuple
+                .get_mut(index)
+                .ok_or(EvalexprError::OutOfBoundsAccess)?;
+            *element = arguments.swap_remove(2);
+            Ok(Value::Tuple(tuple))
+        })),
+        "slice" => Some(Function::new(|argument| {
+            let arguments = argument.as_ranged_len_tuple(2..=3)?;
+            let tuple = arguments[0].as_tuple()?;
+            let start = clamped_slice_index::<NumericTypes>(&arguments[1].as_int()?, tuple.len());
+            let end = if let Some(end) = arguments.get(2) {
+                clamped_slice_index::<NumericTypes>(&end.as_int()?, tuple.len())
+            } else {
+                tuple.len()
+            };
+            Ok(Value::Tuple(
+                tuple
+                    .get(start..end.max(start))
+                    .unwrap_or_default()
+                    .to_vec(),
+            ))
+        })),
+        "zip" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let first = arguments[0].as_tuple()?;
+            let second = arguments[1].as_fixed_len_tuple(first.len())?;
+            Ok(Value::Tuple(
+                first
+                    .into_iter()
+                    .zip(second)
+                    .map(|(a, b)| Value::Tuple(vec![a, b]))
+                    .collect(),
+            ))
+        })),
+        "enumerate" => Some(Function::new(|argument| {
+            let tuple = argument.as_tuple()?;
+            tuple
+                .into_iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    Ok(Value::Tuple(vec![
+                        Value::Int(NumericTypes::Int::from_usize(index)?),
+                        element,
+                    ]))
+                })
+                .collect::<EvalexprResult<_, NumericTypes>>()
+                .map(Value::Tuple)
+        })),
+        "flatten" => Some(Function::new(|argument| {
+            let mut flattened = Vec::new();
+            flatten_into(argument.as_tuple()?, &mut flattened);
+            Ok(Value::Tuple(flattened))
+        })),
+        "chunk" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let tuple = arguments[0].as_tuple()?;
+            let size: NumericTypes::Int = arguments[1].as_int()?;
+            let chunk_size = size
+                .into_usize()
+                .ok()
+                .filter(|chunk_size| *chunk_size > 0)
+                .ok_or_else(|| {
+                    EvalexprError::CustomMessage(format!(
+                        "chunk requires a positive chunk size, but got {}",
+                        size
+                    ))
+                })?;
+            Ok(Value::Tuple(
+                tuple
+                    .chunks(chunk_size)
+                    .map(|chunk| Value::Tuple(chunk.to_vec()))
+                    .collect(),
+            ))
+        })),
+        "nearest" => Some(Function::new(|argument: &Value<NumericTypes>| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let value = arguments[0].as_number()?;
+            let options = arguments[1].as_tuple()?;
+
+            let mut nearest: Option<(&Value<NumericTypes>, NumericTypes::Float)> = None;
+            for option in &options {
+                let distance = (option.as_number()? - value.clone()).abs();
+                if nearest
+                    .as_ref()
+                    .map_or(true, |(_, nearest_distance)| distance < *nearest_distance)
+                {
+                    nearest = Some((option, distance));
+                }
+            }
+            nearest
+                .map(|(option, _)| option.clone())
+                .ok_or_else(|| empty_tuple_error("nearest"))
+        })),
+        "clamp_int" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let int = match &arguments[0] {
+                Value::Float(float) => NumericTypes::float_as_int(float),
+                value => value.as_int()?,
+            };
+            let (lo, hi) = (arguments[1].as_int()?, arguments[2].as_int()?);
+            if lo > hi {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "clamp_int requires the lower bound {} to be at most the upper bound {}",
+                    lo, hi
+                )));
+            }
+            Ok(Value::Int(int.clamp(lo, hi)))
+        })),
+        "lookup" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let pairs = arguments[0].as_tuple()?;
+            for pair in pairs {
+                let pair = pair.as_fixed_len_tuple(2)?;
+                if pair[0] == arguments[1] {
+                    return Ok(pair[1].clone());
+                }
+            }
+            Ok(Value::Empty)
+        })),
         // String functions
         #[cfg(feature = "regex")]
         "str::regex_matches" => Some(Function::new(|argument| {
@@ -268,10 +1597,47 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
             let subject = argument.as_string()?;
             Ok(Value::from(subject.to_uppercase()))
         })),
+        "str::capitalize" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::from(capitalize(&subject)))
+        })),
+        "str::title_case" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::from(
+                subject
+                    .split_inclusive(char::is_whitespace)
+                    .map(capitalize)
+                    .collect::<String>(),
+            ))
+        })),
         "str::trim" => Some(Function::new(|argument| {
             let subject = argument.as_string()?;
             Ok(Value::from(subject.trim()))
         })),
+        "str::trim_matches" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let characters = arguments[1].as_string()?;
+            Ok(Value::from(
+                subject.trim_matches(|c| characters.contains(c)),
+            ))
+        })),
+        "str::trim_start_matches" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let characters = arguments[1].as_string()?;
+            Ok(Value::from(
+                subject.trim_start_matches(|c| characters.contains(c)),
+            ))
+        })),
+        "str::trim_end_matches" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let characters = arguments[1].as_string()?;
+            Ok(Value::from(
+                subject.trim_end_matches(|c| characters.contains(c)),
+            ))
+        })),
         "str::from" => Some(Function::new(|argument| {
             Ok(Value::String(argument.str_from()))
         })),
@@ -289,10 +1655,185 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
             } else {
                 subject.len()
             };
-            if start > end || end > subject.len() {
+            if start > end {
                 return Err(EvalexprError::OutOfBoundsAccess);
             }
-            Ok(Value::from(&subject[start..end]))
+            // Byte indices that are out of bounds or within a character are rejected instead of panicking.
+            Ok(Value::from(
+                subject
+                    .get(start..end)
+                    .ok_or(EvalexprError::OutOfBoundsAccess)?,
+            ))
+        })),
+        "substring" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let subject = arguments[0].as_string()?;
+            let start: NumericTypes::Int = arguments[1].as_int()?;
+            let start = start
+                .into_usize()
+                .map_err(|_| EvalexprError::OutOfBoundsAccess)?;
+            let len: NumericTypes::Int = arguments[2].as_int()?;
+            // Negative lengths result in an empty substring, and lengths beyond the end of the string are clamped.
+            let len = len.into_usize().unwrap_or(0);
+            if start > subject.chars().count() {
+                return Err(EvalexprError::OutOfBoundsAccess);
+            }
+            Ok(Value::String(
+                subject.chars().skip(start).take(len).collect(),
+            ))
+        })),
+        "str::char_at" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let index: NumericTypes::Int = arguments[1].as_int()?;
+            let index = index
+                .into_usize()
+                .map_err(|_| EvalexprError::OutOfBoundsAccess)?;
+            subject
+                .chars()
+                .nth(index)
+                .map(|character| Value::String(character.to_string()))
+                .ok_or(EvalexprError::OutOfBoundsAccess)
+        })),
+        "str::len" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::Int(NumericTypes::Int::from_usize(
+                subject.chars().count(),
+            )?))
+        })),
+        "str::split_once" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let delimiter = arguments[1].as_string()?;
+            Ok(match subject.split_once(delimiter.as_str()) {
+                Some((before, after)) => Value::from(vec![before, after]),
+                None => Value::Empty,
+            })
+        })),
+        "str::count" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let haystack = arguments[0].as_string()?;
+            let needle = arguments[1].as_string()?;
+            if needle.is_empty() {
+                return Err(EvalexprError::CustomMessage(
+                    "str::count requires a non-empty needle".into(),
+                ));
+            }
+            Ok(Value::Int(NumericTypes::Int::from_usize(
+                haystack.matches(needle.as_str()).count(),
+            )?))
+        })),
+        "str::repeat" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let count: NumericTypes::Int = arguments[1].as_int()?;
+            let count = count.into_usize()?;
+            checked_string_length("str::repeat", subject.len().checked_mul(count))?;
+            Ok(Value::from(subject.repeat(count)))
+        })),
+        "str::indent" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let width: NumericTypes::Int = arguments[1].as_int()?;
+            Ok(Value::from(indent(&subject, width.into_usize()?)?))
+        })),
+        "str::wrap" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let subject = arguments[0].as_string()?;
+            let width: NumericTypes::Int = arguments[1].as_int()?;
+            Ok(Value::from(wrap(&subject, width.into_usize()?)))
+        })),
+        "str::ljust" => pad_to_width("str::ljust", |_| 0),
+        "str::rjust" => pad_to_width("str::rjust", |padding| padding),
+        // If the padding cannot be split evenly, the extra space is put on the right.
+        "str::center" => pad_to_width("str::center", |padding| padding / 2),
+        "str::hex" => int_to_radix_string(16, "0x"),
+        "str::oct" => int_to_radix_string(8, "0o"),
+        "str::bin" => int_to_radix_string(2, "0b"),
+        "str::si" => Some(Function::new(|argument| {
+            let (number, digits) = if let Value::Tuple(_) = argument {
+                let arguments = argument.as_fixed_len_tuple(2)?;
+                let digits: NumericTypes::Int = arguments[1].as_int()?;
+                (
+                    arguments[0].as_number()?,
+                    checked_fractional_digits("str::si", &digits)?,
+                )
+            } else {
+                (argument.as_number()?, 2)
+            };
+            Ok(Value::String(format_si::<NumericTypes>(number, digits)?))
+        })),
+        "str::percent" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let ratio = arguments[0].as_number()?;
+            let decimals: NumericTypes::Int = arguments[1].as_int()?;
+            let decimals = checked_fractional_digits("str::percent", &decimals)?;
+            let percentage = ratio * float_constant::<NumericTypes>(100)?;
+            Ok(Value::String(format!("{:.*}%", decimals, percentage)))
+        })),
+        "str::similarity" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_string()?, arguments[1].as_string()?);
+            let max_len = a.chars().count().max(b.chars().count());
+            if max_len == 0 {
+                return Ok(Value::Float(float_constant::<NumericTypes>(1)?));
+            }
+
+            let distance = float_constant::<NumericTypes>(levenshtein_distance(&a, &b))?;
+            let max_len = float_constant::<NumericTypes>(max_len)?;
+            Ok(Value::Float((max_len.clone() - distance) / max_len))
+        })),
+        "url::encode" => Some(Function::new(|argument| {
+            Ok(Value::String(url_encode(&argument.as_string()?)))
+        })),
+        "url::decode" => Some(Function::new(|argument| {
+            Ok(Value::String(url_decode(&argument.as_string()?)?))
+        })),
+        "base64::encode" => Some(Function::new(|argument| {
+            Ok(Value::String(base64_encode(&argument.as_string()?)))
+        })),
+        "base64::decode" => Some(Function::new(|argument| {
+            Ok(Value::String(base64_decode(&argument.as_string()?)?))
+        })),
+        "hash::crc32" => Some(Function::new(|argument| {
+            let checksum = crc32(&argument.as_string()?);
+            Ok(Value::Int(NumericTypes::Int::from_usize(
+                checksum as usize,
+            )?))
+        })),
+        #[cfg(feature = "hash")]
+        "hash::md5" => Some(Function::new(|argument| {
+            Ok(Value::String(to_hex(&Md5::digest(argument.as_string()?))))
+        })),
+        #[cfg(feature = "hash")]
+        "hash::sha256" => Some(Function::new(|argument| {
+            Ok(Value::String(to_hex(&Sha256::digest(
+                argument.as_string()?,
+            ))))
+        })),
+        "duration::parse" => Some(Function::new(|argument| {
+            Ok(Value::Int(parse_duration(&argument.as_string()?)?))
+        })),
+        // Timestamps
+        "time::in_range" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let (timestamp, start, end): (NumericTypes::Int, NumericTypes::Int, NumericTypes::Int) = (
+                arguments[0].as_int()?,
+                arguments[1].as_int()?,
+                arguments[2].as_int()?,
+            );
+            Ok(Value::Boolean(start <= timestamp && timestamp < end))
+        })),
+        "time::add_days" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (timestamp, days): (NumericTypes::Int, NumericTypes::Int) =
+                (arguments[0].as_int()?, arguments[1].as_int()?);
+            let seconds = days.checked_mul(&NumericTypes::Int::from_usize(86_400)?)?;
+            Ok(Value::Int(timestamp.checked_add(&seconds)?))
+        })),
+        #[cfg(feature = "chrono")]
+        "time::format_iso" => Some(Function::new(|argument| {
+            Ok(Value::String(format_iso_timestamp(&argument.as_int()?)?))
         })),
         #[cfg(feature = "rand")]
         "random" => Some(Function::new(|argument| {
@@ -304,8 +1845,124 @@ pub fn builtin_function<NumericTypes: EvalexprNumericTypes>(
         "bitor" => int_function!(bitor, 2),
         "bitxor" => int_function!(bitxor, 2),
         "bitnot" => int_function!(bitnot),
-        "shl" => int_function!(bit_shift_left, 2),
-        "shr" => int_function!(bit_shift_right, 2),
+        "shl" => int_function!(bit_shift_left, 2, fallible),
+        "shr" => int_function!(bit_shift_right, 2, fallible),
         _ => None,
     }
 }
+
+/// The identifiers of all builtin functions that need access to the context.
+/// Every identifier here must be resolved by [`call_builtin_function_with_context`].
+pub(crate) const BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS: &[&str] =
+    &["all", "any", "map", "filter"];
+
+/// Calls the builtin function with the given identifier if it is one that needs access to the context, e.g. to call other functions.
+/// Returns `None` if there is no such builtin function.
+pub(crate) fn call_builtin_function_with_context<C: Context>(
+    identifier: &str,
+    argument: &Value<C::NumericTypes>,
+    context: &C,
+) -> Option<EvalexprResultValue<C::NumericTypes>> {
+    if !BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS.contains(&identifier) {
+        return None;
+    }
+
+    Some(argument.as_fixed_len_tuple(2).and_then(|arguments| {
+        let tuple = arguments[0].as_tuple()?;
+        let function = arguments[1].as_string()?;
+        call_higher_order_builtin_function(identifier, tuple, |element| {
+            call_function(&function, element, context, None)
+        })
+    }))
+}
+
+/// Calls the builtin function with the given identifier from [`BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS`],
+/// with the given tuple and the given function in place of the named function.
+pub(crate) fn call_higher_order_builtin_function<NumericTypes: EvalexprNumericTypes>(
+    identifier: &str,
+    tuple: TupleType<NumericTypes>,
+    function: impl FnMut(&Value<NumericTypes>) -> EvalexprResultValue<NumericTypes>,
+) -> EvalexprResultValue<NumericTypes> {
+    match identifier {
+        "all" => short_circuit_predicate(tuple, function, false),
+        "any" => short_circuit_predicate(tuple, function, true),
+        "map" => map_tuple(tuple, function),
+        "filter" => filter_tuple(tuple, function),
+        _ => Err(EvalexprError::FunctionIdentifierNotFound(
+            identifier.to_string(),
+        )),
+    }
+}
+
+/// Calls the predicate on each element of the tuple.
+/// Returns `short_circuit` as soon as the predicate returns it, and its negation if the predicate never does.
+fn short_circuit_predicate<NumericTypes: EvalexprNumericTypes>(
+    tuple: TupleType<NumericTypes>,
+    mut predicate: impl FnMut(&Value<NumericTypes>) -> EvalexprResultValue<NumericTypes>,
+    short_circuit: bool,
+) -> EvalexprResultValue<NumericTypes> {
+    for element in &tuple {
+        if predicate(element)?.as_boolean()? == short_circuit {
+            return Ok(Value::Boolean(short_circuit));
+        }
+    }
+    Ok(Value::Boolean(!short_circuit))
+}
+
+/// Calls the function on each element of the tuple, and returns the results as tuple.
+fn map_tuple<NumericTypes: EvalexprNumericTypes>(
+    tuple: TupleType<NumericTypes>,
+    function: impl FnMut(&Value<NumericTypes>) -> EvalexprResultValue<NumericTypes>,
+) -> EvalexprResultValue<NumericTypes> {
+    tuple
+        .iter()
+        .map(function)
+        .collect::<Result<_, _>>()
+        .map(Value::Tuple)
+}
+
+/// Calls the predicate on each element of the tuple, and returns the elements for which it returns true as tuple.
+fn filter_tuple<NumericTypes: EvalexprNumericTypes>(
+    tuple: TupleType<NumericTypes>,
+    mut predicate: impl FnMut(&Value<NumericTypes>) -> EvalexprResultValue<NumericTypes>,
+) -> EvalexprResultValue<NumericTypes> {
+    let mut filtered = Vec::new();
+    for element in tuple {
+        if predicate(&element)?.as_boolean()? {
+            filtered.push(element);
+        }
+    }
+    Ok(Value::Tuple(filtered))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        function::builtin::{
+            builtin_function, call_builtin_function_with_context, BUILTIN_FUNCTION_IDENTIFIERS,
+            BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS,
+        },
+        DefaultNumericTypes, EmptyContextWithBuiltinFunctions, Value,
+    };
+
+    #[test]
+    fn test_builtin_function_identifiers_are_resolved() {
+        for identifier in BUILTIN_FUNCTION_IDENTIFIERS {
+            assert!(
+                builtin_function::<DefaultNumericTypes>(identifier).is_some(),
+                "{identifier} is not a builtin function"
+            );
+        }
+        for identifier in BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS {
+            assert!(
+                call_builtin_function_with_context(
+                    identifier,
+                    &Value::Empty,
+                    &EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
+                )
+                .is_some(),
+                "{identifier} is not a builtin function with context"
+            );
+        }
+    }
+}
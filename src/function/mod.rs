@@ -1,6 +1,7 @@
-use std::fmt;
+use std::{fmt, ops::RangeInclusive};
 
 use crate::{
+    context::Context,
     error::EvalexprResultValue,
     value::{
         numeric_types::{default_numeric_types::DefaultNumericTypes, EvalexprNumericTypes},
@@ -30,6 +31,38 @@ where
     }
 }
 
+/// A helper trait to enable cloning through `Fn` trait objects that receive the evaluating context.
+trait ClonableFnWithContext<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes>
+where
+    Self: Fn(
+        &Value<NumericTypes>,
+        &dyn Context<NumericTypes = NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes>,
+    Self: Send + Sync + 'static,
+{
+    fn dyn_clone(&self) -> Box<dyn ClonableFnWithContext<NumericTypes>>;
+}
+
+impl<F, NumericTypes: EvalexprNumericTypes> ClonableFnWithContext<NumericTypes> for F
+where
+    F: Fn(
+        &Value<NumericTypes>,
+        &dyn Context<NumericTypes = NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes>,
+    F: Send + Sync + 'static,
+    F: Clone,
+{
+    fn dyn_clone(&self) -> Box<dyn ClonableFnWithContext<NumericTypes>> {
+        Box::new(self.clone()) as _
+    }
+}
+
+/// The closure of a `Function`, which either receives only the argument or also the evaluating context.
+enum FunctionKind<NumericTypes: EvalexprNumericTypes> {
+    WithoutContext(Box<dyn ClonableFn<NumericTypes>>),
+    WithContext(Box<dyn ClonableFnWithContext<NumericTypes>>),
+}
+
 /// A user-defined function.
 /// Functions can be used in expressions by storing them in a `Context`.
 ///
@@ -45,13 +78,23 @@ where
 /// assert_eq!(eval_with_context("id(4)", &context), Ok(Value::from_int(4)));
 /// ```
 pub struct Function<NumericTypes: EvalexprNumericTypes> {
-    function: Box<dyn ClonableFn<NumericTypes>>,
+    function: FunctionKind<NumericTypes>,
+    /// The amounts of arguments the function accepts, if it advertises them.
+    argument_amount: Option<RangeInclusive<usize>>,
 }
 
 impl<NumericTypes: EvalexprNumericTypes> Clone for Function<NumericTypes> {
     fn clone(&self) -> Self {
         Self {
-            function: self.function.dyn_clone(),
+            function: match &self.function {
+                FunctionKind::WithoutContext(function) => {
+                    FunctionKind::WithoutContext(function.as_ref().dyn_clone())
+                },
+                FunctionKind::WithContext(function) => {
+                    FunctionKind::WithContext(function.as_ref().dyn_clone())
+                },
+            },
+            argument_amount: self.argument_amount.clone(),
         }
     }
 }
@@ -67,12 +110,88 @@ impl<NumericTypes: EvalexprNumericTypes> Function<NumericTypes> {
         F: Clone,
     {
         Self {
-            function: Box::new(function) as _,
+            function: FunctionKind::WithoutContext(Box::new(function) as _),
+            argument_amount: None,
+        }
+    }
+
+    /// Creates a user-defined function that receives the context it is called from in addition to its argument.
+    ///
+    /// This allows the function to read variables or to call other functions of the context.
+    /// The context is the one the expression is evaluated with, even if the function is stored in a context that is combined with others, e.g. in a `ContextStack`.
+    /// The `function` is boxed for storage.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// context.set_value("offset".into(), Value::from_int(10)).unwrap(); // Do proper error handling here
+    /// context.set_function("shift".into(), Function::new_with_context(|argument, context| {
+    ///     let offset = context.get_value("offset").cloned().unwrap_or(Value::from_int(0));
+    ///     Ok(Value::from_int(argument.as_int()? + offset.as_int()?))
+    /// })).unwrap(); // Do proper error handling here
+    /// assert_eq!(eval_with_context("shift(5)", &context), Ok(Value::from_int(15)));
+    /// ```
+    pub fn new_with_context<F>(function: F) -> Self
+    where
+        F: Fn(
+            &Value<NumericTypes>,
+            &dyn Context<NumericTypes = NumericTypes>,
+        ) -> EvalexprResultValue<NumericTypes>,
+        F: Send + Sync + 'static,
+        F: Clone,
+    {
+        Self {
+            function: FunctionKind::WithContext(Box::new(function) as _),
+            argument_amount: None,
         }
     }
 
-    pub(crate) fn call(&self, argument: &Value<NumericTypes>) -> EvalexprResultValue<NumericTypes> {
-        (self.function)(argument)
+    /// Advertises the amounts of arguments this function accepts.
+    ///
+    /// Multiple arguments are passed to a function as a tuple, so the amount of arguments of a call is the length of the tuple,
+    /// zero for an empty argument, and one for any other argument.
+    /// The amount is not checked when the function is called, but by [`Node::validate_against_context`](crate::Node::validate_against_context)
+    /// for calls whose amount of arguments is known without evaluating them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// context.set_function("avg".into(), Function::new(|argument| {
+    ///     let arguments = argument.as_fixed_len_tuple(2)?;
+    ///     Ok(Value::from_float((arguments[0].as_number()? + arguments[1].as_number()?) / 2.0))
+    /// }).with_argument_amount(2..=2)).unwrap(); // Do proper error handling here
+    ///
+    /// let node = build_operator_tree("avg(1)").unwrap(); // Do proper error handling here
+    /// assert_eq!(
+    ///     node.validate_against_context(&context),
+    ///     Err(EvalexprError::wrong_function_argument_amount(1, 2))
+    /// );
+    /// ```
+    pub fn with_argument_amount(mut self, argument_amount: RangeInclusive<usize>) -> Self {
+        self.argument_amount = Some(argument_amount);
+        self
+    }
+
+    /// Returns the amounts of arguments this function accepts, if it advertises them.
+    pub fn argument_amount(&self) -> Option<&RangeInclusive<usize>> {
+        self.argument_amount.as_ref()
+    }
+
+    pub(crate) fn call(
+        &self,
+        argument: &Value<NumericTypes>,
+        context: &dyn Context<NumericTypes = NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        match &self.function {
+            FunctionKind::WithoutContext(function) => function(argument),
+            FunctionKind::WithContext(function) => function(argument, context),
+        }
     }
 }
 
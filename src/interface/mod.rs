@@ -1,9 +1,10 @@
 use crate::{
+    config::ParseConfig,
     error::EvalexprResultValue,
     token, tree,
     value::{
         numeric_types::{default_numeric_types::DefaultNumericTypes, EvalexprNumericTypes},
-        TupleType,
+        ArrayType, TupleType,
     },
     Context, ContextWithMutableVariables, EmptyType, EvalexprError, EvalexprResult, HashMapContext,
     Node, Value, EMPTY_VALUE,
@@ -43,7 +44,8 @@ pub fn eval_with_context<C: Context>(
     string: &str,
     context: &C,
 ) -> EvalexprResultValue<C::NumericTypes> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)?.eval_with_context(context)
+    tree::tokens_to_operator_tree(token::tokenize_with_spans(string)?, None)?
+        .eval_with_context(context)
 }
 
 /// Evaluate the given expression string with the given mutable context.
@@ -65,7 +67,32 @@ pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
     string: &str,
     context: &mut C,
 ) -> EvalexprResultValue<C::NumericTypes> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)?.eval_with_context_mut(context)
+    tree::tokens_to_operator_tree(token::tokenize_with_spans(string)?, None)?
+        .eval_with_context_mut(context)
+}
+
+/// Evaluate the given expression string with the given context, or return the given fallback value if parsing or evaluation fails.
+///
+/// This is useful for best-effort evaluation, e.g. if a broken expression should result in a default value instead of an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context: HashMapContext<DefaultNumericTypes> = context_map! { "a" => int 2 }.unwrap(); // Do proper error handling here
+/// assert_eq!(eval_or("a * 3", &context, Value::from_int(0)), Value::from_int(6));
+/// assert_eq!(eval_or("a / 0", &context, Value::from_int(0)), Value::from_int(0));
+/// assert_eq!(eval_or("a * (3", &context, Value::from_int(0)), Value::from_int(0));
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_or<C: Context>(
+    string: &str,
+    context: &C,
+    fallback: Value<C::NumericTypes>,
+) -> Value<C::NumericTypes> {
+    eval_with_context(string, context).unwrap_or(fallback)
 }
 
 /// Build the operator tree for the given expression string.
@@ -95,7 +122,31 @@ pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
 pub fn build_operator_tree<NumericTypes: EvalexprNumericTypes>(
     string: &str,
 ) -> EvalexprResult<Node<NumericTypes>, NumericTypes> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)
+    tree::tokens_to_operator_tree(token::tokenize_with_spans(string)?, None)
+}
+
+/// Build the operator tree for the given expression string, parsing it according to the given configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let config = ParseConfig::new().with_newline_as_semicolon(true);
+/// let precomputed = build_operator_tree_with_config::<DefaultNumericTypes>("1\n2", &config).unwrap(); // Do proper error handling here
+/// assert_eq!(precomputed.eval(), Ok(Value::from_int(2)));
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn build_operator_tree_with_config<NumericTypes: EvalexprNumericTypes>(
+    string: &str,
+    config: &ParseConfig,
+) -> EvalexprResult<Node<NumericTypes>, NumericTypes> {
+    let tokens = token::tokenize_with_config_and_spans(string, config)?;
+    if let Some(max_nesting_depth) = config.max_nesting_depth() {
+        token::check_nesting_depth(&tokens, max_nesting_depth)?;
+    }
+    tree::tokens_to_operator_tree(tokens, config.max_nesting_depth())
 }
 
 /// Evaluate the given expression string into a string.
@@ -147,6 +198,13 @@ pub fn eval_tuple(string: &str) -> EvalexprResult<TupleType> {
     eval_tuple_with_context_mut(string, &mut HashMapContext::<DefaultNumericTypes>::new())
 }
 
+/// Evaluate the given expression string into an array.
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_array(string: &str) -> EvalexprResult<ArrayType> {
+    eval_array_with_context_mut(string, &mut HashMapContext::<DefaultNumericTypes>::new())
+}
+
 /// Evaluate the given expression string into an empty value.
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
@@ -242,6 +300,20 @@ pub fn eval_tuple_with_context<C: Context>(
     }
 }
 
+/// Evaluate the given expression string into an array with the given context.
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_array_with_context<C: Context>(
+    string: &str,
+    context: &C,
+) -> EvalexprResult<ArrayType<C::NumericTypes>, C::NumericTypes> {
+    match eval_with_context(string, context) {
+        Ok(Value::Array(array)) => Ok(array),
+        Ok(value) => Err(EvalexprError::expected_array(value)),
+        Err(error) => Err(error),
+    }
+}
+
 /// Evaluate the given expression string into an empty value with the given context.
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
@@ -344,6 +416,20 @@ pub fn eval_tuple_with_context_mut<C: ContextWithMutableVariables>(
     }
 }
 
+/// Evaluate the given expression string into an array with the given mutable context.
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_array_with_context_mut<C: ContextWithMutableVariables>(
+    string: &str,
+    context: &mut C,
+) -> EvalexprResult<ArrayType<C::NumericTypes>, C::NumericTypes> {
+    match eval_with_context_mut(string, context) {
+        Ok(Value::Array(array)) => Ok(array),
+        Ok(value) => Err(EvalexprError::expected_array(value)),
+        Err(error) => Err(error),
+    }
+}
+
 /// Evaluate the given expression string into an empty value with the given mutable context.
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
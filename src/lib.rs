@@ -30,7 +30,7 @@
 //!
 //! let mut context = HashMapContext::<DefaultNumericTypes>::new();
 //! // Assign 5 to a like this
-//! assert_eq!(eval_empty_with_context_mut("a = 5", &mut context), Ok(EMPTY_VALUE));
+//! assert_eq!(eval_int_with_context_mut("a = 5", &mut context), Ok(5));
 //! // The HashMapContext is type safe, so this will fail now
 //! assert_eq!(eval_empty_with_context_mut("a = 5.0", &mut context),
 //!            Err(EvalexprError::expected_int(Value::from_float(5.0))));
@@ -123,29 +123,38 @@
 //!
 //! | Operator | Precedence | Description |
 //! |----------|------------|-------------|
-//! | ^ | 120 | Exponentiation |
+//! | [ ] | 190 | Indexing, see below |
+//! | ^, ** | 120 | Exponentiation |
 //! | * | 100 | Product |
 //! | / | 100 | Division (integer if both arguments are integers, otherwise float) |
+//! | // | 100 | Integer division, rounding the quotient down to an integer. Only if enabled in the `ParseConfig`, see [Parse Configuration](#parse-configuration) |
 //! | % | 100 | Modulo (integer if both arguments are integers, otherwise float) |
 //! | + | 95 | Sum or String Concatenation |
 //! | - | 95 | Difference |
+//! | << | 90 | Bitwise left shift (integers only) |
+//! | \>> | 90 | Bitwise right shift (integers only) |
+//! | & | 87 | Bitwise and (integers only) |
+//! | &#124; | 85 | Bitwise or (integers only) |
 //! | < | 80 | Lower than |
 //! | \> | 80 | Greater than |
 //! | <= | 80 | Lower than or equal |
 //! | \>= | 80 | Greater than or equal |
-//! | == | 80 | Equal |
-//! | != | 80 | Not equal |
+//! | == | 80 | Equal. An integer and a float are equal if they denote the same number, while other values of different types are never equal |
+//! | != | 80 | Not equal, the negation of `==` |
 //! | && | 75 | Logical and |
 //! | &#124;&#124; | 70 | Logical or |
+//! | ?? | 65 | Null-coalescing, see below |
+//! | ? : | 60 | Conditional, see below |
 //! | = | 50 | Assignment |
 //! | += | 50 | Sum-Assignment or String-Concatenation-Assignment |
 //! | -= | 50 | Difference-Assignment |
 //! | *= | 50 | Product-Assignment |
 //! | /= | 50 | Division-Assignment |
 //! | %= | 50 | Modulo-Assignment |
-//! | ^= | 50 | Exponentiation-Assignment |
+//! | ^=, **= | 50 | Exponentiation-Assignment |
 //! | &&= | 50 | Logical-And-Assignment |
 //! | &#124;&#124;= | 50 | Logical-Or-Assignment |
+//! | -> | 45 | Lambda, see [Lambdas](#lambdas) |
 //! | , | 40 | Aggregation |
 //! | ; | 0 | Expression Chaining |
 //!
@@ -199,6 +208,39 @@
 //! ])));
 //! ```
 //!
+//! #### Arrays
+//!
+//! Curly braces create an array, which is a separate type from tuples.
+//! The elements of an array are separated by the aggregation operator, but unlike parenthesized tuples,
+//! a tuple inside the curly braces stays a single element of the array.
+//! Arrays are also passed to functions as a single argument instead of being used as the argument list.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("{1, (2, 3)}"), Ok(Value::Array(vec![
+//!     Value::from_int(1),
+//!     Value::from(vec![Value::from_int(2), Value::from_int(3)]),
+//! ])));
+//! assert_eq!(eval("{}"), Ok(Value::Array(vec![])));
+//! assert_eq!(eval("len({1, 2, 3})"), Ok(Value::from_int(3)));
+//! ```
+//!
+//! #### The Index Operator
+//!
+//! Brackets behind a tuple, array or string select the element at the given index, starting at zero.
+//! The element of a string is its character at the index, returned as a string of length one.
+//! Indices that are negative or not less than the length result in an error.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("(1, 2, 3)[1]"), Ok(Value::from_int(2)));
+//! assert_eq!(eval("{(1, 2), 3}[0][1]"), Ok(Value::from_int(2)));
+//! assert_eq!(eval("\"abc\"[2]"), Ok(Value::from("c")));
+//! assert_eq!(eval("(1, 2)[2]"), Err(EvalexprError::OutOfBoundsAccess));
+//! ```
+//!
 //! #### The Assignment Operator
 //!
 //! This crate features the assignment operator, that allows expressions to store their result in a variable in the expression context.
@@ -207,16 +249,21 @@
 //! Note that assignments are type safe when using the `HashMapContext`.
 //! That means that if an identifier is assigned a value of a type once, it cannot be assigned a value of another type.
 //!
+//! An assignment evaluates to the assigned value, and the operator-assignment operators like `+=` evaluate to the value they store.
+//!
 //! ```rust
 //! use evalexpr::*;
 //!
 //! let mut context = HashMapContext::<DefaultNumericTypes>::new();
 //! assert_eq!(eval_with_context("a = 5", &context), Err(EvalexprError::ContextNotMutable));
-//! assert_eq!(eval_empty_with_context_mut("a = 5", &mut context), Ok(EMPTY_VALUE));
+//! assert_eq!(eval_int_with_context_mut("a = 5", &mut context), Ok(5));
 //! assert_eq!(eval_empty_with_context_mut("a = 5.0", &mut context),
 //!            Err(EvalexprError::expected_int(Value::from_float(5.0))));
 //! assert_eq!(eval_int_with_context("a", &context), Ok(5));
 //! assert_eq!(context.get_value("a"), Some(Value::from_int(5)).as_ref());
+//! // An assignment evaluates to the assigned value, so assignments can be nested
+//! assert_eq!(eval_int_with_context_mut("b = a = 6; b", &mut context), Ok(6));
+//! assert_eq!(eval_int_with_context_mut("b = (a += 1); b", &mut context), Ok(7));
 //! ```
 //!
 //! For each binary operator, there exists an equivalent operator-assignment operator.
@@ -255,6 +302,38 @@
 //! assert_eq!(healing_script.eval_int_with_context_mut(&mut context), Ok(5));
 //! ```
 //!
+//! #### The Conditional Operator
+//!
+//! The conditional operator `condition ? a : b` evaluates to `a` if the condition is true, and to `b` otherwise.
+//! Unlike the builtin function `if`, it only evaluates the branch that is selected by the condition,
+//! so an assignment or a failing function call in the other branch has no effect.
+//! Conditionals can be chained, with `a ? b : c ? d : e` meaning `a ? b : (c ? d : e)`.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("x = -3; x > 0 ? x : -x"), Ok(Value::from_int(3)));
+//! assert_eq!(eval("x = 0; x < 0 ? \"negative\" : x == 0 ? \"zero\" : \"positive\""), Ok(Value::from("zero")));
+//! assert_eq!(eval("a = 1; false ? (a = 2) : (); a"), Ok(Value::from_int(1)));
+//! ```
+//!
+//! #### The Null-Coalescing Operator
+//!
+//! The null-coalescing operator `a ?? b` evaluates to `a`, unless `a` is empty or reads a variable that does not exist, in which case it evaluates to `b`.
+//! This allows to fall back to a default for optional variables of the context.
+//! Like the conditional operator, it only evaluates `b` if it is needed.
+//! Other errors that occur while evaluating `a`, like a failing function call, are not caught.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context: HashMapContext<DefaultNumericTypes> = context_map! { "present" => int 1 }.unwrap(); // Do proper error handling here
+//! assert_eq!(eval_with_context("present ?? 0", &context), Ok(Value::from_int(1)));
+//! assert_eq!(eval_with_context("missing ?? 0", &context), Ok(Value::from_int(0)));
+//! assert_eq!(eval_with_context("null ?? missing ?? 2", &context), Ok(Value::from_int(2)));
+//! assert!(eval_with_context("1 / 0 ?? 0", &context).is_err());
+//! ```
+//!
 //! ### Contexts
 //!
 //! An expression evaluator that just evaluates expressions would be useful already, but this crate can do more.
@@ -325,6 +404,15 @@
 //!
 //! For more information about user-defined functions, refer to the respective [section](#user-defined-functions).
 //!
+//! Multiple contexts can be combined with a `ContextStack`, which resolves identifiers by searching its contexts from the most recently pushed one to the first one.
+//! This allows for example to shadow global variables with local ones.
+//!
+//! A `HashMapContext` constructed with `new_case_insensitive` matches identifiers case-insensitively, such that `Price`, `price` and `PRICE` denote the same variable.
+//!
+//! The variables of a `HashMapContext` can be saved with `snapshot` and reset with `restore`,
+//! which allows to roll back the assignments of an expression that failed to evaluate.
+//! To preview the assignments of an expression without performing them, it can be evaluated with a `DryRunContext` wrapping another context.
+//!
 //! ### Builtin Functions
 //!
 //! This crate offers a set of builtin functions (see below for a full list).
@@ -344,22 +432,51 @@
 //!
 //! | Identifier           | Argument Amount | Argument Types                | Description |
 //! |----------------------|-----------------|-------------------------------|-------------|
-//! | `min`                | >= 1            | Numeric                       | Returns the minimum of the arguments |
-//! | `max`                | >= 1            | Numeric                       | Returns the maximum of the arguments |
-//! | `len`                | 1               | String/Tuple                  | Returns the character length of a string, or the amount of elements in a tuple (not recursively) |
+//! | `min`                | >= 1            | Numeric                       | Returns the minimum of the arguments. Returns NaN if any argument is NaN |
+//! | `max`                | >= 1            | Numeric                       | Returns the maximum of the arguments. Returns NaN if any argument is NaN |
+//! | `argmin`             | >= 1            | Numeric                       | Returns the index of the minimum of the arguments as integer. If multiple arguments are minimal, the first of their indices is returned. NaN arguments are ignored, unless all arguments are NaN |
+//! | `argmax`             | >= 1            | Numeric                       | Returns the index of the maximum of the arguments as integer. If multiple arguments are maximal, the first of their indices is returned. NaN arguments are ignored, unless all arguments are NaN |
+//! | `len`                | 1               | String/Tuple/Array            | Returns the character length of a string, or the amount of elements in a tuple or array (not recursively) |
 //! | `floor`              | 1               | Numeric                       | Returns the largest integer less than or equal to a number |
 //! | `round`              | 1               | Numeric                       | Returns the nearest integer to a number. Rounds half-way cases away from 0.0 |
 //! | `ceil`               | 1               | Numeric                       | Returns the smallest integer greater than or equal to a number |
+//! | `round_to`           | 2               | Numeric, Int                  | Rounds a number to the given amount of decimal digits like `round`, e.g. `round_to(3.14159, 2)` returns 3.14. A negative amount of digits rounds to tens, hundreds and so on |
+//! | `floor_to`           | 2               | Numeric, Int                  | Rounds a number down to the given amount of decimal digits, like `round_to` |
+//! | `ceil_to`            | 2               | Numeric, Int                  | Rounds a number up to the given amount of decimal digits, like `round_to` |
 //! | `if`                 | 3               | Boolean, Any, Any             | If the first argument is true, returns the second argument, otherwise, returns the third  |
 //! | `contains`           | 2               | Tuple, any non-tuple          | Returns true if second argument exists in first tuple argument. |
 //! | `contains_any`       | 2               | Tuple, Tuple of any non-tuple | Returns true if one of the values in the second tuple argument exists in first tuple argument. |
-//! | `typeof`             | 1               | Any                           | returns "string", "float", "int", "boolean", "tuple", or "empty" depending on the type of the argument  |
+//! | `in`                 | >= 2            | Any                           | Returns true if the first argument is equal to any of the other arguments according to `==`, e.g. `in(x, 1, 2, 3)`, so `in(1, 1.0)` returns true |
+//! | `tuple::len`         | 1               | Tuple                         | Returns the amount of elements in a tuple (not recursively). Unlike `len`, it does not accept strings |
+//! | `tuple::count`       | 2               | Tuple, Any                    | Returns the amount of elements of the tuple that are equal to the second argument according to `==`, e.g. `tuple::count((1, 2, 1.0), 1)` returns 2 |
+//! | `with_index`         | 3               | Tuple, Int, Any               | Returns a copy of the tuple with the element at the given index replaced by the third argument |
+//! | `slice`              | 2/3             | Tuple, Int, Int               | Returns the elements of the tuple from the second argument (inclusive) to the third argument (exclusive). Negative indices count from the end, and indices outside of the tuple are clamped. If the last argument is omitted, the slice extends to the end of the tuple |
+//! | `zip`                | 2               | Tuple, Tuple                  | Pairs the elements of two tuples of the same length, e.g. `zip((1, 2), (3, 4))` returns `((1, 3), (2, 4))` |
+//! | `enumerate`          | 1               | Tuple                         | Pairs each element of the tuple with its index, e.g. `enumerate(("a", "b"))` returns `((0, "a"), (1, "b"))` |
+//! | `flatten`            | 1               | Tuple                         | Recursively replaces nested tuples by their elements, e.g. `flatten((1, (2, (3, 4))))` returns `(1, 2, 3, 4)` |
+//! | `chunk`              | 2               | Tuple, Int                    | Splits the tuple into tuples of the length given by the second argument, of which only the last may be shorter, e.g. `chunk((1, 2, 3), 2)` returns `((1, 2), (3))`. The length must be positive |
+//! | `all`                | 2               | Tuple, String                 | Returns true if the function named by the second argument returns true for all elements of the tuple. Stops at the first element for which it returns false, and returns true for an empty tuple. The function may be a builtin function, one from the context, or a [lambda](#lambdas), and must return a boolean |
+//! | `any`                | 2               | Tuple, String                 | Returns true if the function named by the second argument returns true for any element of the tuple. Stops at the first element for which it returns true, and returns false for an empty tuple. The function may be a builtin function, one from the context, or a [lambda](#lambdas), and must return a boolean |
+//! | `map`                | 2               | Tuple, String                 | Returns the tuple of the results of calling the function named by the second argument on each element of the tuple, e.g. `map((1, 2), "math::abs")`. The function may be a builtin function, one from the context, or a [lambda](#lambdas) |
+//! | `filter`             | 2               | Tuple, String                 | Returns the tuple of the elements of the tuple for which the function named by the second argument returns true. The function may be a builtin function, one from the context, or a [lambda](#lambdas), and must return a boolean |
+//! | `lookup`             | 2               | Tuple of 2-tuples, Any        | Returns the value of the first `(key, value)` pair in the tuple whose key equals the second argument, or an empty value if there is none |
+//! | `nearest`            | 2               | Numeric, Tuple of Numeric     | Returns the element of the tuple that is numerically closest to the first argument. If multiple elements are equally close, the first of them is returned |
+//! | `clamp_int`          | 3               | Numeric, Int, Int             | Converts the first argument to an integer, truncating floats towards zero, and clamps it into the range from the second to the third argument. Fails if the lower bound is greater than the upper bound |
+//! | `typeof`             | 1               | Any                           | returns "string", "float", "int", "boolean", "tuple", "array", or "empty" depending on the type of the argument  |
+//! | `is_valid`           | 1               | Any                           | Returns true if the argument is an integer or a finite float, and false for NaN, infinite floats and any non-numeric value. Unlike `math::is_finite`, it never fails |
+//! | `strict_eq`          | 2               | Any, Any                      | Returns true if both arguments have the same type and value, such that e.g. `strict_eq(1, 1.0)` is false. Elements of tuples and arrays are compared in the same way |
 //! | `math::is_nan`       | 1               | Numeric                       | Returns true if the argument is the floating-point value NaN, false if it is another floating-point value, and throws an error if it is not a number  |
 //! | `math::is_finite`    | 1               | Numeric                       | Returns true if the argument is a finite floating-point number, false otherwise  |
 //! | `math::is_infinite`  | 1               | Numeric                       | Returns true if the argument is an infinite floating-point number, false otherwise  |
 //! | `math::is_normal`    | 1               | Numeric                       | Returns true if the argument is a floating-point number that is neither zero, infinite, [subnormal](https://en.wikipedia.org/wiki/Subnormal_number), or NaN, false otherwise  |
+//! | `math::int_min`      | 0               | Empty                         | Returns the smallest integer of the configured integer type |
+//! | `math::int_max`      | 0               | Empty                         | Returns the largest integer of the configured integer type |
+//! | `math::float_min`    | 0               | Empty                         | Returns the smallest finite float of the configured float type, i.e. the negative float with the largest magnitude |
+//! | `math::float_max`    | 0               | Empty                         | Returns the largest finite float of the configured float type |
+//! | `math::inf`          | 0               | Empty                         | Returns positive infinity |
+//! | `math::nan`          | 0               | Empty                         | Returns the floating-point value NaN |
 //! | `math::ln`           | 1               | Numeric                       | Returns the natural logarithm of the number |
-//! | `math::log`          | 2               | Numeric, Numeric              | Returns the logarithm of the number with respect to an arbitrary base |
+//! | `math::log`          | 1, 2            | Numeric, Numeric              | Returns the natural logarithm of the number, or its logarithm with respect to an arbitrary base if a base is given. Fails if the number or the base is not positive, or if the base is 1 |
 //! | `math::log2`         | 1               | Numeric                       | Returns the base 2 logarithm of the number |
 //! | `math::log10`        | 1               | Numeric                       | Returns the base 10 logarithm of the number |
 //! | `math::exp`          | 1               | Numeric                       | Returns `e^(number)`, (the exponential function) |
@@ -381,50 +498,120 @@
 //! | `math::sqrt`         | 1               | Numeric                       | Returns the square root of a number. Returns NaN for a negative number |
 //! | `math::cbrt`         | 1               | Numeric                       | Returns the cube root of a number |
 //! | `math::hypot`        | 2               | Numeric                       | Calculates the length of the hypotenuse of a right-angle triangle given legs of length given by the two arguments |
+//! | `math::fmin`         | >= 1            | Numeric                       | Returns the minimum of the arguments, ignoring NaN arguments like the IEEE 754 `minNum` operation. Returns NaN only if all arguments are NaN |
+//! | `math::fmax`         | >= 1            | Numeric                       | Returns the maximum of the arguments, ignoring NaN arguments like the IEEE 754 `maxNum` operation. Returns NaN only if all arguments are NaN |
 //! | `math::abs`          | 1               | Numeric                       | Returns the absolute value of a number, returning an integer if the argument was an integer, and a float otherwise |
-//! | `str::regex_matches` | 2               | String, String                | Returns true if the first argument matches the regex in the second argument (Requires `regex_support` feature flag) |
-//! | `str::regex_replace` | 3               | String, String, String        | Returns the first argument with all matches of the regex in the second argument replaced by the third argument (Requires `regex_support` feature flag) |
+//! | `math::normalize`    | 3               | Numeric, Numeric, Numeric     | Clamps the first argument into the range from the second to the third argument, and returns its relative position in that range as a float between 0 and 1. This is the inverse of a linear interpolation. Fails if the bounds are equal |
+//! | `math::wrap`         | 3               | Numeric, Numeric, Numeric     | Wraps the first argument modularly into the half-open range from the second (inclusive) to the third argument (exclusive), e.g. `math::wrap(370, 0, 360)` returns `10`. Returns an integer if all arguments are integers, and a float otherwise. Returns NaN if any argument is NaN. Fails if the lower bound is not less than the upper bound |
+//! | `math::clamp01`     | 1               | Numeric                       | Clamps the argument into the range from 0 to 1 and returns it as float |
+//! | `math::gamma`       | 2               | Numeric, Numeric              | Clamps the first argument into the range from 0 to 1 like `math::clamp01`, and applies the gamma correction `x^(1/gamma)` with the gamma given as second argument. Returns a float, and fails if the gamma is zero |
+//! | `stats::mean`        | >= 1            | Numeric                       | Returns the arithmetic mean of the arguments as float |
+//! | `stats::weighted_mean` | 2             | Tuple of Numeric, Tuple of Numeric | Returns the mean of the numbers in the first tuple weighted by the numbers in the second tuple of the same length as float. Fails if the weights sum up to zero |
+//! | `stats::median`      | >= 1            | Numeric                       | Returns the median of the arguments as float, which is the mean of the two middle values for an even amount of arguments |
+//! | `stats::percentile`  | 2               | Tuple of Numeric, Numeric     | Returns the given percentile between 0 and 100 of the numbers in the tuple as float, interpolating linearly between the closest ranks |
+//! | `stats::variance`    | >= 2            | Numeric                       | Returns the sample variance of the arguments as float |
+//! | `stats::stdev`       | >= 2            | Numeric                       | Returns the sample standard deviation of the arguments as float |
+//! | `stats::zscore`      | 3               | Numeric, Numeric, Numeric     | Returns the z-score `(value - mean) / stdev` of the first argument for the mean and standard deviation given as second and third argument as float. Fails if the standard deviation is zero |
+//! | `stats::sma`         | 2               | Tuple of Numeric, Int         | Returns the simple moving averages of the numbers in the tuple over windows of the given length as tuple of floats. Fails if the window is not between 1 and the length of the tuple |
+//! | `stats::ema`         | 2               | Tuple of Numeric, Numeric     | Returns the exponential moving averages of the numbers in the tuple with the given smoothing factor as tuple of floats, starting with the first number. Fails if the smoothing factor is not greater than 0 and at most 1 |
+//! | `finance::percent_of` | 2              | Numeric, Numeric              | Returns `pct` percent of `value` as float, i.e. `value * pct / 100` |
+//! | `finance::pct_change` | 2              | Numeric, Numeric              | Returns the change from the old value to the new value in percent of the old value as float, i.e. `(new - old) / old * 100`. Fails if the old value is zero |
+//! | `finance::bps`       | 2               | Numeric, Numeric              | Returns the given amount of basis points of `value` as float, i.e. `value * bps / 10000` |
+//! | `finance::compound`  | 3               | Numeric, Numeric, Numeric     | Returns the principal given as first argument compounded at the rate per period given as second argument over the amount of periods given as third argument as float, i.e. `principal * (1 + rate)^periods` |
+//! | `str::regex_matches` | 2               | String, String                | Returns true if the first argument matches the regex in the second argument (Requires the `regex` feature flag) |
+//! | `str::regex_replace` | 3               | String, String, String        | Returns the first argument with all matches of the regex in the second argument replaced by the third argument (Requires the `regex` feature flag) |
 //! | `str::to_lowercase`  | 1               | String                        | Returns the lower-case version of the string |
 //! | `str::to_uppercase`  | 1               | String                        | Returns the upper-case version of the string |
+//! | `str::capitalize`    | 1               | String                        | Returns the string with its first character in upper case and all other characters in lower case, e.g. `str::capitalize("hELLO wORLD")` returns `"Hello world"` |
+//! | `str::title_case`    | 1               | String                        | Capitalizes each whitespace-separated word of the string like `str::capitalize`, e.g. `str::title_case("hello wORLD")` returns `"Hello World"` |
 //! | `str::trim`          | 1               | String                        | Strips whitespace from the start and the end of the string |
+//! | `str::trim_matches` | 2               | String, String                | Strips all characters that occur in the second argument from the start and the end of the first argument, e.g. `str::trim_matches("\"hi!\"", "\"!")` returns `"hi"` |
+//! | `str::trim_start_matches` | 2          | String, String                | Like `str::trim_matches`, but only strips characters from the start of the string |
+//! | `str::trim_end_matches` | 2            | String, String                | Like `str::trim_matches`, but only strips characters from the end of the string |
 //! | `str::from`          | >= 0            | Any                           | Returns passed value as string |
-//! | `str::substring`     | 3               | String, Int, Int              | Returns a substring of the first argument, starting at the second argument and ending at the third argument. If the last argument is omitted, the substring extends to the end of the string |
+//! | `str::substring`     | 2/3             | String, Int, Int              | Returns a substring of the first argument, starting at the byte index given as second argument and ending at the byte index given as third argument (exclusive). If the last argument is omitted, the substring extends to the end of the string. Indices that are out of bounds or within a character result in an error |
+//! | `substring`          | 3               | String, Int, Int              | Returns the substring of the first argument that starts at the character index given as second argument and has the character length given as third argument, e.g. `substring("äbc", 1, 5)` returns `"bc"`. The length is clamped to the end of the string, and only a start beyond the end of the string results in an error |
+//! | `str::char_at`       | 2               | String, Int                   | Returns the character at the character index given as second argument as string |
+//! | `str::len`           | 1               | String                        | Returns the amount of characters of the string, e.g. `str::len("äb")` returns 2 |
+//! | `str::split_once`   | 2               | String, String                | Splits the first argument at the first occurrence of the second argument and returns the parts before and after it as a 2-tuple. Returns `()` if the second argument does not occur |
+//! | `str::count`        | 2               | String, String                | Returns the amount of non-overlapping occurrences of the second argument in the first argument, e.g. `str::count("aaa", "aa")` returns 1. Fails if the second argument is empty |
+//! | `str::repeat`       | 2               | String, Int                   | Returns the string repeated the given amount of times. Fails if the amount is negative or the result would be longer than 16 MiB |
+//! | `str::indent`       | 2               | String, Int                   | Prefixes each non-empty line of the string with the given amount of spaces |
+//! | `str::wrap`         | 2               | String, Int                   | Wraps each line of the string at word boundaries, such that the lines are at most the given amount of characters long. Words that are longer than that are put on a line of their own |
+//! | `str::ljust`        | 2               | String, Int                   | Pads the string with spaces on the right to the given amount of characters. Strings that are at least that long are returned unchanged |
+//! | `str::rjust`        | 2               | String, Int                   | Pads the string with spaces on the left to the given amount of characters. Strings that are at least that long are returned unchanged |
+//! | `str::center`       | 2               | String, Int                   | Pads the string with spaces on both sides to the given amount of characters, putting the extra space on the right if the padding is odd, e.g. `str::center("ab", 5)` returns `" ab  "`. Strings that are at least that long are returned unchanged |
+//! | `str::hex`          | 1, 2            | Int, Boolean                  | Returns the hexadecimal representation of the integer, prefixed with `0x` if the optional second argument is true. Negative integers are represented by their absolute value with a leading minus |
+//! | `str::oct`          | 1, 2            | Int, Boolean                  | Returns the octal representation of the integer like `str::hex`, using the prefix `0o` |
+//! | `str::bin`          | 1, 2            | Int, Boolean                  | Returns the binary representation of the integer like `str::hex`, using the prefix `0b` |
+//! | `str::si`           | 1, 2            | Numeric, Int                  | Formats the number with the SI prefix (`p`, `n`, `µ`, `m`, `k`, `M`, `G`, `T`, `P` or `E`) that makes its magnitude at least 1 and less than 1000, e.g. `str::si(1500)` returns `"1.5k"`. The magnitude is rounded to the amount of fractional digits given as second argument, which defaults to 2 and is at most 17, and trailing zeros are removed |
+//! | `str::percent`      | 2               | Numeric, Int                  | Formats the ratio as percentage with the amount of fractional digits given as second argument, at most 17, e.g. `str::percent(0.1234, 2)` returns `"12.34%"` |
+//! | `str::similarity`   | 2               | String, String                | Returns the similarity of the strings as float between 0 and 1, computed as one minus their Levenshtein distance divided by the character length of the longer string. Two empty strings have a similarity of 1 |
 //! | `bitand`             | 2               | Int                           | Computes the bitwise and of the given integers |
 //! | `bitor`              | 2               | Int                           | Computes the bitwise or of the given integers |
 //! | `bitxor`             | 2               | Int                           | Computes the bitwise xor of the given integers |
 //! | `bitnot`             | 1               | Int                           | Computes the bitwise not of the given integer |
 //! | `shl`                | 2               | Int                           | Computes the given integer bitwise shifted left by the other given integer |
 //! | `shr`                | 2               | Int                           | Computes the given integer bitwise shifted right by the other given integer |
+//! | `url::encode`        | 1               | String                        | Percent-encodes the string as UTF-8 for use in a URL, leaving only ASCII letters, digits, `-`, `.`, `_` and `~` unchanged |
+//! | `url::decode`        | 1               | String                        | Decodes a percent-encoded string. Fails if a `%` is not followed by two hexadecimal digits or if the decoded string is not valid UTF-8 |
+//! | `base64::encode`     | 1               | String                        | Encodes the string as UTF-8 with the standard base64 alphabet, padded with `=` |
+//! | `base64::decode`     | 1               | String                        | Decodes a padded string in the standard base64 alphabet. Fails if the string is not valid base64 or if the decoded string is not valid UTF-8 |
+//! | `hash::crc32`        | 1               | String                        | Returns the CRC-32 checksum (as used by zlib and PNG) of the string encoded as UTF-8 as non-negative integer. Fails if the integer type cannot represent the checksum |
+//! | `hash::md5`          | 1               | String                        | Returns the MD5 digest of the string encoded as UTF-8 as lowercase hexadecimal string. Requires the `hash` feature flag. |
+//! | `hash::sha256`       | 1               | String                        | Returns the SHA-256 digest of the string encoded as UTF-8 as lowercase hexadecimal string. Requires the `hash` feature flag. |
+//! | `duration::parse`    | 1               | String                        | Returns the total amount of seconds of a duration like `"1h30m"` as integer. A duration consists of one or more non-negative integers, each followed by one of the units `d`, `h`, `m` or `s` |
+//! | `time::in_range`     | 3               | Int, Int, Int                 | Returns true if the timestamp given as first argument is at least the second argument and less than the third argument |
+//! | `time::add_days`     | 2               | Int, Int                      | Adds the amount of days given as second argument to the timestamp in seconds given as first argument, e.g. `time::add_days(0, 2)` returns `172800`. The amount of days may be negative |
+//! | `time::format_iso`   | 1               | Int                           | Formats the Unix timestamp in seconds as ISO-8601 date and time in UTC, e.g. `time::format_iso(0)` returns `"1970-01-01T00:00:00Z"`. Fails if the timestamp is outside of the supported range of dates. Requires the `chrono` feature flag. |
 //! | `random`             | 0               | Empty                         | Return a random float between 0 and 1. Requires the `rand` feature flag. |
 //!
 //! The `min` and `max` functions can deal with a mixture of integer and floating point arguments.
 //! If the maximum or minimum is an integer, then an integer is returned.
 //! Otherwise, a float is returned.
+//! The same holds for `math::fmin` and `math::fmax`, which differ from `min` and `max` only in how they treat NaN arguments.
 //!
-//! The regex functions require the feature flag `regex_support`.
+//! The regex functions require the feature flag `regex`.
+//! The cryptographic hash functions `hash::md5` and `hash::sha256` require the feature flag `hash`, while `hash::crc32` is always available.
+//! The function `time::format_iso` requires the feature flag `chrono`.
 //!
 //! ### Values
 //!
 //! Operators take values as arguments and produce values as results.
-//! Values can be booleans, integer or floating point numbers, strings, tuples or the empty type.
+//! Values can be booleans, integer or floating point numbers, strings, tuples, arrays or the empty type.
 //! Values are denoted as displayed in the following table.
 //!
 //! | Value type | Example |
 //! |------------|---------|
-//! | `Value::String` | `"abc"`, `""`, `"a\"b\\c"` |
+//! | `Value::String` | `"abc"`, `""`, `"a\"b\\c"`, `'abc'`, `'don\'t'` |
 //! | `Value::Boolean` | `true`, `false` |
-//! | `Value::Int` | `3`, `-9`, `0`, `135412`, `0xfe02`, `-0x1e` |
-//! | `Value::Float` | `3.`, `.35`, `1.00`, `0.5`, `123.554`, `23e4`, `-2e-3`, `3.54e+2` |
+//! | `Value::Int` | `3`, `-9`, `0`, `135412`, `0xfe02`, `-0x1e`, `0o755`, `0b1010`, `1_000_000` |
+//! | `Value::Float` | `3.`, `.35`, `1.00`, `0.5`, `123.554`, `23e4`, `-2e-3`, `3.54e+2`, `1_000.5` |
 //! | `Value::Tuple` | `(3, 55.0, false, ())`, `(1, 2)` |
-//! | `Value::Empty` | `()` |
+//! | `Value::Array` | `{3, 55.0, false, ()}`, `{1}`, `{}` |
+//! | `Value::Empty` | `()`, `null` |
 //!
 //! By default, integers are internally represented as `i64`, and floating point numbers are represented as `f64`.
 //! The numeric types are defined by the `Context` trait and can for example be customised by implementing a custom context.
 //! Alternatively, for example the standard `HashMapContext` type takes the numeric types as type parameters, so it works with arbitrary numeric types.
-//! Tuples are represented as `Vec<Value>` and empty values are not stored, but represented by Rust's unit type `()` where necessary.
+//! The numeric types `I128NumericTypes` use `i128` instead of `i64` for integers, e.g. `HashMapContext::<I128NumericTypes>::new()`.
+//! Integer literals may be written in hexadecimal, octal or binary with the prefixes `0x`, `0o` and `0b`, and may contain underscores to separate digits.
+//! String literals can be enclosed in double quotes or single quotes, like `"don't"` or `'say "hi"'`, where the other quote is an ordinary character.
+//! Within string literals, `\"`, `\'` and `\\` denote a double quote, a single quote and a backslash, `\n`, `\t`, `\r` and `\0` denote a newline, a tab, a carriage return and a null character,
+//! and `\u{...}` denotes the unicode character with the code point given as one to six hexadecimal digits, e.g. `"\u{1F600}"`.
+//! String literals may span multiple lines, and a backslash at the end of a line continues the string on the next line without a newline, for both `\n` and `\r\n` line endings.
+//! The keyword `null` denotes the empty value, e.g. in `x == null`, so it cannot be used as a variable or function identifier.
+//! Tuples and arrays are represented as `Vec<Value>` and empty values are not stored, but represented by Rust's unit type `()` where necessary.
+//!
+//! By default, integer arithmetic that overflows results in an error.
+//! This can be changed per context with an `OverflowMode`, which for `HashMapContext` is set with `set_overflow_mode`.
+//! Overflowing results can then be promoted to floats, wrap around or saturate at the bounds of the integer type.
+//!
+//! Values can be formatted for display with `Value::format_with` and a `ValueFormatter`,
+//! which configures the amount of fractional digits of floats, a thousands separator, and the delimiters of tuples and the separator of their elements.
 //!
 //! There exist type aliases for some of the types.
-//! They include `IntType`, `FloatType`, `TupleType` and `EmptyType`.
+//! They include `IntType`, `FloatType`, `TupleType`, `ArrayType` and `EmptyType`.
 //!
 //! Values can be constructed either directly or using `from` functions.
 //! For integers and floats, the `from` functions are `from_int` and `from_float`, and all others use the `From` trait.
@@ -480,6 +667,8 @@
 //!
 //! This crate allows to define arbitrary functions to be used in parsed expressions.
 //! A function is defined as a `Function` instance, wrapping an `fn(&Value) -> EvalexprResult<Value>`.
+//! Functions created with `Function::new_with_context` additionally receive the context they are called from,
+//! which allows them to read variables or to call other functions of the context.
 //! The definition needs to be included in the [`Context`](#contexts) that is used for evaluation.
 //! As of now, functions cannot be defined within the expression, but that might change in the future.
 //!
@@ -506,11 +695,34 @@
 //!
 //! Functions have a precedence of 190.
 //!
+//! ### Lambdas
+//!
+//! A lambda `x -> body` is an anonymous function that can be passed instead of a function name to the builtin functions `map`, `filter`, `all` and `any`.
+//! The body is evaluated for each element of the tuple with the parameter bound to the element, while all other identifiers are resolved in the context of the call.
+//! A lambda with multiple parameters like `(a, b) -> a * b` binds its parameters to the elements of each element, which must then be a tuple of as many elements.
+//! Lambdas cannot modify the context, and they cannot be evaluated otherwise, e.g. stored in a variable.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context: HashMapContext<DefaultNumericTypes> = context_map! { "offset" => int 10 }.unwrap(); // Do proper error handling here
+//! assert_eq!(
+//!     eval_with_context("map((1, 2, 3), x -> x + offset)", &context),
+//!     Ok(Value::Tuple(vec![Value::from_int(11), Value::from_int(12), Value::from_int(13)]))
+//! );
+//! assert_eq!(
+//!     eval_with_context("map(((1, 2), (3, 4)), (a, b) -> a * b)", &context),
+//!     Ok(Value::Tuple(vec![Value::from_int(2), Value::from_int(12)]))
+//! );
+//! assert_eq!(eval_with_context("any((1, 2, 3), x -> x > 2)", &context), Ok(Value::from(true)));
+//! assert_eq!(eval("x -> x + 1"), Err(EvalexprError::UnappliedLambda));
+//! ```
+//!
 //! ### Comments
 //!
 //! Evalexpr supports C-style inline comments and end-of-line comments.
 //! Inline comments are started with a `/*` and terminated with a `*/`.
-//! End-of-line comments are started with a `//` and terminated with a newline character.
+//! End-of-line comments are started with a `//` and terminated with a newline character, unless the integer division operator is enabled in the `ParseConfig`.
 //! For example:
 //!
 //! ```rust
@@ -528,6 +740,39 @@
 //! );
 //! ```
 //!
+//! ### Parse Configuration
+//!
+//! The way expression strings are parsed can be customised with a `ParseConfig` that is passed to `build_operator_tree_with_config`.
+//! The default configuration parses expressions like `build_operator_tree`.
+//!
+//! | Option | Default | Description |
+//! |--------|---------|-------------|
+//! | `newline_as_semicolon` | `false` | A newline outside of parentheses acts as a semicolon, unless the line ends with an incomplete expression |
+//! | `keyword_operators` | `false` | The keywords `and`, `or` and `not` are parsed as the operators `&&`, &#124;&#124; and `!` |
+//! | `percent_literals` | `false` | A number directly followed by `%` is parsed as a percentage, e.g. `5%` as `0.05`, unless the `%` is followed by a value like in `10%3` |
+//! | `integer_division_operator` | `false` | `//` is parsed as the integer division operator, e.g. `7.5 // 2` as `3`, instead of starting an end-of-line comment |
+//! | `max_nesting_depth` | `None` | The maximum depth to which parentheses, brackets, curly braces and operators may be nested. Deeper expressions are rejected with `EvalexprError::RecursionLimitExceeded` |
+//! | `boolean_literal` | only `true` and `false` | Additional literals that are parsed as booleans, like `yes` and `no` |
+//!
+//! For untrusted input, limiting the nesting depth avoids exhausting the stack while building the operator tree,
+//! and `Node::eval_with_context_limited` does the same for evaluating it.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let config = ParseConfig::new().with_newline_as_semicolon(true);
+//! let script = build_operator_tree_with_config::<DefaultNumericTypes>(
+//!     "
+//!     a = 2
+//!     b = a +
+//!         3
+//!     a * b
+//!     ",
+//!     &config,
+//! ).unwrap(); // Do proper error handling here
+//! assert_eq!(script.eval(), Ok(Value::from_int(10)));
+//! ```
+//!
 //! ### [Serde](https://serde.rs)
 //!
 //! To use this crate with serde, the `serde_support` feature flag has to be set.
@@ -581,26 +826,30 @@
 #![allow(clippy::get_first)]
 
 pub use crate::{
+    config::{OverflowMode, ParseConfig},
     context::{
-        Context, ContextWithMutableFunctions, ContextWithMutableVariables, EmptyContext,
-        EmptyContextWithBuiltinFunctions, HashMapContext, IterateVariablesContext,
+        Context, ContextSnapshot, ContextStack, ContextWithMutableFunctions,
+        ContextWithMutableVariables, DryRunContext, EmptyContext, EmptyContextWithBuiltinFunctions,
+        HashMapContext, IterateFunctionsContext, IterateVariablesContext,
     },
     error::{EvalexprError, EvalexprResult},
     function::Function,
     interface::*,
     operator::Operator,
-    token::PartialToken,
-    tree::Node,
+    token::{tokenize, tokenize_with_spans, PartialToken, SpannedToken, Token},
+    tree::{EvalBuffer, Node, NodeDiff},
     value::{
         numeric_types::{
-            default_numeric_types::DefaultNumericTypes, EvalexprFloat, EvalexprInt,
-            EvalexprNumericTypes,
+            default_numeric_types::DefaultNumericTypes, i128_numeric_types::I128NumericTypes,
+            EvalexprFloat, EvalexprInt, EvalexprNumericTypes,
         },
         value_type::ValueType,
-        EmptyType, TupleType, Value, EMPTY_VALUE,
+        ArrayType, EmptyType, TupleType, Value, ValueFormatter, EMPTY_VALUE,
     },
 };
 
+pub mod batch;
+mod config;
 mod context;
 pub mod error;
 #[cfg(feature = "serde")]
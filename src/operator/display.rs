@@ -14,6 +14,7 @@ impl<NumericTypes: EvalexprNumericTypes> Display for Operator<NumericTypes> {
             Neg => write!(f, "-"),
             Mul => write!(f, "*"),
             Div => write!(f, "/"),
+            IntDiv => write!(f, "//"),
             Mod => write!(f, "%"),
             Exp => write!(f, "^"),
 
@@ -27,6 +28,15 @@ impl<NumericTypes: EvalexprNumericTypes> Display for Operator<NumericTypes> {
             Or => write!(f, "||"),
             Not => write!(f, "!"),
 
+            BitAnd => write!(f, "&"),
+            BitOr => write!(f, "|"),
+            Shl => write!(f, "<<"),
+            Shr => write!(f, ">>"),
+
+            Index => write!(f, "[]"),
+            Conditional => write!(f, "?"),
+            Coalesce => write!(f, "??"),
+
             Assign => write!(f, " = "),
             AddAssign => write!(f, " += "),
             SubAssign => write!(f, " -= "),
@@ -36,9 +46,14 @@ impl<NumericTypes: EvalexprNumericTypes> Display for Operator<NumericTypes> {
             ExpAssign => write!(f, " ^= "),
             AndAssign => write!(f, " &&= "),
             OrAssign => write!(f, " ||= "),
+            Lambda { params } => match params.as_slice() {
+                [param] => write!(f, "{} ->", param),
+                params => write!(f, "({}) ->", params.join(", ")),
+            },
 
             Tuple => write!(f, ", "),
             Chain => write!(f, "; "),
+            Array => write!(f, "{{}}"),
 
             Const { value } => write!(f, "{}", value),
             VariableIdentifierWrite { identifier } | VariableIdentifierRead { identifier } => {
@@ -1,13 +1,16 @@
-use crate::function::builtin::builtin_function;
+use crate::function::builtin::{builtin_function, call_builtin_function_with_context};
 
 use crate::{
+    config::OverflowMode,
     context::Context,
     error::*,
+    function::Function,
     value::{
         numeric_types::{
             default_numeric_types::DefaultNumericTypes, EvalexprFloat, EvalexprInt,
             EvalexprNumericTypes,
         },
+        value_type::ValueType,
         Value,
     },
     ContextWithMutableVariables,
@@ -32,6 +35,8 @@ pub enum Operator<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     Mul,
     /// A binary division operator.
     Div,
+    /// A binary integer division operator, which floors the quotient.
+    IntDiv,
     /// A binary modulo operator.
     Mod,
     /// A binary exponentiation operator.
@@ -56,6 +61,25 @@ pub enum Operator<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     /// A binary logical not operator.
     Not,
 
+    /// A binary bitwise and operator.
+    BitAnd,
+    /// A binary bitwise or operator.
+    BitOr,
+    /// A binary bitwise left shift operator.
+    Shl,
+    /// A binary bitwise right shift operator.
+    Shr,
+
+    /// A binary index operator `a[i]`, selecting the element of a tuple or array, or the character of a string, at the given index.
+    Index,
+
+    /// A ternary conditional operator `condition ? a : b`.
+    /// Only the branch selected by the condition is evaluated.
+    Conditional,
+    /// A binary null-coalescing operator `a ?? b`.
+    /// The second argument is only evaluated if the first one is empty or reads a variable that does not exist.
+    Coalesce,
+
     /// A binary assignment operator.
     Assign,
     /// A binary add-assign operator.
@@ -75,8 +99,18 @@ pub enum Operator<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     /// A binary or-assign operator.
     OrAssign,
 
+    /// A lambda expression `x -> body` or `(a, b) -> body`, whose only child is its body.
+    /// It can only be evaluated as the function argument of the builtin functions `map`, `filter`, `all` and `any`.
+    Lambda {
+        /// The identifiers of the parameters of the lambda.
+        params: Vec<String>,
+    },
+
     /// An n-ary tuple constructor.
     Tuple,
+    /// An array constructor.
+    /// Its only child is the root of the expression within the curly braces, whose tuple elements become the elements of the array.
+    Array,
     /// An n-ary subexpression chain.
     Chain,
 
@@ -128,7 +162,7 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
 
             Add | Sub => 95,
             Neg => 110,
-            Mul | Div | Mod => 100,
+            Mul | Div | IntDiv | Mod => 100,
             Exp => 120,
 
             Eq | Neq | Gt | Lt | Geq | Leq => 80,
@@ -136,11 +170,21 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             Or => 70,
             Not => 110,
 
+            Shl | Shr => 90,
+            BitAnd => 87,
+            BitOr => 85,
+
+            Index => 190,
+            Coalesce => 65,
+            Conditional => 60,
+
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => 50,
+            Lambda { .. } => 45,
 
             Tuple => 40,
             Chain => 0,
+            Array => 200,
 
             Const { .. } => 200,
             VariableIdentifierWrite { .. } | VariableIdentifierRead { .. } => 200,
@@ -153,7 +197,10 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
     /// Left-to-right chaining has priority if operators with different order but same precedence are chained.
     pub(crate) const fn is_left_to_right(&self) -> bool {
         use crate::operator::Operator::*;
-        !matches!(self, Assign | FunctionIdentifier { .. })
+        !matches!(
+            self,
+            Assign | Conditional | Lambda { .. } | FunctionIdentifier { .. }
+        )
     }
 
     /// Returns true if chains of this operator should be flattened into one operator with many arguments.
@@ -172,11 +219,16 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
     pub(crate) const fn max_argument_amount(&self) -> Option<usize> {
         use crate::operator::Operator::*;
         match self {
-            Add | Sub | Mul | Div | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | And | Or
-            | Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
-            | AndAssign | OrAssign => Some(2),
+            Add | Sub | Mul | Div | IntDiv | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | And
+            | Or | BitAnd | BitOr | Shl | Shr | Index | Coalesce | Assign | AddAssign
+            | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign | AndAssign | OrAssign => {
+                Some(2)
+            },
+            // While parsing, the parameters are the first child, until they are moved into the operator.
+            Lambda { .. } => Some(2),
+            Conditional => Some(3),
             Tuple | Chain => None,
-            Not | Neg | RootNode => Some(1),
+            Not | Neg | RootNode | Array => Some(1),
             Const { .. } => Some(0),
             VariableIdentifierWrite { .. } | VariableIdentifierRead { .. } => Some(0),
             FunctionIdentifier { .. } => Some(1),
@@ -185,7 +237,69 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
 
     /// Returns true if this operator is unary, i.e. it requires exactly one argument.
     pub(crate) fn is_unary(&self) -> bool {
-        self.max_argument_amount() == Some(1) && *self != Operator::RootNode
+        self.max_argument_amount() == Some(1)
+            && !matches!(self, Operator::RootNode | Operator::Array)
+    }
+
+    /// Returns the name of the variant of this operator, without its fields.
+    pub(crate) fn name(&self) -> &'static str {
+        use crate::operator::Operator::*;
+        match self {
+            RootNode => "RootNode",
+            Add => "Add",
+            Sub => "Sub",
+            Neg => "Neg",
+            Mul => "Mul",
+            Div => "Div",
+            IntDiv => "IntDiv",
+            Mod => "Mod",
+            Exp => "Exp",
+            Eq => "Eq",
+            Neq => "Neq",
+            Gt => "Gt",
+            Lt => "Lt",
+            Geq => "Geq",
+            Leq => "Leq",
+            And => "And",
+            Or => "Or",
+            Not => "Not",
+            BitAnd => "BitAnd",
+            BitOr => "BitOr",
+            Shl => "Shl",
+            Shr => "Shr",
+            Index => "Index",
+            Conditional => "Conditional",
+            Coalesce => "Coalesce",
+            Assign => "Assign",
+            AddAssign => "AddAssign",
+            SubAssign => "SubAssign",
+            MulAssign => "MulAssign",
+            DivAssign => "DivAssign",
+            ModAssign => "ModAssign",
+            ExpAssign => "ExpAssign",
+            AndAssign => "AndAssign",
+            Lambda { .. } => "Lambda",
+            OrAssign => "OrAssign",
+            Tuple => "Tuple",
+            Chain => "Chain",
+            Array => "Array",
+            Const { .. } => "Const",
+            VariableIdentifierWrite { .. } => "VariableIdentifierWrite",
+            VariableIdentifierRead { .. } => "VariableIdentifierRead",
+            FunctionIdentifier { .. } => "FunctionIdentifier",
+        }
+    }
+
+    /// Returns an error if the given argument of this arithmetic operator is a boolean.
+    fn expect_no_boolean(
+        &self,
+        argument: &Value<NumericTypes>,
+    ) -> EvalexprResult<(), NumericTypes> {
+        if let Value::Boolean(boolean) = argument {
+            Err(EvalexprError::boolean_in_arithmetic(self.clone(), *boolean))
+        } else {
+            Ok(())
+        }
     }
 
     /// Evaluates the operator with the given arguments and context.
@@ -205,6 +319,8 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             Add => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 expect_number_or_string(&arguments[0])?;
                 expect_number_or_string(&arguments[1])?;
 
@@ -214,7 +330,13 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
                     result.push_str(&b);
                     Ok(Value::String(result))
                 } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    a.checked_add(&b).map(Value::Int)
+                    eval_int_arithmetic(
+                        context.overflow_mode(),
+                        || a.checked_add(&b),
+                        || a.wrapping_add(&b),
+                        || a.saturating_add(&b),
+                        || NumericTypes::int_as_float(&a) + NumericTypes::int_as_float(&b),
+                    )
                 } else if let (Ok(a), Ok(b)) = (arguments[0].as_number(), arguments[1].as_number())
                 {
                     Ok(Value::Float(a + b))
@@ -230,11 +352,19 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             Sub => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
                 if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    a.checked_sub(&b).map(Value::Int)
+                    eval_int_arithmetic(
+                        context.overflow_mode(),
+                        || a.checked_sub(&b),
+                        || a.wrapping_sub(&b),
+                        || a.saturating_sub(&b),
+                        || NumericTypes::int_as_float(&a) - NumericTypes::int_as_float(&b),
+                    )
                 } else {
                     Ok(Value::Float(
                         arguments[0].as_number()? - arguments[1].as_number()?,
@@ -243,21 +373,36 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             Neg => {
                 expect_operator_argument_amount(arguments.len(), 1)?;
+                self.expect_no_boolean(&arguments[0])?;
                 arguments[0].as_number()?;
 
                 if let Ok(a) = arguments[0].as_int() {
-                    a.checked_neg().map(Value::Int)
+                    eval_int_arithmetic(
+                        context.overflow_mode(),
+                        || a.checked_neg(),
+                        || a.wrapping_neg(),
+                        || a.saturating_neg(),
+                        || -NumericTypes::int_as_float(&a),
+                    )
                 } else {
                     Ok(Value::Float(-arguments[0].as_number()?))
                 }
             },
             Mul => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
                 if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    a.checked_mul(&b).map(Value::Int)
+                    eval_int_arithmetic(
+                        context.overflow_mode(),
+                        || a.checked_mul(&b),
+                        || a.wrapping_mul(&b),
+                        || a.saturating_mul(&b),
+                        || NumericTypes::int_as_float(&a) * NumericTypes::int_as_float(&b),
+                    )
                 } else {
                     Ok(Value::Float(
                         arguments[0].as_number()? * arguments[1].as_number()?,
@@ -266,6 +411,8 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             Div => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
@@ -277,8 +424,42 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
                     ))
                 }
             },
+            IntDiv => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
+                arguments[0].as_number()?;
+                arguments[1].as_number()?;
+
+                if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
+                    let quotient = a.checked_div(&b)?;
+                    let zero = NumericTypes::Int::from_usize(0)?;
+                    let remainder = a.checked_rem(&b)?;
+                    // The integer division truncates, so an inexact negative quotient needs to be rounded down.
+                    if remainder != zero && (remainder < zero) != (b < zero) {
+                        quotient
+                            .checked_sub(&NumericTypes::Int::from_usize(1)?)
+                            .map(Value::Int)
+                    } else {
+                        Ok(Value::Int(quotient))
+                    }
+                } else {
+                    let quotient = (arguments[0].as_number()? / arguments[1].as_number()?).floor();
+                    // Quotients outside of the int range, including infinity and NaN, are not saturated but rejected.
+                    float_as_exact_int::<NumericTypes>(&quotient)
+                        .map(Value::Int)
+                        .ok_or_else(|| {
+                            EvalexprError::division_error(
+                                arguments[0].clone(),
+                                arguments[1].clone(),
+                            )
+                        })
+                }
+            },
             Mod => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
@@ -292,6 +473,8 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             Exp => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
+                self.expect_no_boolean(&arguments[0])?;
+                self.expect_no_boolean(&arguments[1])?;
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
@@ -302,12 +485,12 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             Eq => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
 
-                Ok(Value::Boolean(arguments[0] == arguments[1]))
+                Ok(Value::Boolean(values_equal(&arguments[0], &arguments[1])))
             },
             Neq => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
 
-                Ok(Value::Boolean(arguments[0] != arguments[1]))
+                Ok(Value::Boolean(!values_equal(&arguments[0], &arguments[1])))
             },
             Gt => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
@@ -389,9 +572,90 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
 
                 Ok(Value::Boolean(!a))
             },
+            BitAnd => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a.bitand(&b)))
+            },
+            BitOr => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a.bitor(&b)))
+            },
+            Shl => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                a.bit_shift_left(&b).map(Value::Int)
+            },
+            Shr => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                a.bit_shift_right(&b).map(Value::Int)
+            },
+            Index => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let element = match &arguments[0] {
+                    Value::Tuple(elements) | Value::Array(elements) => {
+                        let index = arguments[1].as_int()?.into_usize()?;
+                        elements.get(index).cloned()
+                    },
+                    Value::String(string) => {
+                        let index = arguments[1].as_int()?.into_usize()?;
+                        string
+                            .chars()
+                            .nth(index)
+                            .map(|character| Value::String(character.to_string()))
+                    },
+                    value => {
+                        return Err(EvalexprError::type_error(
+                            value.clone(),
+                            vec![ValueType::Tuple, ValueType::Array, ValueType::String],
+                        ))
+                    },
+                };
+
+                element.ok_or(EvalexprError::OutOfBoundsAccess)
+            },
+            Conditional => {
+                expect_operator_argument_amount(arguments.len(), 3)?;
+
+                if arguments[0].as_boolean()? {
+                    Ok(arguments[1].clone())
+                } else {
+                    Ok(arguments[2].clone())
+                }
+            },
+            Coalesce => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+
+                if arguments[0].is_empty() {
+                    Ok(arguments[1].clone())
+                } else {
+                    Ok(arguments[0].clone())
+                }
+            },
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => Err(EvalexprError::ContextNotMutable),
+            Lambda { .. } => Err(EvalexprError::UnappliedLambda),
             Tuple => Ok(Value::Tuple(arguments.into())),
+            Array => {
+                expect_operator_argument_amount(arguments.len(), 1)?;
+
+                // Evaluated nodes distinguish `{(1, 2)}` from `{1, 2}`, but the evaluated content cannot.
+                Ok(Value::Array(match &arguments[0] {
+                    Value::Tuple(tuple) => tuple.clone(),
+                    Value::Empty => Vec::new(),
+                    value => vec![value.clone()],
+                }))
+            },
             Chain => {
                 if arguments.is_empty() {
                     return Err(EvalexprError::wrong_operator_argument_amount(0, 1));
@@ -412,8 +676,8 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             VariableIdentifierRead { identifier } => {
                 expect_operator_argument_amount(arguments.len(), 0)?;
 
-                if let Some(value) = context.get_value(identifier).cloned() {
-                    Ok(value)
+                if let Some(value) = context.get_value_cow(identifier) {
+                    Ok(value.into_owned())
                 } else {
                     Err(EvalexprError::VariableIdentifierNotFound(
                         identifier.clone(),
@@ -422,22 +686,8 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
             },
             FunctionIdentifier { identifier } => {
                 expect_operator_argument_amount(arguments.len(), 1)?;
-                let arguments = &arguments[0];
-
-                match context.call_function(identifier, arguments) {
-                    Err(EvalexprError::FunctionIdentifierNotFound(_))
-                        if !context.are_builtin_functions_disabled() =>
-                    {
-                        if let Some(builtin_function) = builtin_function(identifier) {
-                            builtin_function.call(arguments)
-                        } else {
-                            Err(EvalexprError::FunctionIdentifierNotFound(
-                                identifier.clone(),
-                            ))
-                        }
-                    },
-                    result => result,
-                }
+
+                call_function(identifier, &arguments[0], context, None)
             },
         }
     }
@@ -457,7 +707,7 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
                 let target = arguments[0].as_string()?;
                 context.set_value(target, arguments[1].clone())?;
 
-                Ok(Value::Empty)
+                Ok(arguments[1].clone())
             },
             AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign | AndAssign
             | OrAssign => {
@@ -484,11 +734,93 @@ impl<NumericTypes: EvalexprNumericTypes> Operator<NumericTypes> {
                         self
                     ),
                 }?;
-                context.set_value(target, result)?;
+                context.set_value(target, result.clone())?;
 
-                Ok(Value::Empty)
+                Ok(result)
             },
             _ => self.eval(arguments, context),
         }
     }
 }
+
+/// Calls the function with the given identifier from the context.
+/// If the context does not know the function and builtin functions are enabled, the builtin function with that identifier is called instead.
+///
+/// The builtin function can be given as `resolved_builtin_function` if it was resolved ahead of time.
+/// Otherwise, it is looked up by its identifier.
+pub(crate) fn call_function<C: Context>(
+    identifier: &str,
+    argument: &Value<C::NumericTypes>,
+    context: &C,
+    resolved_builtin_function: Option<&Function<C::NumericTypes>>,
+) -> EvalexprResultValue<C::NumericTypes> {
+    match context.call_function_with_context(identifier, argument, context) {
+        Err(EvalexprError::FunctionIdentifierNotFound(_))
+            if !context.are_builtin_functions_disabled() =>
+        {
+            if let Some(result) = call_builtin_function_with_context(identifier, argument, context)
+            {
+                result
+            } else if let Some(builtin_function) = resolved_builtin_function {
+                builtin_function.call(argument, context)
+            } else if let Some(builtin_function) = builtin_function(identifier) {
+                builtin_function.call(argument, context)
+            } else {
+                Err(EvalexprError::FunctionIdentifierNotFound(
+                    identifier.to_string(),
+                ))
+            }
+        },
+        result => result,
+    }
+}
+
+/// Evaluates an integer arithmetic operation, handling overflow as defined by the given overflow mode.
+///
+/// The closures compute the operation with overflow checking, with wrapping, with saturation, and with floats, respectively.
+fn eval_int_arithmetic<NumericTypes: EvalexprNumericTypes>(
+    overflow_mode: OverflowMode,
+    checked: impl FnOnce() -> EvalexprResult<NumericTypes::Int, NumericTypes>,
+    wrapping: impl FnOnce() -> NumericTypes::Int,
+    saturating: impl FnOnce() -> NumericTypes::Int,
+    promoted: impl FnOnce() -> NumericTypes::Float,
+) -> EvalexprResultValue<NumericTypes> {
+    match overflow_mode {
+        OverflowMode::Error => checked().map(Value::Int),
+        OverflowMode::PromoteToFloat => {
+            Ok(checked().map_or_else(|_| Value::Float(promoted()), Value::Int))
+        },
+        OverflowMode::Wrapping => Ok(Value::Int(wrapping())),
+        OverflowMode::Saturating => Ok(Value::Int(saturating())),
+    }
+}
+
+/// Returns true if the values are equal, where an int and a float are equal if they denote the same number.
+/// All other values are compared structurally, such that e.g. `(1, 2)` and `(1, 2.0)` are not equal.
+pub(crate) fn values_equal<NumericTypes: EvalexprNumericTypes>(
+    a: &Value<NumericTypes>,
+    b: &Value<NumericTypes>,
+) -> bool {
+    match (a, b) {
+        (Value::Int(int), Value::Float(float)) | (Value::Float(float), Value::Int(int)) => {
+            // Converting the int to a float is not an option, as large ints are rounded when converted to floats.
+            float_as_exact_int::<NumericTypes>(float).map_or(false, |float| float == *int)
+        },
+        (a, b) => a == b,
+    }
+}
+
+/// Converts a float into an int if it denotes an integer within the range of the int type, and returns `None` otherwise.
+///
+/// Unlike `float_as_int`, this never saturates.
+/// The range is derived from `Int::MIN`, which is assumed to be a negated power of two, and hence representable exactly as a float.
+pub(crate) fn float_as_exact_int<NumericTypes: EvalexprNumericTypes>(
+    float: &NumericTypes::Float,
+) -> Option<NumericTypes::Int> {
+    let min = NumericTypes::int_as_float(&NumericTypes::Int::MIN);
+    if float.floor() == *float && *float >= min && *float < -min {
+        Some(NumericTypes::float_as_int(float))
+    } else {
+        None
+    }
+}
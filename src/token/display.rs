@@ -15,6 +15,7 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for Token<NumericTypes> {
             Minus => write!(f, "-"),
             Star => write!(f, "*"),
             Slash => write!(f, "/"),
+            DoubleSlash => write!(f, "//"),
             Percent => write!(f, "%"),
             Hat => write!(f, "^"),
 
@@ -29,10 +30,24 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for Token<NumericTypes> {
             Or => write!(f, "||"),
             Not => write!(f, "!"),
 
+            // Bitwise
+            BitAnd => write!(f, "&"),
+            BitOr => write!(f, "|"),
+            Shl => write!(f, "<<"),
+            Shr => write!(f, ">>"),
+
             // Precedence
             LBrace => write!(f, "("),
             RBrace => write!(f, ")"),
 
+            // Arrays
+            LCurlyBrace => write!(f, "{{"),
+            RCurlyBrace => write!(f, "}}"),
+
+            // Indexing
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
+
             // Assignment
             Assign => write!(f, "="),
             PlusAssign => write!(f, "+="),
@@ -44,15 +59,22 @@ impl<NumericTypes: EvalexprNumericTypes> fmt::Display for Token<NumericTypes> {
             AndAssign => write!(f, "&&="),
             OrAssign => write!(f, "||="),
 
+            // Conditional
+            QuestionMark => write!(f, "?"),
+            Colon => write!(f, ":"),
+            Coalesce => write!(f, "??"),
+
             // Special
             Comma => write!(f, ","),
             Semicolon => write!(f, ";"),
+            Arrow => write!(f, "->"),
 
             // Values => write!(f, ""), Variables and Functions
             Identifier(identifier) => identifier.fmt(f),
             Float(float) => float.fmt(f),
             Int(int) => int.fmt(f),
             Boolean(boolean) => boolean.fmt(f),
+            Null => write!(f, "null"),
             String(string) => fmt::Debug::fmt(string, f),
         }
     }
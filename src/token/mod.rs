@@ -1,6 +1,11 @@
-use std::str::FromStr;
+use std::{
+    iter::Peekable,
+    ops::Range,
+    str::{CharIndices, FromStr},
+};
 
 use crate::{
+    config::ParseConfig,
     error::{EvalexprError, EvalexprResult},
     value::numeric_types::{
         default_numeric_types::DefaultNumericTypes, EvalexprInt, EvalexprNumericTypes,
@@ -9,54 +14,129 @@ use crate::{
 
 mod display;
 
+/// A token of an expression string, as produced by [`tokenize`].
 #[derive(Clone, PartialEq, Debug)]
 pub enum Token<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     // Arithmetic
+    /// A plus `+`.
     Plus,
+    /// A minus `-`.
     Minus,
+    /// A star `*`.
     Star,
+    /// A slash `/`.
     Slash,
+    /// A double slash `//`, if the integer division operator is enabled.
+    DoubleSlash,
+    /// A percent sign `%`.
     Percent,
+    /// A hat `^`, or a double star `**`.
     Hat,
 
     // Logic
+    /// An equality operator `==`.
     Eq,
+    /// An inequality operator `!=`.
     Neq,
+    /// A greater-than sign `>`.
     Gt,
+    /// A lower-than sign `<`.
     Lt,
+    /// A greater-than-or-equal operator `>=`.
     Geq,
+    /// A lower-than-or-equal operator `<=`.
     Leq,
+    /// A logical and `&&`, or the keyword `and` if keyword operators are enabled.
     And,
+    /// A logical or `||`, or the keyword `or` if keyword operators are enabled.
     Or,
+    /// A logical not `!`, or the keyword `not` if keyword operators are enabled.
     Not,
 
+    // Bitwise
+    /// A bitwise and `&`.
+    BitAnd,
+    /// A bitwise or `|`.
+    BitOr,
+    /// A left shift `<<`.
+    Shl,
+    /// A right shift `>>`.
+    Shr,
+
     // Precedence
+    /// An opening parenthesis `(`.
     LBrace,
+    /// A closing parenthesis `)`.
     RBrace,
 
+    // Arrays
+    /// An opening curly brace `{`.
+    LCurlyBrace,
+    /// A closing curly brace `}`.
+    RCurlyBrace,
+
+    // Indexing
+    /// An opening bracket `[`.
+    LBracket,
+    /// A closing bracket `]`.
+    RBracket,
+
     // Assignment
+    /// An assignment `=`.
     Assign,
+    /// A sum-assignment `+=`.
     PlusAssign,
+    /// A difference-assignment `-=`.
     MinusAssign,
+    /// A product-assignment `*=`.
     StarAssign,
+    /// A division-assignment `/=`.
     SlashAssign,
+    /// A modulo-assignment `%=`.
     PercentAssign,
+    /// An exponentiation-assignment `^=` or `**=`.
     HatAssign,
+    /// A logical-and-assignment `&&=`.
     AndAssign,
+    /// A logical-or-assignment `||=`.
     OrAssign,
 
+    // Conditional
+    /// A question mark `?`.
+    QuestionMark,
+    /// A single colon `:`.
+    Colon,
+    /// A double question mark `??`.
+    Coalesce,
+
     // Special
+    /// A comma `,`.
     Comma,
+    /// A semicolon `;`, or a newline that separates expressions if newlines act as semicolons.
     Semicolon,
+    /// An arrow `->`, separating the parameters of a lambda from its body.
+    Arrow,
 
     // Values, Variables and Functions
+    /// An identifier of a variable or function.
     Identifier(String),
+    /// A float literal.
     Float(NumericTypes::Float),
+    /// An integer literal.
     Int(NumericTypes::Int),
+    /// A boolean literal.
     Boolean(bool),
+    /// The literal `null` of the empty value.
+    Null,
+    /// A string literal, with its escape sequences resolved.
     String(String),
 }
 
+/// A token together with the byte range of the expression string it was parsed from, as produced by [`tokenize_with_spans`].
+pub type SpannedToken<NumericTypes = DefaultNumericTypes> = (Token<NumericTypes>, Range<usize>);
+
+type SpannedPartialToken<NumericTypes> = (PartialToken<NumericTypes>, Range<usize>);
+
 /// A partial token is an input character whose meaning depends on the characters around it.
 #[derive(Clone, Debug, PartialEq)]
 pub enum PartialToken<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
@@ -107,6 +187,14 @@ fn char_to_partial_token<NumericTypes: EvalexprNumericTypes>(
         '(' => PartialToken::Token(Token::LBrace),
         ')' => PartialToken::Token(Token::RBrace),
 
+        '{' => PartialToken::Token(Token::LCurlyBrace),
+        '}' => PartialToken::Token(Token::RCurlyBrace),
+
+        '[' => PartialToken::Token(Token::LBracket),
+        ']' => PartialToken::Token(Token::RBracket),
+
+        '?' => PartialToken::Token(Token::QuestionMark),
+
         ',' => PartialToken::Token(Token::Comma),
         ';' => PartialToken::Token(Token::Semicolon),
 
@@ -135,6 +223,7 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Minus => false,
             Token::Star => false,
             Token::Slash => false,
+            Token::DoubleSlash => false,
             Token::Percent => false,
             Token::Hat => false,
 
@@ -148,11 +237,27 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Or => false,
             Token::Not => false,
 
+            Token::BitAnd => false,
+            Token::BitOr => false,
+            Token::Shl => false,
+            Token::Shr => false,
+
             Token::LBrace => true,
             Token::RBrace => false,
 
+            Token::LCurlyBrace => true,
+            Token::RCurlyBrace => false,
+
+            Token::LBracket => false,
+            Token::RBracket => false,
+
+            Token::QuestionMark => false,
+            Token::Colon => false,
+            Token::Coalesce => false,
+
             Token::Comma => false,
             Token::Semicolon => false,
+            Token::Arrow => false,
 
             Token::Assign => false,
             Token::PlusAssign => false,
@@ -168,6 +273,7 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Float(_) => true,
             Token::Int(_) => true,
             Token::Boolean(_) => true,
+            Token::Null => true,
             Token::String(_) => true,
         }
     }
@@ -179,6 +285,7 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Minus => false,
             Token::Star => false,
             Token::Slash => false,
+            Token::DoubleSlash => false,
             Token::Percent => false,
             Token::Hat => false,
 
@@ -192,11 +299,27 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Or => false,
             Token::Not => false,
 
+            Token::BitAnd => false,
+            Token::BitOr => false,
+            Token::Shl => false,
+            Token::Shr => false,
+
             Token::LBrace => false,
             Token::RBrace => true,
 
+            Token::LCurlyBrace => false,
+            Token::RCurlyBrace => true,
+
+            Token::LBracket => false,
+            Token::RBracket => true,
+
+            Token::QuestionMark => false,
+            Token::Colon => false,
+            Token::Coalesce => false,
+
             Token::Comma => false,
             Token::Semicolon => false,
+            Token::Arrow => false,
 
             Token::Assign => false,
             Token::PlusAssign => false,
@@ -212,6 +335,7 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
             Token::Float(_) => true,
             Token::Int(_) => true,
             Token::Boolean(_) => true,
+            Token::Null => true,
             Token::String(_) => true,
         }
     }
@@ -235,58 +359,107 @@ impl<NumericTypes: EvalexprNumericTypes> Token<NumericTypes> {
 }
 
 /// Parses an escape sequence within a string literal.
+/// Returns `None` for a line continuation, i.e. a backslash directly before a `\n` or `\r\n` newline, which is removed from the string.
 fn parse_escape_sequence<Iter: Iterator<Item = char>, NumericTypes: EvalexprNumericTypes>(
     iter: &mut Iter,
-) -> EvalexprResult<char, NumericTypes> {
+) -> EvalexprResult<Option<char>, NumericTypes> {
     match iter.next() {
-        Some('"') => Ok('"'),
-        Some('\\') => Ok('\\'),
+        Some('"') => Ok(Some('"')),
+        Some('\'') => Ok(Some('\'')),
+        Some('\\') => Ok(Some('\\')),
+        Some('n') => Ok(Some('\n')),
+        Some('t') => Ok(Some('\t')),
+        Some('r') => Ok(Some('\r')),
+        Some('0') => Ok(Some('\0')),
+        Some('u') => parse_unicode_escape_sequence(iter).map(Some),
+        Some('\n') => Ok(None),
+        Some('\r') => match iter.next() {
+            Some('\n') => Ok(None),
+            Some(c) => Err(EvalexprError::IllegalEscapeSequence(format!("\\\r{}", c))),
+            None => Err(EvalexprError::IllegalEscapeSequence("\\\r".to_string())),
+        },
         Some(c) => Err(EvalexprError::IllegalEscapeSequence(format!("\\{}", c))),
         None => Err(EvalexprError::IllegalEscapeSequence("\\".to_string())),
     }
 }
 
+/// Parses the code point of a unicode escape sequence like `\u{1F600}` within a string literal, after the `\u`.
+/// The code point consists of one to six hexadecimal digits.
+fn parse_unicode_escape_sequence<
+    Iter: Iterator<Item = char>,
+    NumericTypes: EvalexprNumericTypes,
+>(
+    iter: &mut Iter,
+) -> EvalexprResult<char, NumericTypes> {
+    let mut sequence = String::from("\\u");
+    if let Some(c) = iter.next() {
+        sequence.push(c);
+        if c == '{' {
+            // Stop at the first character that cannot be part of the code point, such that it is included in the error.
+            for c in iter.by_ref() {
+                sequence.push(c);
+                if !c.is_ascii_hexdigit() {
+                    break;
+                }
+            }
+        }
+    }
+
+    sequence
+        .strip_prefix("\\u{")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .filter(|digits| (1..=6).contains(&digits.len()))
+        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+        .and_then(char::from_u32)
+        .ok_or(EvalexprError::IllegalEscapeSequence(sequence))
+}
+
 /// Parses a string value from the given character iterator.
 ///
 /// The first character from the iterator is interpreted as first character of the string.
-/// The string is terminated by a double quote `"`.
-/// Occurrences of `"` within the string can be escaped with `\`.
+/// The string is terminated by the given quote, which is either a double quote `"` or a single quote `'`.
+/// The other quote is an ordinary character within the string.
+/// Occurrences of `"` and `'` within the string can be escaped with `\`.
 /// The backslash needs to be escaped with another backslash `\`.
+/// Newlines, tabs, carriage returns and null characters can be written as `\n`, `\t`, `\r` and `\0`,
+/// and any other character as unicode escape sequence like `\u{1F600}`.
+/// The string may span multiple lines, and a backslash at the end of a line joins it with the next line without the newline.
 fn parse_string_literal<Iter: Iterator<Item = char>, NumericTypes: EvalexprNumericTypes>(
     mut iter: &mut Iter,
+    quote: char,
 ) -> EvalexprResult<PartialToken<NumericTypes>, NumericTypes> {
     let mut result = String::new();
 
     while let Some(c) = iter.next() {
         match c {
-            '"' => return Ok(PartialToken::Token(Token::String(result))),
-            '\\' => result.push(parse_escape_sequence(&mut iter)?),
+            c if c == quote => return Ok(PartialToken::Token(Token::String(result))),
+            '\\' => result.extend(parse_escape_sequence(&mut iter)?),
             c => result.push(c),
         }
     }
 
-    Err(EvalexprError::UnmatchedDoubleQuote)
+    if quote == '\'' {
+        Err(EvalexprError::UnmatchedSingleQuote)
+    } else {
+        Err(EvalexprError::UnmatchedDoubleQuote)
+    }
 }
 
 fn try_skip_comment<NumericTypes: EvalexprNumericTypes>(
-    iter: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    iter: &mut Peekable<CharIndices<'_>>,
 ) -> EvalexprResult<bool, NumericTypes> {
     let mut matched = false;
-    if let Some(lookahead) = iter.peek() {
+    if let Some((_, lookahead)) = iter.peek() {
         if *lookahead == '/' {
             matched = true;
             iter.next();
-            // line comment
-            for c in iter {
-                if c == '\n' {
-                    break;
-                }
-            }
+            // line comment, the terminating newline is left in the iterator
+            while iter.next_if(|(_, c)| *c != '\n').is_some() {}
         } else if *lookahead == '*' {
             // inline commment
             iter.next();
-            while let Some(c) = iter.next() {
-                if let Some(next) = iter.peek() {
+            while let Some((_, c)) = iter.next() {
+                if let Some((_, next)) = iter.peek() {
                     if c == '*' && *next == '/' {
                         matched = true;
                         iter.next();
@@ -305,37 +478,132 @@ fn try_skip_comment<NumericTypes: EvalexprNumericTypes>(
     Ok(matched)
 }
 
-/// Converts a string to a vector of partial tokens.
+/// Returns true if the given partial token can be the last token of a complete expression.
+fn ends_expression<NumericTypes: EvalexprNumericTypes>(
+    partial_token: &PartialToken<NumericTypes>,
+    config: &ParseConfig,
+) -> bool {
+    match partial_token {
+        PartialToken::Token(token) => token.is_rightsided_value(),
+        PartialToken::Literal(literal) => {
+            keyword_operator::<NumericTypes>(literal, config).is_none()
+        },
+        _ => false,
+    }
+}
+
+/// Returns the operator token denoted by the given literal if it is a keyword operator enabled in the given config.
+fn keyword_operator<NumericTypes: EvalexprNumericTypes>(
+    literal: &str,
+    config: &ParseConfig,
+) -> Option<Token<NumericTypes>> {
+    if !config.keyword_operators() {
+        return None;
+    }
+
+    match literal {
+        "and" => Some(Token::And),
+        "or" => Some(Token::Or),
+        "not" => Some(Token::Not),
+        _ => None,
+    }
+}
+
+/// Returns the byte offset of the next character of the given iterator, or the length of the string if there is none.
+fn next_offset(iter: &mut Peekable<CharIndices<'_>>, string: &str) -> usize {
+    iter.peek().map_or(string.len(), |(offset, _)| *offset)
+}
+
+/// Converts a string to a vector of partial tokens, each together with the byte range of the string it was parsed from.
 fn str_to_partial_tokens<NumericTypes: EvalexprNumericTypes>(
     string: &str,
-) -> EvalexprResult<Vec<PartialToken<NumericTypes>>, NumericTypes> {
-    let mut result = Vec::new();
-    let mut iter = string.chars().peekable();
+    config: &ParseConfig,
+) -> EvalexprResult<Vec<SpannedPartialToken<NumericTypes>>, NumericTypes> {
+    let mut result: Vec<SpannedPartialToken<NumericTypes>> = Vec::new();
+    let mut iter = string.char_indices().peekable();
+    // Used to decide if a newline acts as a semicolon.
+    let mut brace_depth = 0usize;
+    // The byte offset of a newline that acts as a semicolon once another token follows.
+    let mut pending_semicolon = None;
 
-    while let Some(c) = iter.next() {
-        if c == '"' {
-            result.push(parse_string_literal(&mut iter)?);
+    while let Some((start, c)) = iter.next() {
+        if c == '"' || c == '\'' {
+            if let Some(newline) = pending_semicolon.take() {
+                result.push((PartialToken::Token(Token::Semicolon), newline..newline + 1));
+            }
+            let partial_token = parse_string_literal(&mut (&mut iter).map(|(_, c)| c), c)?;
+            result.push((partial_token, start..next_offset(&mut iter, string)));
         } else {
-            let partial_token = char_to_partial_token(c);
+            let partial_token = if c == ':' && iter.next_if(|(_, c)| *c == ':').is_none() {
+                // A single colon separates the branches of a conditional,
+                // while double colons are part of identifiers like `math::ln`.
+                PartialToken::Token(Token::Colon)
+            } else if c == ':' {
+                PartialToken::Literal("::".to_string())
+            } else {
+                char_to_partial_token(c)
+            };
 
             if let PartialToken::Slash = partial_token {
-                if try_skip_comment(&mut iter)? {
+                // With the integer division operator enabled, `//` does not start an end-of-line comment.
+                let is_integer_division =
+                    config.integer_division_operator() && matches!(iter.peek(), Some((_, '/')));
+                if !is_integer_division && try_skip_comment(&mut iter)? {
                     continue;
                 }
             }
 
-            let if_let_successful =
-                if let (Some(PartialToken::Literal(last)), PartialToken::Literal(literal)) =
-                    (result.last_mut(), &partial_token)
-                {
-                    last.push_str(literal);
-                    true
-                } else {
-                    false
-                };
+            if config.newline_as_semicolon() {
+                match &partial_token {
+                    PartialToken::Whitespace => {
+                        if c == '\n'
+                            && brace_depth == 0
+                            && result
+                                .iter()
+                                .rev()
+                                .find(|(partial_token, _)| {
+                                    *partial_token != PartialToken::Whitespace
+                                })
+                                .map_or(false, |(partial_token, _)| {
+                                    ends_expression(partial_token, config)
+                                })
+                        {
+                            // The semicolon is only inserted once another token follows,
+                            // such that trailing newlines do not terminate the expression chain.
+                            pending_semicolon.get_or_insert(start);
+                        }
+                    },
+                    partial_token => {
+                        if let Some(newline) = pending_semicolon.take() {
+                            if *partial_token != PartialToken::Token(Token::Semicolon) {
+                                result.push((
+                                    PartialToken::Token(Token::Semicolon),
+                                    newline..newline + 1,
+                                ));
+                            }
+                        }
+
+                        match partial_token {
+                            PartialToken::Token(
+                                Token::LBrace | Token::LCurlyBrace | Token::LBracket,
+                            ) => brace_depth += 1,
+                            PartialToken::Token(
+                                Token::RBrace | Token::RCurlyBrace | Token::RBracket,
+                            ) => brace_depth = brace_depth.saturating_sub(1),
+                            _ => {},
+                        }
+                    },
+                }
+            }
 
-            if !if_let_successful {
-                result.push(partial_token);
+            let end = next_offset(&mut iter, string);
+            if let (Some((PartialToken::Literal(last), span)), PartialToken::Literal(literal)) =
+                (result.last_mut(), &partial_token)
+            {
+                last.push_str(literal);
+                span.end = end;
+            } else {
+                result.push((partial_token, start..end));
             }
         }
     }
@@ -343,17 +611,28 @@ fn str_to_partial_tokens<NumericTypes: EvalexprNumericTypes>(
 }
 
 /// Resolves all partial tokens by converting them to complex tokens.
+/// The byte range of a token spans the byte ranges of all partial tokens it was resolved from.
 fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
-    mut tokens: &[PartialToken<NumericTypes>],
-) -> EvalexprResult<Vec<Token<NumericTypes>>, NumericTypes> {
+    mut tokens: &[SpannedPartialToken<NumericTypes>],
+    config: &ParseConfig,
+) -> EvalexprResult<Vec<SpannedToken<NumericTypes>>, NumericTypes> {
     let mut result = Vec::new();
     while !tokens.is_empty() {
-        let first = tokens[0].clone();
-        let second = tokens.get(1).cloned();
-        let third = tokens.get(2).cloned();
+        let first = tokens[0].0.clone();
+        let second = tokens
+            .get(1)
+            .map(|(partial_token, _)| partial_token.clone());
+        let third = tokens
+            .get(2)
+            .map(|(partial_token, _)| partial_token.clone());
         let mut cutoff = 2;
 
-        result.extend(match first {
+        let token = match first {
+            PartialToken::Token(Token::QuestionMark)
+                if second == Some(PartialToken::Token(Token::QuestionMark)) =>
+            {
+                Some(Token::Coalesce)
+            },
             PartialToken::Token(token) => {
                 cutoff = 1;
                 Some(token)
@@ -367,6 +646,7 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Minus => match second {
                 Some(PartialToken::Eq) => Some(Token::MinusAssign),
+                Some(PartialToken::Gt) => Some(Token::Arrow),
                 _ => {
                     cutoff = 1;
                     Some(Token::Minus)
@@ -374,6 +654,14 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Star => match second {
                 Some(PartialToken::Eq) => Some(Token::StarAssign),
+                // A double star is an alternative spelling of the exponentiation operator.
+                Some(PartialToken::Star) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::HatAssign)
+                    },
+                    _ => Some(Token::Hat),
+                },
                 _ => {
                     cutoff = 1;
                     Some(Token::Star)
@@ -381,6 +669,8 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Slash => match second {
                 Some(PartialToken::Eq) => Some(Token::SlashAssign),
+                // Two adjacent slashes only remain if they are not an end-of-line comment.
+                Some(PartialToken::Slash) => Some(Token::DoubleSlash),
                 _ => {
                     cutoff = 1;
                     Some(Token::Slash)
@@ -402,12 +692,18 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Literal(literal) => {
                 cutoff = 1;
-                if let Ok(number) = parse_dec_or_hex::<NumericTypes>(&literal) {
+                if let Some(token) = keyword_operator(&literal, config) {
+                    Some(token)
+                } else if let Ok(number) = parse_int_literal::<NumericTypes>(&literal) {
                     Some(Token::Int(number))
-                } else if let Ok(number) = literal.parse::<NumericTypes::Float>() {
+                } else if let Ok(number) = parse_float_literal::<NumericTypes>(&literal) {
                     Some(Token::Float(number))
                 } else if let Ok(boolean) = literal.parse::<bool>() {
                     Some(Token::Boolean(boolean))
+                } else if literal == "null" {
+                    Some(Token::Null)
+                } else if let Some(boolean) = config.boolean_literal(&literal) {
+                    Some(Token::Boolean(boolean))
                 } else {
                     // If there are two tokens following this one, check if the next one is
                     // a plus or a minus. If so, then attempt to parse all three tokens as a
@@ -417,9 +713,10 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
                         (Some(second), Some(third))
                             if second == PartialToken::Minus || second == PartialToken::Plus =>
                         {
-                            if let Ok(number) = format!("{}{}{}", literal, second, third)
-                                .parse::<NumericTypes::Float>()
-                            {
+                            if let Ok(number) = parse_float_literal::<NumericTypes>(&format!(
+                                "{}{}{}",
+                                literal, second, third
+                            )) {
                                 cutoff = 3;
                                 Some(Token::Float(number))
                             } else {
@@ -450,6 +747,7 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Gt => match second {
                 Some(PartialToken::Eq) => Some(Token::Geq),
+                Some(PartialToken::Gt) => Some(Token::Shr),
                 _ => {
                     cutoff = 1;
                     Some(Token::Gt)
@@ -457,6 +755,7 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
             },
             PartialToken::Lt => match second {
                 Some(PartialToken::Eq) => Some(Token::Leq),
+                Some(PartialToken::Lt) => Some(Token::Shl),
                 _ => {
                     cutoff = 1;
                     Some(Token::Lt)
@@ -470,7 +769,10 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
                     },
                     _ => Some(Token::And),
                 },
-                _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::BitAnd)
+                },
             },
             PartialToken::VerticalBar => match second {
                 Some(PartialToken::VerticalBar) => match third {
@@ -480,35 +782,198 @@ fn partial_tokens_to_tokens<NumericTypes: EvalexprNumericTypes>(
                     },
                     _ => Some(Token::Or),
                 },
-                _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::BitOr)
+                },
             },
-        });
+        };
 
+        let token = match token {
+            Some(Token::Int(int)) if is_percent_sign(&tokens[cutoff..], config) => {
+                cutoff += 1;
+                Some(Token::Float(percent::<NumericTypes>(
+                    NumericTypes::int_as_float(&int),
+                )?))
+            },
+            Some(Token::Float(float)) if is_percent_sign(&tokens[cutoff..], config) => {
+                cutoff += 1;
+                Some(Token::Float(percent::<NumericTypes>(float)?))
+            },
+            token => token,
+        };
+
+        if let Some(token) = token {
+            result.push((token, tokens[0].1.start..tokens[cutoff - 1].1.end));
+        }
         tokens = &tokens[cutoff..];
     }
     Ok(result)
 }
 
-pub(crate) fn tokenize<NumericTypes: EvalexprNumericTypes>(
+/// Returns true if the given partial tokens following a number start with a percent sign that makes the number a percent literal.
+///
+/// This is the case if percent literals are enabled in the given config, and the percent sign directly follows the number
+/// and is not followed by a value, which would make it the modulo operator.
+fn is_percent_sign<NumericTypes: EvalexprNumericTypes>(
+    tokens: &[SpannedPartialToken<NumericTypes>],
+    config: &ParseConfig,
+) -> bool {
+    if !config.percent_literals() || !matches!(tokens.first(), Some((PartialToken::Percent, _))) {
+        return false;
+    }
+
+    match tokens[1..]
+        .iter()
+        .map(|(partial_token, _)| partial_token)
+        .find(|partial_token| **partial_token != PartialToken::Whitespace)
+    {
+        // The percent sign of a percent-assignment directly precedes a single equal sign.
+        Some(PartialToken::Eq) => !matches!(
+            (tokens.get(1), tokens.get(2)),
+            (Some((PartialToken::Eq, _)), next) if !matches!(next, Some((PartialToken::Eq, _)))
+        ),
+        Some(PartialToken::Literal(literal)) => {
+            keyword_operator::<NumericTypes>(literal, config).is_some()
+        },
+        Some(PartialToken::Token(token)) => !token.is_leftsided_value(),
+        _ => true,
+    }
+}
+
+/// Converts the number of a percent literal to the fraction it denotes.
+fn percent<NumericTypes: EvalexprNumericTypes>(
+    number: NumericTypes::Float,
+) -> EvalexprResult<NumericTypes::Float, NumericTypes> {
+    let hundred = NumericTypes::int_as_float(&NumericTypes::Int::from_usize(100)?);
+    Ok(number / hundred)
+}
+
+/// Splits the given expression string into tokens, without parsing them into an operator tree.
+///
+/// Whitespace and comments are skipped.
+/// Fails if the string cannot be split into tokens, for example because of an unmatched double quote.
+///
+/// # Example
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(
+///     tokenize::<DefaultNumericTypes>("a + 2"),
+///     Ok(vec![Token::Identifier("a".into()), Token::Plus, Token::Int(2)])
+/// );
+/// ```
+pub fn tokenize<NumericTypes: EvalexprNumericTypes>(
+    string: &str,
+) -> EvalexprResult<Vec<Token<NumericTypes>>, NumericTypes> {
+    tokenize_with_config(string, &ParseConfig::default())
+}
+
+/// Splits the given expression string into tokens like [`tokenize`], and returns each token together with the byte range of the string it was parsed from.
+///
+/// This allows to map tokens back to the string, for example for syntax highlighting.
+/// The range of a string literal includes its double quotes, and a newline that is converted to a semicolon has the range of the newline.
+///
+/// # Example
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(
+///     tokenize_with_spans::<DefaultNumericTypes>("ab >= \"c\""),
+///     Ok(vec![
+///         (Token::Identifier("ab".into()), 0..2),
+///         (Token::Geq, 3..5),
+///         (Token::String("c".into()), 6..9),
+///     ])
+/// );
+/// ```
+pub fn tokenize_with_spans<NumericTypes: EvalexprNumericTypes>(
+    string: &str,
+) -> EvalexprResult<Vec<SpannedToken<NumericTypes>>, NumericTypes> {
+    tokenize_with_config_and_spans(string, &ParseConfig::default())
+}
+
+pub(crate) fn tokenize_with_config<NumericTypes: EvalexprNumericTypes>(
     string: &str,
+    config: &ParseConfig,
 ) -> EvalexprResult<Vec<Token<NumericTypes>>, NumericTypes> {
-    partial_tokens_to_tokens(&str_to_partial_tokens(string)?)
+    Ok(tokenize_with_config_and_spans(string, config)?
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect())
 }
 
-fn parse_dec_or_hex<NumericTypes: EvalexprNumericTypes>(
+/// Fails with an `EvalexprError::RecursionLimitExceeded` if parentheses, brackets or curly braces are nested deeper than the given depth.
+pub(crate) fn check_nesting_depth<NumericTypes: EvalexprNumericTypes>(
+    tokens: &[SpannedToken<NumericTypes>],
+    max_nesting_depth: usize,
+) -> EvalexprResult<(), NumericTypes> {
+    let mut depth = 0usize;
+    for (token, _) in tokens {
+        match token {
+            Token::LBrace | Token::LCurlyBrace | Token::LBracket => {
+                depth += 1;
+                if depth > max_nesting_depth {
+                    return Err(EvalexprError::RecursionLimitExceeded);
+                }
+            },
+            Token::RBrace | Token::RCurlyBrace | Token::RBracket => depth = depth.saturating_sub(1),
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn tokenize_with_config_and_spans<NumericTypes: EvalexprNumericTypes>(
+    string: &str,
+    config: &ParseConfig,
+) -> EvalexprResult<Vec<SpannedToken<NumericTypes>>, NumericTypes> {
+    partial_tokens_to_tokens(&str_to_partial_tokens(string, config)?, config)
+}
+
+/// Parses an integer literal, which is either decimal or hexadecimal, octal or binary with the prefix `0x`, `0o` or `0b` in any case.
+/// Literals starting with a digit may contain underscores as digit separators, like `1_000_000` or `0xFF_FF`.
+fn parse_int_literal<NumericTypes: EvalexprNumericTypes>(
     literal: &str,
 ) -> Result<NumericTypes::Int, ()> {
-    if let Some(literal) = literal.strip_prefix("0x") {
-        NumericTypes::Int::from_hex_str(literal)
+    let literal = strip_digit_separators(literal);
+
+    let radix = match literal.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => return NumericTypes::Int::from_str(&literal).map_err(|_| ()),
+    };
+    NumericTypes::Int::from_radix_str(&literal[2..], radix)
+}
+
+/// Parses a float literal, which may contain underscores as digit separators like integer literals, e.g. `1_000.5`.
+fn parse_float_literal<NumericTypes: EvalexprNumericTypes>(
+    literal: &str,
+) -> Result<NumericTypes::Float, ()> {
+    strip_digit_separators(literal).parse().map_err(|_| ())
+}
+
+/// Removes the underscores used as digit separators from the given literal, if it starts with a digit.
+/// Literals that do not start with a digit are identifiers, which may contain underscores.
+fn strip_digit_separators(literal: &str) -> String {
+    if literal.starts_with(|character: char| character.is_ascii_digit()) {
+        literal.replace('_', "")
     } else {
-        NumericTypes::Int::from_str(literal).map_err(|_| ())
+        literal.to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        token::{char_to_partial_token, tokenize, Token},
+        config::ParseConfig,
+        token::{
+            char_to_partial_token, tokenize, tokenize_with_config_and_spans, tokenize_with_spans,
+            Token,
+        },
         value::numeric_types::default_numeric_types::DefaultNumericTypes,
     };
     use std::fmt::Write;
@@ -516,7 +981,8 @@ mod tests {
     #[test]
     fn test_partial_token_display() {
         let chars = vec![
-            '+', '-', '*', '/', '%', '^', '(', ')', ',', ';', '=', '!', '>', '<', '&', '|', ' ',
+            '+', '-', '*', '/', '%', '^', '(', ')', '?', ',', ';', '=', '!', '>', '<', '&', '|',
+            ' ',
         ];
 
         for char in chars {
@@ -530,7 +996,7 @@ mod tests {
     #[test]
     fn test_token_display() {
         let token_string =
-            "+ - * / % ^ == != > < >= <= && || ! ( ) = += -= *= /= %= ^= &&= ||= , ; ";
+            "+ - * / % ^ == != > < >= <= && || ! & | << >> ( ) = += -= *= /= %= ^= &&= ||= ? : , ; ";
         let tokens = tokenize::<DefaultNumericTypes>(token_string).unwrap();
         let mut result_string = String::new();
 
@@ -575,4 +1041,41 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_token_spans() {
+        let tokens = tokenize_with_spans::<DefaultNumericTypes>(
+            "math::ln(x) >= 1e-3 && s == \"ä\\\"\" /* comment */ ||= ä2",
+        )
+        .unwrap();
+        assert_eq!(
+            tokens,
+            [
+                (Token::Identifier("math::ln".to_string()), 0..8),
+                (Token::LBrace, 8..9),
+                (Token::Identifier("x".to_string()), 9..10),
+                (Token::RBrace, 10..11),
+                (Token::Geq, 12..14),
+                (Token::Float(1e-3), 15..19),
+                (Token::And, 20..22),
+                (Token::Identifier("s".to_string()), 23..24),
+                (Token::Eq, 25..27),
+                (Token::String("ä\"".to_string()), 28..34),
+                (Token::OrAssign, 49..52),
+                (Token::Identifier("ä2".to_string()), 53..56),
+            ]
+        );
+
+        let config = ParseConfig::new().with_newline_as_semicolon(true);
+        let tokens =
+            tokenize_with_config_and_spans::<DefaultNumericTypes>("a\n\n\"b\"", &config).unwrap();
+        assert_eq!(
+            tokens,
+            [
+                (Token::Identifier("a".to_string()), 0..1),
+                (Token::Semicolon, 1..2),
+                (Token::String("b".to_string()), 3..6),
+            ]
+        );
+    }
 }
@@ -0,0 +1,92 @@
+use crate::{value::numeric_types::EvalexprNumericTypes, Node};
+
+/// A structural difference between two operator trees, as returned by [`Node::diff`].
+///
+/// Nodes are identified by their path, which is the sequence of indices of the [`children`](Node::children) leading from the root node to them.
+/// The root node itself has the empty path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeDiff {
+    /// The node at the path has a different operator in the other tree, so the whole subtree rooted at it differs.
+    Replaced {
+        /// The path of the replaced node.
+        path: Vec<usize>,
+    },
+    /// The other tree has an additional child at the path, which does not exist in this tree.
+    Added {
+        /// The path of the added node in the other tree.
+        path: Vec<usize>,
+    },
+    /// The node at the path does not exist in the other tree.
+    Removed {
+        /// The path of the removed node in this tree.
+        path: Vec<usize>,
+    },
+}
+
+impl NodeDiff {
+    /// Returns the path of the node this difference refers to.
+    pub fn path(&self) -> &[usize] {
+        match self {
+            NodeDiff::Replaced { path } | NodeDiff::Added { path } | NodeDiff::Removed { path } => {
+                path
+            },
+        }
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
+    /// Returns the structural differences between the operator tree rooted at this node and the one rooted at the given node.
+    ///
+    /// The trees are compared top-down.
+    /// If two nodes at the same path have different operators, the node is reported as replaced without comparing its children.
+    /// Otherwise, their children are compared pairwise, and children that only exist in one of the trees are reported as added or removed.
+    /// The differences are returned in depth-first order, and the result is empty if the trees are equal.
+    ///
+    /// This allows to keep caches of subtrees, e.g. of their values, when an expression is edited:
+    /// only the entries of the reported nodes and their ancestors need to be invalidated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let old = build_operator_tree::<DefaultNumericTypes>("a * 2 + 1").unwrap(); // Do proper error handling here
+    /// let new = build_operator_tree::<DefaultNumericTypes>("a * 3 + 1").unwrap(); // Do proper error handling here
+    /// // The path leads from the root node to the sum, the product and its second factor.
+    /// assert_eq!(old.diff(&new), vec![NodeDiff::Replaced { path: vec![0, 0, 1] }]);
+    /// assert_eq!(old.diff(&old), vec![]);
+    /// ```
+    pub fn diff(&self, other: &Self) -> Vec<NodeDiff> {
+        let mut diffs = Vec::new();
+        self.diff_at(other, &mut Vec::new(), &mut diffs);
+        diffs
+    }
+
+    fn diff_at(&self, other: &Self, path: &mut Vec<usize>, diffs: &mut Vec<NodeDiff>) {
+        if self.operator() != other.operator() {
+            diffs.push(NodeDiff::Replaced { path: path.clone() });
+            return;
+        }
+
+        let common_len = self.children().len().min(other.children().len());
+        for (index, (child, other_child)) in
+            self.children().iter().zip(other.children()).enumerate()
+        {
+            path.push(index);
+            child.diff_at(other_child, path, diffs);
+            path.pop();
+        }
+
+        let child_path = |index| path.iter().copied().chain(Some(index)).collect();
+        diffs.extend(
+            (common_len..self.children().len()).map(|index| NodeDiff::Removed {
+                path: child_path(index),
+            }),
+        );
+        diffs.extend(
+            (common_len..other.children().len()).map(|index| NodeDiff::Added {
+                path: child_path(index),
+            }),
+        );
+    }
+}
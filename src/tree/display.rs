@@ -1,12 +1,221 @@
-use crate::Node;
+use crate::{
+    operator::Operator,
+    value::numeric_types::{EvalexprFloat, EvalexprNumericTypes},
+    Node, Value,
+};
 use std::fmt::{Display, Error, Formatter};
 
-impl Display for Node {
+/// Displays the operator tree as an expression in infix notation, which can be parsed back into an equivalent operator tree.
+///
+/// Parentheses are only displayed where required by the precedence and associativity of the operators,
+/// except for function arguments, which are always displayed in parentheses.
+///
+/// Operators that need to be enabled in the [`ParseConfig`](crate::ParseConfig) are displayed in their enabled spelling,
+/// so the resulting expression only parses back into an equivalent operator tree with the same configuration.
+/// For example, the integer division operator is displayed as `//`, which starts an end-of-line comment with the default configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let mut node = build_operator_tree::<DefaultNumericTypes>("a * (b + 1)").unwrap(); // Do proper error handling here
+/// assert_eq!(node.to_string(), "a * (b + 1)");
+///
+/// // Rewrite `b + 1` to `b - c`
+/// let sum = &mut node.children_mut()[0].children_mut()[1].children_mut()[0];
+/// *sum.operator_mut() = Operator::Sub;
+/// *sum.children_mut()[1].operator_mut() = Operator::VariableIdentifierRead { identifier: "c".into() };
+/// assert_eq!(node.to_string(), "a * (b - c)");
+/// assert_eq!(build_operator_tree::<DefaultNumericTypes>(&node.to_string()).unwrap().to_string(), "a * (b - c)");
+/// ```
+impl<NumericTypes: EvalexprNumericTypes> Display for Node<NumericTypes> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        self.operator.fmt(f)?;
-        for child in self.children() {
-            write!(f, " {}", child)?;
+        use crate::operator::Operator::*;
+        match (self.operator(), self.children()) {
+            // The root of the whole expression is not parenthesized.
+            (RootNode, []) => Ok(()),
+            (RootNode, [child]) => child.fmt(f),
+            (Neg | Not, [operand]) => {
+                write!(f, "{}", self.operator())?;
+                fmt_operand(operand, self.operator().precedence(), false, f)
+            },
+            (Index, [subject, index]) => {
+                // Indexing associates to the left, and function calls bind as tightly, so neither needs parentheses as subject.
+                fmt_operand(subject, self.operator().precedence(), false, f)?;
+                write!(f, "[")?;
+                fmt_group(index, f)?;
+                write!(f, "]")
+            },
+            (Conditional, [condition, if_true, if_false]) => {
+                let precedence = self.operator().precedence();
+                fmt_operand(condition, precedence, true, f)?;
+                write!(f, " ? ")?;
+                fmt_group(if_true, f)?;
+                write!(f, " : ")?;
+                fmt_operand(if_false, precedence, false, f)
+            },
+            (Lambda { .. }, [body]) => {
+                write!(f, "{} ", self.operator())?;
+                fmt_operand(body, self.operator().precedence(), false, f)
+            },
+            (Tuple | Chain, elements) => {
+                let separator = if self.operator() == &Tuple {
+                    ", "
+                } else {
+                    "; "
+                };
+                for (index, element) in elements.iter().enumerate() {
+                    // The empty statement after a trailing semicolon is displayed as nothing.
+                    if self.operator() == &Chain
+                        && element.operator() == &RootNode
+                        && element.children().is_empty()
+                    {
+                        write!(f, ";")?;
+                        continue;
+                    }
+                    if index > 0 {
+                        write!(f, "{}", separator)?;
+                    }
+                    fmt_operand(element, self.operator().precedence() + 1, false, f)?;
+                }
+                Ok(())
+            },
+            (Array, [content]) => {
+                write!(f, "{{")?;
+                fmt_group(content, f)?;
+                write!(f, "}}")
+            },
+            (Const { value }, []) => fmt_value(value, f),
+            (VariableIdentifierRead { identifier }, [])
+            | (VariableIdentifierWrite { identifier }, []) => write!(f, "{}", identifier),
+            (FunctionIdentifier { identifier }, [argument]) => {
+                write!(f, "{}(", identifier)?;
+                fmt_group(argument, f)?;
+                write!(f, ")")
+            },
+            (operator, [left, right]) if operator.max_argument_amount() == Some(2) => {
+                let precedence = operator.precedence();
+                fmt_operand(left, precedence, !operator.is_left_to_right(), f)?;
+                write!(f, " {} ", operator.to_string().trim())?;
+                fmt_operand(right, precedence, operator.is_left_to_right(), f)
+            },
+            // Malformed trees are displayed in prefix notation.
+            (operator, children) => {
+                write!(f, "{}", operator)?;
+                for child in children {
+                    write!(f, " {}", child)?;
+                }
+                Ok(())
+            },
         }
-        Ok(())
+    }
+}
+
+/// Displays the operand of an operator with the given precedence, parenthesizing it if it binds less tightly than the operator.
+/// If `parenthesize_equal` is true, the operand is also parenthesized if it has the same precedence,
+/// which is required on the side against which the operator associates.
+fn fmt_operand<NumericTypes: EvalexprNumericTypes>(
+    operand: &Node<NumericTypes>,
+    precedence: i32,
+    parenthesize_equal: bool,
+    f: &mut Formatter,
+) -> Result<(), Error> {
+    let operand = operand.without_root_nodes();
+    let operand_precedence = display_precedence(operand);
+    if operand.operator() == &Operator::RootNode {
+        // Only the empty expression `()` remains a root node.
+        write!(f, "()")
+    } else if operand_precedence < precedence
+        || (parenthesize_equal && operand_precedence == precedence)
+    {
+        write!(f, "({})", operand)
+    } else {
+        operand.fmt(f)
+    }
+}
+
+/// Displays a node that is enclosed in delimiters, which replace the parentheses it may need.
+/// A tuple that is parenthesized within the delimiters keeps its parentheses,
+/// as it is a single tuple argument or array element rather than a list of them.
+fn fmt_group<NumericTypes: EvalexprNumericTypes>(
+    node: &Node<NumericTypes>,
+    f: &mut Formatter,
+) -> Result<(), Error> {
+    let content = node.without_root_nodes();
+    let parenthesized = matches!(
+        (node.operator(), node.children()),
+        (Operator::RootNode, [child]) if child.operator() == &Operator::RootNode
+    );
+    if parenthesized && content.operator() == &Operator::Tuple {
+        write!(f, "({})", content)
+    } else {
+        content.fmt(f)
+    }
+}
+
+/// Returns the precedence that determines whether a node needs to be parenthesized when it is displayed as an operand.
+fn display_precedence<NumericTypes: EvalexprNumericTypes>(node: &Node<NumericTypes>) -> i32 {
+    match node.operator() {
+        // Negative numbers are displayed with a leading minus, which parses as negation.
+        Operator::Const { value } if value.to_string().starts_with('-') => {
+            Operator::<NumericTypes>::Neg.precedence()
+        },
+        operator => operator.precedence(),
+    }
+}
+
+/// Displays a value as an expression that evaluates to it.
+fn fmt_value<NumericTypes: EvalexprNumericTypes>(
+    value: &Value<NumericTypes>,
+    f: &mut Formatter,
+) -> Result<(), Error> {
+    match value {
+        Value::String(string) => {
+            write!(f, "\"")?;
+            for character in string.chars() {
+                if matches!(character, '"' | '\\') {
+                    write!(f, "\\")?;
+                }
+                write!(f, "{}", character)?;
+            }
+            write!(f, "\"")
+        },
+        Value::Float(float) => fmt_float::<NumericTypes>(float, f),
+        Value::Tuple(elements) | Value::Array(elements) => {
+            let (open, close) = if matches!(value, Value::Tuple(_)) {
+                ("(", ")")
+            } else {
+                ("{", "}")
+            };
+            write!(f, "{}", open)?;
+            for (index, element) in elements.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                fmt_value(element, f)?;
+            }
+            write!(f, "{}", close)
+        },
+        value => write!(f, "{}", value),
+    }
+}
+
+/// Displays a float such that it is parsed as float again, even if it has no fractional part.
+/// Infinity and NaN have no literals, so they are displayed as calls of the respective builtin functions.
+fn fmt_float<NumericTypes: EvalexprNumericTypes>(
+    float: &NumericTypes::Float,
+    f: &mut Formatter,
+) -> Result<(), Error> {
+    let string = float.to_string();
+    if float.is_nan() {
+        write!(f, "math::nan()")
+    } else if float.is_infinite() {
+        let sign = if string.starts_with('-') { "-" } else { "" };
+        write!(f, "{}math::inf()", sign)
+    } else if string.contains(['.', 'e', 'E']) {
+        write!(f, "{}", string)
+    } else {
+        write!(f, "{}.0", string)
     }
 }
@@ -0,0 +1,284 @@
+use std::marker::PhantomData;
+
+use crate::{
+    config::OverflowMode,
+    error::{expect_operator_argument_amount, EvalexprError, EvalexprResult},
+    value::{numeric_types::EvalexprNumericTypes, value_type::ValueType},
+    Context, Node,
+};
+
+impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
+    /// Infers the type of the value this node evaluates to, without evaluating it.
+    ///
+    /// The types of variables are taken from the values they have in the given context.
+    /// Inference follows the type rules of the arithmetic, comparison and logical operators,
+    /// so for example `a + b` is inferred to be an `Int` if both `a` and `b` are integers, and a `Float` if one of them is a float.
+    /// If the operands of an operator have types that the operator does not accept, `EvalexprError::WrongTypeCombination` is returned.
+    ///
+    /// If the type cannot be inferred, for example because the expression calls a function or assigns a variable,
+    /// `EvalexprError::TypeUnknown` is returned.
+    /// This is also the case for integer arithmetic if the [overflow mode](crate::OverflowMode) of the context promotes overflowing results to floats.
+    /// Note that an inferred type does not guarantee that evaluation succeeds, as it may still fail e.g. because of a division by zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let context: HashMapContext<DefaultNumericTypes> = context_map! { "a" => int 1, "b" => int 2 }.unwrap(); // Do proper error handling here
+    /// let node = build_operator_tree("a + b").unwrap(); // Do proper error handling here
+    /// assert_eq!(node.infer_type(&context), Ok(ValueType::Int));
+    /// let node = build_operator_tree("a / 2.0 > b").unwrap(); // Do proper error handling here
+    /// assert_eq!(node.infer_type(&context), Ok(ValueType::Boolean));
+    /// ```
+    pub fn infer_type<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> EvalexprResult<ValueType, NumericTypes> {
+        self.infer_type_in(context)
+    }
+
+    /// Returns the type of the value this node evaluates to if it can be inferred without knowing the variables, or `None` otherwise.
+    ///
+    /// This is the case for literals and operators on them, like `1 + 2.5` or `"a" + "b"`, as well as operators that always result in the same type,
+    /// like the comparison `a < 2`, which is a `Boolean` whatever the value of `a` is.
+    /// The type is inferred like by [`infer_type`](Node::infer_type), assuming that integer overflow results in an error,
+    /// which is the default [overflow mode](crate::OverflowMode).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// assert_eq!(build_operator_tree::<DefaultNumericTypes>("1 < 2").unwrap().result_type(), Some(ValueType::Boolean));
+    /// assert_eq!(build_operator_tree::<DefaultNumericTypes>("1 + 2.5").unwrap().result_type(), Some(ValueType::Float));
+    /// assert_eq!(build_operator_tree::<DefaultNumericTypes>("a + 1").unwrap().result_type(), None);
+    /// ```
+    pub fn result_type(&self) -> Option<ValueType> {
+        self.infer_type_in(&UnknownVariables(PhantomData)).ok()
+    }
+
+    fn infer_type_in<C: TypeContext<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> EvalexprResult<ValueType, NumericTypes> {
+        use crate::operator::Operator::*;
+
+        match self.operator() {
+            RootNode => match self.children() {
+                [] => Ok(ValueType::Empty),
+                [child] => child.infer_type_in(context),
+                children => Err(EvalexprError::wrong_operator_argument_amount(
+                    children.len(),
+                    1,
+                )),
+            },
+            Const { value } => Ok(value.into()),
+            VariableIdentifierRead { identifier } => context
+                .variable_type(identifier)?
+                .ok_or_else(|| self.type_unknown()),
+            Add | Sub | Mul | Div | Mod => {
+                let [a, b] = self.infer_argument_types::<2, C>(context)?;
+                match (self.operator(), a?, b?) {
+                    (Add, ValueType::String, ValueType::String) => Ok(ValueType::String),
+                    (Add | Sub | Mul, ValueType::Int, ValueType::Int)
+                        if promotes_overflow_to_float(context) =>
+                    {
+                        Err(self.type_unknown())
+                    },
+                    (_, ValueType::Int, ValueType::Int) => Ok(ValueType::Int),
+                    (_, a, b) if is_number(a) && is_number(b) => Ok(ValueType::Float),
+                    (_, a, b) => Err(self.wrong_type_combination(vec![a, b])),
+                }
+            },
+            Exp => self.infer_binary_type(context, ValueType::Float, |a, b| {
+                is_number(a) && is_number(b)
+            }),
+            IntDiv => {
+                self.infer_binary_type(context, ValueType::Int, |a, b| is_number(a) && is_number(b))
+            },
+            Neg => {
+                let [a] = self.infer_argument_types::<1, C>(context)?;
+                match a? {
+                    ValueType::Int if promotes_overflow_to_float(context) => {
+                        Err(self.type_unknown())
+                    },
+                    a if is_number(a) => Ok(a),
+                    a => Err(self.wrong_type_combination(vec![a])),
+                }
+            },
+            Eq | Neq => self.infer_binary_type(context, ValueType::Boolean, |_, _| true),
+            Gt | Lt | Geq | Leq => self.infer_binary_type(context, ValueType::Boolean, |a, b| {
+                (a == ValueType::String && b == ValueType::String) || (is_number(a) && is_number(b))
+            }),
+            And | Or => self.infer_binary_type(context, ValueType::Boolean, |a, b| {
+                a == ValueType::Boolean && b == ValueType::Boolean
+            }),
+            Not => {
+                let [a] = self.infer_argument_types::<1, C>(context)?;
+                match a {
+                    Ok(ValueType::Boolean) | Err(_) => Ok(ValueType::Boolean),
+                    Ok(a) => Err(self.wrong_type_combination(vec![a])),
+                }
+            },
+            BitAnd | BitOr | Shl | Shr => {
+                self.infer_binary_type(context, ValueType::Int, |a, b| {
+                    a == ValueType::Int && b == ValueType::Int
+                })
+            },
+            Index => {
+                let [subject, index] = self.infer_argument_types::<2, C>(context)?;
+                match (subject, index) {
+                    (Ok(subject), _)
+                        if !matches!(
+                            subject,
+                            ValueType::Tuple | ValueType::Array | ValueType::String
+                        ) =>
+                    {
+                        Err(self.wrong_type_combination(vec![subject]))
+                    },
+                    (Ok(subject), Ok(index)) if index != ValueType::Int => {
+                        Err(self.wrong_type_combination(vec![subject, index]))
+                    },
+                    (Ok(ValueType::String), _) => Ok(ValueType::String),
+                    _ => Err(self.type_unknown()),
+                }
+            },
+            Conditional => {
+                let [condition, a, b] = self.infer_argument_types::<3, C>(context)?;
+                match (condition, a, b) {
+                    (Ok(condition), _, _) if condition != ValueType::Boolean => {
+                        Err(self.wrong_type_combination(vec![condition]))
+                    },
+                    (_, Ok(a), Ok(b)) if a == b => Ok(a),
+                    _ => Err(self.type_unknown()),
+                }
+            },
+            Coalesce => {
+                expect_operator_argument_amount(self.children().len(), 2)?;
+                match self.children()[0].infer_type_in(context) {
+                    Ok(ValueType::Empty) | Err(EvalexprError::VariableIdentifierNotFound(_)) => {
+                        self.children()[1].infer_type_in(context)
+                    },
+                    Err(EvalexprError::TypeUnknown { .. }) => Err(self.type_unknown()),
+                    result => result,
+                }
+            },
+            Tuple => Ok(ValueType::Tuple),
+            Array => Ok(ValueType::Array),
+            Lambda { .. } => Err(EvalexprError::UnappliedLambda),
+            Assign
+            | AddAssign
+            | SubAssign
+            | MulAssign
+            | DivAssign
+            | ModAssign
+            | ExpAssign
+            | AndAssign
+            | OrAssign
+            | Chain
+            | VariableIdentifierWrite { .. }
+            | FunctionIdentifier { .. } => Err(self.type_unknown()),
+        }
+    }
+
+    /// Infers the types of the exactly `N` children of this node.
+    /// Children whose type is unknown are returned as `Err(EvalexprError::TypeUnknown)`, while other errors are propagated.
+    fn infer_argument_types<const N: usize, C: TypeContext<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> EvalexprResult<[EvalexprResult<ValueType, NumericTypes>; N], NumericTypes> {
+        expect_operator_argument_amount(self.children().len(), N)?;
+
+        let mut result = [(); N].map(|_| Ok(ValueType::Empty));
+        for (child, result) in self.children().iter().zip(result.iter_mut()) {
+            *result = match child.infer_type_in(context) {
+                Err(error @ EvalexprError::TypeUnknown { .. }) => Err(error),
+                Err(error) => return Err(error),
+                value_type => value_type,
+            };
+        }
+        Ok(result)
+    }
+
+    /// Infers the type of a binary operator that always results in `result_type` if the types of its arguments are accepted.
+    /// If the type of an argument is unknown, it is assumed to be accepted.
+    fn infer_binary_type<C: TypeContext<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+        result_type: ValueType,
+        accepts: impl FnOnce(ValueType, ValueType) -> bool,
+    ) -> EvalexprResult<ValueType, NumericTypes> {
+        match self.infer_argument_types::<2, C>(context)? {
+            [Ok(a), Ok(b)] if !accepts(a, b) => Err(self.wrong_type_combination(vec![a, b])),
+            _ => Ok(result_type),
+        }
+    }
+
+    fn type_unknown(&self) -> EvalexprError<NumericTypes> {
+        EvalexprError::TypeUnknown {
+            operator: self.operator().clone(),
+        }
+    }
+
+    fn wrong_type_combination(&self, actual: Vec<ValueType>) -> EvalexprError<NumericTypes> {
+        EvalexprError::wrong_type_combination(self.operator().clone(), actual)
+    }
+}
+
+fn is_number(value_type: ValueType) -> bool {
+    matches!(value_type, ValueType::Int | ValueType::Float)
+}
+
+/// Returns true if integer arithmetic may result in a float when evaluating with the given context.
+fn promotes_overflow_to_float<C: TypeContext>(context: &C) -> bool {
+    context.overflow_mode() == OverflowMode::PromoteToFloat
+}
+
+/// The properties of a context that type inference depends on.
+trait TypeContext {
+    type NumericTypes: EvalexprNumericTypes;
+
+    /// Returns the type of the variable with the given identifier, or `None` if it is not known before evaluation.
+    fn variable_type(
+        &self,
+        identifier: &str,
+    ) -> EvalexprResult<Option<ValueType>, Self::NumericTypes>;
+
+    fn overflow_mode(&self) -> OverflowMode;
+}
+
+impl<C: Context> TypeContext for C {
+    type NumericTypes = C::NumericTypes;
+
+    fn variable_type(
+        &self,
+        identifier: &str,
+    ) -> EvalexprResult<Option<ValueType>, Self::NumericTypes> {
+        self.get_value_cow(identifier)
+            .map(|value| Some(ValueType::from(value.as_ref())))
+            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(identifier.to_string()))
+    }
+
+    fn overflow_mode(&self) -> OverflowMode {
+        Context::overflow_mode(self)
+    }
+}
+
+/// A type context in which each variable may have any type.
+struct UnknownVariables<NumericTypes>(PhantomData<NumericTypes>);
+
+impl<NumericTypes: EvalexprNumericTypes> TypeContext for UnknownVariables<NumericTypes> {
+    type NumericTypes = NumericTypes;
+
+    fn variable_type(
+        &self,
+        _identifier: &str,
+    ) -> EvalexprResult<Option<ValueType>, Self::NumericTypes> {
+        Ok(None)
+    }
+
+    fn overflow_mode(&self) -> OverflowMode {
+        OverflowMode::default()
+    }
+}
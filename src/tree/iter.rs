@@ -75,6 +75,9 @@ impl<'a, NumericTypes: EvalexprNumericTypes> Iterator for OperatorIterMut<'a, Nu
 
             if let Some(result) = result {
                 self.stack.push(result.children.iter_mut());
+                // The operator may be changed through the returned reference, invalidating the resolved builtin function and whether the node is constant.
+                result.builtin_function = None;
+                result.is_constant.reset();
                 return Some(&mut result.operator);
             }
         }
@@ -89,6 +92,8 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
 
     /// Returns a mutable iterator over all operators in this tree.
     pub fn iter_operators_mut(&mut self) -> impl Iterator<Item = &mut Operator<NumericTypes>> {
+        // Changing an operator in the tree may change whether this node is constant.
+        self.is_constant.reset();
         OperatorIterMut::new(self)
     }
 }
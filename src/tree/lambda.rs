@@ -0,0 +1,112 @@
+use crate::{
+    error::{expect_operator_argument_amount, EvalexprError, EvalexprResult, EvalexprResultValue},
+    function::builtin::{
+        call_higher_order_builtin_function, BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS,
+    },
+    operator::Operator,
+    tree::DynContext,
+    value::{numeric_types::EvalexprNumericTypes, Value},
+    Context, Node,
+};
+
+impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
+    /// Moves the parameters of all lambdas in the operator tree rooted at this node from their first child into their operator.
+    ///
+    /// While parsing, the parameters of a lambda are parsed like an ordinary expression.
+    pub(crate) fn resolve_lambda_parameters(&mut self) -> EvalexprResult<(), NumericTypes> {
+        for child in &mut self.children {
+            child.resolve_lambda_parameters()?;
+        }
+
+        if let Operator::Lambda { params } = &mut self.operator {
+            expect_operator_argument_amount(self.children.len(), 2)?;
+            *params = self.children.remove(0).lambda_parameters()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the identifiers of the lambda parameters written as this node,
+    /// which is either an identifier or a parenthesized tuple of identifiers.
+    fn lambda_parameters(&self) -> EvalexprResult<Vec<String>, NumericTypes> {
+        match (self.operator(), self.children()) {
+            (Operator::RootNode, [child]) => child.lambda_parameters(),
+            (Operator::Tuple, children) => children
+                .iter()
+                .map(|child| {
+                    child
+                        .lambda_parameter()
+                        .ok_or(EvalexprError::IllegalLambdaParameters)
+                })
+                .collect(),
+            _ => self
+                .lambda_parameter()
+                .map(|param| vec![param])
+                .ok_or(EvalexprError::IllegalLambdaParameters),
+        }
+    }
+
+    /// Returns the identifier of the single lambda parameter written as this node, if it is an identifier.
+    fn lambda_parameter(&self) -> Option<String> {
+        match (self.operator(), self.children()) {
+            (Operator::RootNode, [child]) => child.lambda_parameter(),
+            (Operator::VariableIdentifierRead { identifier }, _) => Some(identifier.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the function identifier, the node of the tuple and the lambda node
+    /// if this node is a call of a builtin higher-order function like `map(t, x -> x + 1)` with a lambda as its function argument.
+    pub(crate) fn as_lambda_call(&self) -> Option<(&str, &Self, &Self)> {
+        let Operator::FunctionIdentifier { identifier } = self.operator() else {
+            return None;
+        };
+        if !BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS.contains(&identifier.as_str()) {
+            return None;
+        }
+
+        let argument = self.children().first()?.without_root_nodes();
+        let (Operator::Tuple, [tuple, lambda]) = (argument.operator(), argument.children()) else {
+            return None;
+        };
+        let lambda = lambda.without_root_nodes();
+        matches!(lambda.operator(), Operator::Lambda { .. }).then_some((identifier, tuple, lambda))
+    }
+
+    /// Calls the builtin higher-order function with the given identifier with the given tuple and this lambda node as function.
+    /// The body of the lambda is evaluated once per element with the parameters bound to the element,
+    /// or to the elements of the element if the lambda has multiple parameters.
+    /// If a maximum depth is given, the body is evaluated with [`eval_with_context_limited`](Node::eval_with_context_limited).
+    pub(crate) fn eval_lambda_call<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        identifier: &str,
+        tuple: Value<NumericTypes>,
+        context: &C,
+        max_depth: Option<usize>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        if context.are_builtin_functions_disabled() {
+            return Err(EvalexprError::FunctionIdentifierNotFound(
+                identifier.to_string(),
+            ));
+        }
+        let Operator::Lambda { params } = self.operator() else {
+            return Err(EvalexprError::UnappliedLambda);
+        };
+        expect_operator_argument_amount(self.children().len(), 1)?;
+        let body = &self.children()[0];
+
+        call_higher_order_builtin_function(identifier, tuple.as_tuple()?, |element| {
+            let arguments = if params.len() == 1 {
+                vec![element.clone()]
+            } else {
+                element.as_fixed_len_tuple(params.len())?
+            };
+            // The context the lambda is evaluated in is wrapped as a trait object,
+            // such that evaluating nested lambdas does not instantiate evaluation for ever deeper nested context types.
+            let context = DynContext::with_arguments(context, params, arguments);
+            match max_depth {
+                Some(max_depth) => body.eval_with_context_limited(&context, max_depth),
+                None => body.eval_with_context(&context),
+            }
+        })
+    }
+}
@@ -1,24 +1,58 @@
 use crate::{
+    config::OverflowMode,
     error::EvalexprResultValue,
-    token::Token,
+    token::{SpannedToken, Token},
     value::{
         numeric_types::{default_numeric_types::DefaultNumericTypes, EvalexprNumericTypes},
-        TupleType, EMPTY_VALUE,
+        ArrayType, TupleType, EMPTY_VALUE,
     },
     Context, ContextWithMutableVariables, EmptyType, HashMapContext,
 };
 
 use crate::{
-    error::{EvalexprError, EvalexprResult},
+    error::{expect_operator_argument_amount, EvalexprError, EvalexprResult},
+    function::{builtin::builtin_function, Function},
     operator::*,
     value::Value,
 };
-use std::mem;
+pub use diff::NodeDiff;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    iter::once,
+    mem,
+    ops::{Deref, Range},
+    sync::atomic::{AtomicU8, Ordering},
+};
 
-// Exclude display module from coverage, as it prints not well-defined prefix notation.
-#[cfg(not(tarpaulin_include))]
+mod diff;
 mod display;
+mod infer_type;
 mod iter;
+mod lambda;
+mod validate;
+
+/// A reusable buffer for the arguments of the operators evaluated by [`Node::eval_with_context_and_buffer`].
+///
+/// The buffer keeps its allocation between evaluations, so it should be created once and reused for all evaluations, e.g. in a loop.
+#[derive(Debug, Clone)]
+pub struct EvalBuffer<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
+    values: Vec<Value<NumericTypes>>,
+}
+
+impl<NumericTypes: EvalexprNumericTypes> EvalBuffer<NumericTypes> {
+    /// Constructs an empty buffer.
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Default for EvalBuffer<NumericTypes> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// A node in the operator tree.
 /// The operator tree is created by the crate-level `build_operator_tree` method.
@@ -37,17 +71,65 @@ mod iter;
 /// assert_eq!(node.eval_with_context(&context), Ok(Value::from_int(3)));
 /// ```
 ///
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Node<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     operator: Operator<NumericTypes>,
     children: Vec<Node<NumericTypes>>,
+    /// The builtin function matching the identifier of a `FunctionIdentifier` operator, resolved when the node is built.
+    /// It is reset whenever the operator is accessed mutably, in which case the builtin function is looked up on each evaluation.
+    builtin_function: Option<Function<NumericTypes>>,
+    /// Whether this node is constant, computed on the first call to `is_constant`.
+    /// It is reset whenever the operator or the children of this node are accessed mutably.
+    is_constant: CachedFlag,
+}
+
+/// A boolean that is computed lazily and cached.
+/// It is stored in an atomic so that nodes can still be shared between threads.
+#[derive(Debug, Default)]
+struct CachedFlag(AtomicU8);
+
+impl CachedFlag {
+    const UNKNOWN: u8 = 0;
+    const FALSE: u8 = 1;
+    const TRUE: u8 = 2;
+
+    fn get(&self) -> Option<bool> {
+        match self.0.load(Ordering::Relaxed) {
+            Self::FALSE => Some(false),
+            Self::TRUE => Some(true),
+            _ => None,
+        }
+    }
+
+    fn set(&self, value: bool) {
+        let value = if value { Self::TRUE } else { Self::FALSE };
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    fn reset(&mut self) {
+        *self.0.get_mut() = Self::UNKNOWN;
+    }
+}
+
+impl Clone for CachedFlag {
+    fn clone(&self) -> Self {
+        Self(AtomicU8::new(self.0.load(Ordering::Relaxed)))
+    }
 }
 
 impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
     fn new(operator: Operator<NumericTypes>) -> Self {
+        let builtin_function = if let Operator::FunctionIdentifier { identifier } = &operator {
+            builtin_function(identifier)
+        } else {
+            None
+        };
+
         Self {
             children: Vec::new(),
             operator,
+            builtin_function,
+            is_constant: CachedFlag::default(),
         }
     }
 
@@ -321,6 +403,32 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
             })
     }
 
+    /// Counts how often each kind of operator occurs in this expression.
+    ///
+    /// The operators are keyed by the name of their [`Operator`] variant, e.g. `"Add"` or `"FunctionIdentifier"`.
+    /// All nodes of the tree are counted, including this node and the root nodes that are created for each parenthesized subexpression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree::<DefaultNumericTypes>("a + b * 2 + max(a, 1)").unwrap(); // Do proper error handling here
+    /// let counts = tree.operator_counts();
+    /// assert_eq!(counts["Add"], 2);
+    /// assert_eq!(counts["Mul"], 1);
+    /// assert_eq!(counts["VariableIdentifierRead"], 3);
+    /// assert_eq!(counts["Const"], 2);
+    /// assert_eq!(counts.get("Sub"), None);
+    /// ```
+    pub fn operator_counts(&self) -> HashMap<&'static str, usize> {
+        let mut counts = HashMap::new();
+        for node in once(self).chain(self.iter()) {
+            *counts.entry(node.operator().name()).or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Evaluates the operator tree rooted at this node with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -328,11 +436,198 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         &self,
         context: &C,
     ) -> EvalexprResultValue<NumericTypes> {
+        if let Some(result) = self.eval_lazily(context, None, |child, context| {
+            child.eval_with_context(*context)
+        }) {
+            return result;
+        }
+
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context(context)?);
         }
-        self.operator().eval(&arguments, context)
+        self.eval_operator(&arguments, context)
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given context, like [`eval_with_context`](Node::eval_with_context),
+    /// but fails with an `EvalexprError::RecursionLimitExceeded` instead of recursing deeper than the given depth.
+    ///
+    /// The depth of this node is one, and the depth of each other node is one more than the depth of its parent.
+    /// Note that operator trees of untrusted expressions should also be built with a [`ParseConfig`](crate::ParseConfig) that limits the nesting depth,
+    /// as building them recurses as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let context = HashMapContext::<DefaultNumericTypes>::new();
+    /// let tree = build_operator_tree("((1 + 2))").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.eval_with_context_limited(&context, 10), Ok(Value::from_int(3)));
+    /// assert_eq!(
+    ///     tree.eval_with_context_limited(&context, 2),
+    ///     Err(EvalexprError::RecursionLimitExceeded)
+    /// );
+    /// ```
+    pub fn eval_with_context_limited<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+        max_depth: usize,
+    ) -> EvalexprResultValue<NumericTypes> {
+        let Some(max_depth) = max_depth.checked_sub(1) else {
+            return Err(EvalexprError::RecursionLimitExceeded);
+        };
+
+        if let Some(result) = self.eval_lazily(context, Some(max_depth), |child, context| {
+            child.eval_with_context_limited(*context, max_depth)
+        }) {
+            return result;
+        }
+
+        let mut arguments = Vec::new();
+        for child in self.children() {
+            arguments.push(child.eval_with_context_limited(context, max_depth)?);
+        }
+        self.eval_operator(&arguments, context)
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given context, like [`eval_with_context`](Node::eval_with_context).
+    ///
+    /// The arguments of the operators are collected in the given buffer instead of a newly allocated vector per node.
+    /// Reusing the same buffer for repeated evaluations avoids allocating while evaluating, once the buffer has grown large enough.
+    /// The buffer is empty again when this method returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("a * 2 + 1").unwrap(); // Do proper error handling here
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// let mut buffer = EvalBuffer::new();
+    ///
+    /// for a in 0..3 {
+    ///     context.set_value("a".into(), Value::from_int(a)).unwrap(); // Do proper error handling here
+    ///     assert_eq!(
+    ///         tree.eval_with_context_and_buffer(&context, &mut buffer),
+    ///         Ok(Value::from_int(a * 2 + 1))
+    ///     );
+    /// }
+    /// ```
+    pub fn eval_with_context_and_buffer<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+        buffer: &mut EvalBuffer<NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        if let Some(result) = self.eval_lazily(context, None, |child, context| {
+            child.eval_with_context_and_buffer(*context, buffer)
+        }) {
+            return result;
+        }
+
+        let start = buffer.values.len();
+        let result = self
+            .children()
+            .iter()
+            .try_for_each(|child| {
+                let value = child.eval_with_context_and_buffer(context, buffer)?;
+                buffer.values.push(value);
+                Ok(())
+            })
+            .and_then(|()| self.eval_operator(&buffer.values[start..], context));
+        buffer.values.truncate(start);
+        result
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given context, collecting errors of independent components instead of failing on the first one.
+    ///
+    /// If the expression is a tuple, each of its elements is evaluated separately, and the result is a tuple in which the elements that failed are replaced by `Value::Empty`.
+    /// If the expression is a chain, each of its statements is evaluated separately, and the result is the value of the last statement, or `None` if it failed.
+    /// Any other expression is evaluated as a whole, resulting in `None` if it fails.
+    /// The errors are returned in the order of the components that caused them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let context: HashMapContext<DefaultNumericTypes> = context_map! { "a" => int 6 }.unwrap(); // Do proper error handling here
+    /// let tree = build_operator_tree("a / 2, a / 0, a + 1").unwrap(); // Do proper error handling here
+    ///
+    /// let (value, errors) = tree.eval_with_context_catching(&context);
+    /// assert_eq!(
+    ///     value,
+    ///     Some(Value::Tuple(vec![Value::from_int(3), Value::Empty, Value::from_int(7)]))
+    /// );
+    /// assert_eq!(
+    ///     errors,
+    ///     vec![EvalexprError::DivisionError {
+    ///         dividend: Value::from_int(6),
+    ///         divisor: Value::from_int(0),
+    ///     }]
+    /// );
+    /// ```
+    pub fn eval_with_context_catching<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> (
+        Option<Value<NumericTypes>>,
+        Vec<EvalexprError<NumericTypes>>,
+    ) {
+        let mut errors = Vec::new();
+        let value = match (self.operator(), self.children()) {
+            (Operator::RootNode, [child]) => return child.eval_with_context_catching(context),
+            (Operator::Tuple, children) => Some(Value::Tuple(
+                children
+                    .iter()
+                    .map(|child| {
+                        child.eval_with_context(context).unwrap_or_else(|error| {
+                            errors.push(error);
+                            Value::Empty
+                        })
+                    })
+                    .collect(),
+            )),
+            (Operator::Chain, children) => children.iter().fold(None, |_, child| {
+                child
+                    .eval_with_context(context)
+                    .map_err(|error| errors.push(error))
+                    .ok()
+            }),
+            _ => self
+                .eval_with_context(context)
+                .map_err(|error| errors.push(error))
+                .ok(),
+        };
+        (value, errors)
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given context trait object.
+    ///
+    /// This works like [`eval_with_context`](Node::eval_with_context), but allows to evaluate with contexts whose concrete type is not known,
+    /// e.g. because contexts of different types are stored behind trait objects.
+    ///
+    /// Fails, if one of the operators in the expression tree fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let contexts: Vec<Box<dyn Context<NumericTypes = DefaultNumericTypes>>> = vec![
+    ///     Box::new(context_map! { "a" => int 1 }.unwrap()), // Do proper error handling here
+    ///     Box::new(EmptyContextWithBuiltinFunctions::default()),
+    /// ];
+    /// let tree = build_operator_tree("max(a, 2)").unwrap(); // Do proper error handling here
+    ///
+    /// assert_eq!(tree.eval_with_raw_context(contexts[0].as_ref()), Ok(Value::from_int(2)));
+    /// assert!(tree.eval_with_raw_context(contexts[1].as_ref()).is_err());
+    /// ```
+    pub fn eval_with_raw_context(
+        &self,
+        context: &dyn Context<NumericTypes = NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        self.eval_with_context(&DynContext::new(context))
     }
 
     /// Evaluates the operator tree rooted at this node with the given mutable context.
@@ -344,11 +639,120 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         &self,
         context: &mut C,
     ) -> EvalexprResultValue<NumericTypes> {
+        if let Some(result) = self.eval_lazily(&mut *context, None, |child, context| {
+            child.eval_with_context_mut(*context)
+        }) {
+            return result;
+        }
+
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context_mut(context)?);
         }
-        self.operator().eval_mut(&arguments, context)
+        if self.builtin_function.is_some() {
+            self.eval_operator(&arguments, context)
+        } else {
+            self.operator().eval_mut(&arguments, context)
+        }
+    }
+
+    /// Evaluates this node if it does not evaluate all of its children before its operator,
+    /// i.e. if it is a conditional, a null-coalescing operator, an array, a lambda or a call of a builtin function with a lambda.
+    /// Returns `None` for all other nodes.
+    ///
+    /// The children are evaluated with the given closure, which receives the reference to the context, such that it may be mutable.
+    /// If a maximum depth is given, the bodies of called lambdas are evaluated with [`eval_with_context_limited`](Node::eval_with_context_limited).
+    fn eval_lazily<ContextRef>(
+        &self,
+        mut context: ContextRef,
+        max_depth: Option<usize>,
+        mut eval_child: impl FnMut(&Self, &mut ContextRef) -> EvalexprResultValue<NumericTypes>,
+    ) -> Option<EvalexprResultValue<NumericTypes>>
+    where
+        ContextRef: Deref,
+        ContextRef::Target: Context<NumericTypes = NumericTypes> + Sized,
+    {
+        match self.operator() {
+            Operator::Conditional => {
+                Some(self.eval_conditional(|child| eval_child(child, &mut context)))
+            },
+            Operator::Coalesce => Some(self.eval_coalesce(|child| eval_child(child, &mut context))),
+            Operator::Array => {
+                Some(self.eval_array_elements(|child| eval_child(child, &mut context)))
+            },
+            Operator::Lambda { .. } => Some(Err(EvalexprError::UnappliedLambda)),
+            _ => {
+                let (identifier, tuple, lambda) = self.as_lambda_call()?;
+                Some(eval_child(tuple, &mut context).and_then(|tuple| {
+                    lambda.eval_lambda_call(identifier, tuple, &*context, max_depth)
+                }))
+            },
+        }
+    }
+
+    /// Evaluates this conditional node by evaluating its condition and then only the branch selected by it.
+    fn eval_conditional(
+        &self,
+        mut eval_child: impl FnMut(&Self) -> EvalexprResultValue<NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        expect_operator_argument_amount(self.children().len(), 3)?;
+
+        if eval_child(&self.children()[0])?.as_boolean()? {
+            eval_child(&self.children()[1])
+        } else {
+            eval_child(&self.children()[2])
+        }
+    }
+
+    /// Evaluates this null-coalescing node by evaluating its first argument, and only if that is empty or reads a missing variable, its second argument.
+    /// Any other error of the first argument is propagated.
+    fn eval_coalesce(
+        &self,
+        mut eval_child: impl FnMut(&Self) -> EvalexprResultValue<NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        expect_operator_argument_amount(self.children().len(), 2)?;
+
+        match eval_child(&self.children()[0]) {
+            Ok(Value::Empty) | Err(EvalexprError::VariableIdentifierNotFound(_)) => {
+                eval_child(&self.children()[1])
+            },
+            result => result,
+        }
+    }
+
+    /// Evaluates this array node by evaluating each element within its curly braces separately,
+    /// such that a tuple element is not merged into the array.
+    fn eval_array_elements(
+        &self,
+        eval_child: impl FnMut(&Self) -> EvalexprResultValue<NumericTypes>,
+    ) -> EvalexprResultValue<NumericTypes> {
+        expect_operator_argument_amount(self.children().len(), 1)?;
+
+        let elements = match self.children()[0].children() {
+            [tuple] if tuple.operator() == &Operator::Tuple => tuple.children(),
+            elements => elements,
+        };
+        elements
+            .iter()
+            .map(eval_child)
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    }
+
+    /// Evaluates the operator of this node with the given arguments and context.
+    /// Uses the builtin function resolved when building this node, if any.
+    fn eval_operator<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        arguments: &[Value<NumericTypes>],
+        context: &C,
+    ) -> EvalexprResultValue<NumericTypes> {
+        match (self.operator(), &self.builtin_function) {
+            (Operator::FunctionIdentifier { identifier }, Some(builtin_function)) => {
+                expect_operator_argument_amount(arguments.len(), 1)?;
+                call_function(identifier, &arguments[0], context, Some(builtin_function))
+            },
+            (operator, _) => operator.eval(arguments, context),
+        }
     }
 
     /// Evaluates the operator tree rooted at this node.
@@ -444,6 +848,20 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         }
     }
 
+    /// Evaluates the operator tree rooted at this node into an array with an the given context.
+    ///
+    /// Fails, if one of the operators in the expression tree fails.
+    pub fn eval_array_with_context<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> EvalexprResult<ArrayType<NumericTypes>, NumericTypes> {
+        match self.eval_with_context(context) {
+            Ok(Value::Array(array)) => Ok(array),
+            Ok(value) => Err(EvalexprError::expected_array(value)),
+            Err(error) => Err(error),
+        }
+    }
+
     /// Evaluates the operator tree rooted at this node into an empty value with an the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -556,6 +974,22 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         }
     }
 
+    /// Evaluates the operator tree rooted at this node into an array with an the given mutable context.
+    ///
+    /// Fails, if one of the operators in the expression tree fails.
+    pub fn eval_array_with_context_mut<
+        C: ContextWithMutableVariables + Context<NumericTypes = NumericTypes>,
+    >(
+        &self,
+        context: &mut C,
+    ) -> EvalexprResult<ArrayType<NumericTypes>, NumericTypes> {
+        match self.eval_with_context_mut(context) {
+            Ok(Value::Array(array)) => Ok(array),
+            Ok(value) => Err(EvalexprError::expected_array(value)),
+            Err(error) => Err(error),
+        }
+    }
+
     /// Evaluates the operator tree rooted at this node into an empty value with an the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -621,6 +1055,13 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         self.eval_tuple_with_context_mut(&mut HashMapContext::new())
     }
 
+    /// Evaluates the operator tree rooted at this node into an array.
+    ///
+    /// Fails, if one of the operators in the expression tree fails.
+    pub fn eval_array(&self) -> EvalexprResult<ArrayType<NumericTypes>, NumericTypes> {
+        self.eval_array_with_context_mut(&mut HashMapContext::new())
+    }
+
     /// Evaluates the operator tree rooted at this node into an empty value.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -642,6 +1083,7 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
     ///
     /// WARNING: Writing to this might have unexpected results, as some operators require certain amounts and types of arguments.
     pub fn children_mut(&mut self) -> &mut Vec<Node<NumericTypes>> {
+        self.is_constant.reset();
         &mut self.children
     }
 
@@ -649,9 +1091,54 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
     ///
     /// WARNING: Writing to this might have unexpected results, as some operators require different amounts and types of arguments.
     pub fn operator_mut(&mut self) -> &mut Operator<NumericTypes> {
+        self.builtin_function = None;
+        self.is_constant.reset();
         &mut self.operator
     }
 
+    /// Returns true if this node evaluates to the same result independent of the context,
+    /// i.e. if it does not contain variables, assignments or function calls.
+    ///
+    /// Function calls are not considered constant, as functions may depend on state outside of the expression, like `random`.
+    /// Note that evaluating a constant node may still fail, e.g. because of a division by zero.
+    ///
+    /// The result is cached in each node of the tree, so repeated calls do not traverse the tree again.
+    /// The cache of a node is reset when its operator or children are accessed mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let node = build_operator_tree::<DefaultNumericTypes>("(1 + 2) * 3").unwrap(); // Do proper error handling here
+    /// assert!(node.is_constant());
+    /// let node = build_operator_tree::<DefaultNumericTypes>("(1 + a) * 3").unwrap(); // Do proper error handling here
+    /// assert!(!node.is_constant());
+    /// ```
+    pub fn is_constant(&self) -> bool {
+        if let Some(is_constant) = self.is_constant.get() {
+            return is_constant;
+        }
+
+        let is_constant = !matches!(
+            self.operator(),
+            Operator::VariableIdentifierRead { .. }
+                | Operator::VariableIdentifierWrite { .. }
+                | Operator::FunctionIdentifier { .. }
+        ) && self.children().iter().all(Node::is_constant);
+        self.is_constant.set(is_constant);
+        is_constant
+    }
+
+    /// Returns the node within the parentheses if this node is a parenthesized expression, and this node otherwise.
+    /// Note that the parser also wraps the elements of tuples and chains into root nodes.
+    pub(crate) fn without_root_nodes(&self) -> &Self {
+        match (self.operator(), self.children()) {
+            (Operator::RootNode, [child]) => child.without_root_nodes(),
+            _ => self,
+        }
+    }
+
     fn has_enough_children(&self) -> bool {
         Some(self.children().len()) == self.operator().max_argument_amount()
     }
@@ -664,11 +1151,18 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
         }
     }
 
+    /// Inserts the node at the back of the tree according to its precedence.
+    /// If a depth limit is given, fails with an `EvalexprError::RecursionLimitExceeded`
+    /// instead of descending more than that many nodes into the tree.
     fn insert_back_prioritized(
         &mut self,
         node: Node<NumericTypes>,
         is_root_node: bool,
+        depth_limit: Option<usize>,
     ) -> EvalexprResult<(), NumericTypes> {
+        if depth_limit == Some(0) {
+            return Err(EvalexprError::RecursionLimitExceeded);
+        }
         // println!(
         //     "Inserting {:?} into {:?}, is_root_node = {is_root_node}",
         //     node.operator(),
@@ -696,10 +1190,11 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
                     //     self.children.last().unwrap().operator()
                     // );
                     // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
-                    self.children
-                        .last_mut()
-                        .unwrap()
-                        .insert_back_prioritized(node, false)
+                    self.children.last_mut().unwrap().insert_back_prioritized(
+                        node,
+                        false,
+                        depth_limit.map(|limit| limit - 1),
+                    )
                 } else {
                     // println!("Rotating");
                     if node.operator().is_leaf() {
@@ -711,14 +1206,14 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
                     // Root nodes have at most one child
                     // TODO I am not sure if this is the correct error
                     if self.operator() == &Operator::RootNode && !self.children().is_empty() {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
                     }
                     // Do not insert root nodes into root nodes.
                     // TODO I am not sure if this is the correct error
                     if self.operator() == &Operator::RootNode
                         && node.operator() == &Operator::RootNode
                     {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
                     }
                     self.children.push(node);
                     let node = self.children.last_mut().unwrap();
@@ -726,14 +1221,14 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
                     // Root nodes have at most one child
                     // TODO I am not sure if this is the correct error
                     if node.operator() == &Operator::RootNode && !node.children().is_empty() {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
                     }
                     // Do not insert root nodes into root nodes.
                     // TODO I am not sure if this is the correct error
                     if node.operator() == &Operator::RootNode
                         && last_child.operator() == &Operator::RootNode
                     {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
                     }
                     node.children.push(last_child);
                     Ok(())
@@ -749,6 +1244,44 @@ impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
     }
 }
 
+impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
+    /// Returns how deeply the operators of this tree are nested, e.g. `-(1 + 2)` has an operator depth of two.
+    /// Root nodes and leaves like constants and variables do not count.
+    ///
+    /// The tree is traversed without recursion, such that arbitrarily deep trees can be measured.
+    fn operator_depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            let depth = if node.operator() == &Operator::RootNode || node.children.is_empty() {
+                depth
+            } else {
+                depth + 1
+            };
+            max_depth = max_depth.max(depth);
+            stack.extend(node.children.iter().map(|child| (child, depth)));
+        }
+        max_depth
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Drop for Node<NumericTypes> {
+    /// Drops the children without recursion, such that dropping arbitrarily deep trees does not overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> PartialEq for Node<NumericTypes> {
+    fn eq(&self, other: &Self) -> bool {
+        // The resolved builtin function only depends on the operator, so it does not need to be compared.
+        self.operator == other.operator && self.children == other.children
+    }
+}
+
 fn collapse_root_stack_to<NumericTypes: EvalexprNumericTypes>(
     root_stack: &mut Vec<Node<NumericTypes>>,
     mut root: Node<NumericTypes>,
@@ -767,7 +1300,7 @@ fn collapse_root_stack_to<NumericTypes: EvalexprNumericTypes>(
             }
         } else {
             // This is the only way the topmost root node could have been removed
-            return Err(EvalexprError::UnmatchedRBrace);
+            return Err(EvalexprError::UnmatchedRBrace { span: None });
         }
     }
 
@@ -782,7 +1315,7 @@ fn collapse_all_sequences<NumericTypes: EvalexprNumericTypes>(
     let mut root = if let Some(root) = root_stack.pop() {
         root
     } else {
-        return Err(EvalexprError::UnmatchedRBrace);
+        return Err(EvalexprError::UnmatchedRBrace { span: None });
     };
 
     loop {
@@ -790,7 +1323,7 @@ fn collapse_all_sequences<NumericTypes: EvalexprNumericTypes>(
         if root.operator() == &Operator::RootNode {
             // This should fire if parsing something like `4(5)`
             if root.has_too_many_children() {
-                return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
             }
 
             root_stack.push(root);
@@ -804,7 +1337,7 @@ fn collapse_all_sequences<NumericTypes: EvalexprNumericTypes>(
             } else {
                 // This should fire if parsing something like `4(5)`
                 if root.has_too_many_children() {
-                    return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                    return Err(EvalexprError::MissingOperatorOutsideOfBrace { span: None });
                 }
 
                 root_stack.push(potential_higher_root);
@@ -813,7 +1346,7 @@ fn collapse_all_sequences<NumericTypes: EvalexprNumericTypes>(
             }
         } else {
             // This is the only way the topmost root node could have been removed
-            return Err(EvalexprError::UnmatchedRBrace);
+            return Err(EvalexprError::UnmatchedRBrace { span: None });
         }
     }
 
@@ -821,15 +1354,69 @@ fn collapse_all_sequences<NumericTypes: EvalexprNumericTypes>(
     Ok(())
 }
 
+/// Returns the innermost open group if its enclosed expression is the topmost root on the root stack.
+fn open_group_at_top<'groups, NumericTypes: EvalexprNumericTypes>(
+    open_groups: &'groups [OpenGroup<NumericTypes>],
+    root_stack: &[Node<NumericTypes>],
+) -> Option<&'groups OpenGroup<NumericTypes>> {
+    open_groups
+        .last()
+        .filter(|(_, depth, _)| *depth == root_stack.len())
+}
+
+/// Closes the innermost open group if it was opened by the given token and its enclosed expression is the topmost root on the root stack.
+/// Otherwise, returns `EvalexprError::UnmatchedQuestionMark` if the innermost group is an unfinished conditional, or the given error.
+fn close_group<NumericTypes: EvalexprNumericTypes>(
+    open_groups: &mut Vec<OpenGroup<NumericTypes>>,
+    root_stack: &[Node<NumericTypes>],
+    opening: Token<NumericTypes>,
+    unmatched: EvalexprError<NumericTypes>,
+) -> EvalexprResult<(), NumericTypes> {
+    match open_group_at_top(open_groups, root_stack) {
+        Some((token, _, _)) if *token == opening => {
+            open_groups.pop();
+            Ok(())
+        },
+        Some((Token::QuestionMark, _, span)) => Err(EvalexprError::UnmatchedQuestionMark {
+            span: Some(span.clone()),
+        }),
+        _ => Err(unmatched),
+    }
+}
+
+/// The opening token of an unfinished conditional, array or index,
+/// together with the length of the root stack at which its enclosed expression is parsed and the span of the opening token.
+type OpenGroup<NumericTypes> = (Token<NumericTypes>, usize, Range<usize>);
+
+/// Builds the operator tree from the given tokens.
+/// If a maximum depth is given, trees whose operators are nested deeper are rejected with an `EvalexprError::RecursionLimitExceeded`.
 pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
-    tokens: Vec<Token<NumericTypes>>,
+    tokens: Vec<SpannedToken<NumericTypes>>,
+    max_depth: Option<usize>,
+) -> EvalexprResult<Node<NumericTypes>, NumericTypes> {
+    // Errors that do not know their position themselves get the span of the token that was parsed when they occurred.
+    let mut current_span = None;
+    spanned_tokens_to_operator_tree(tokens, max_depth, &mut current_span)
+        .map_err(|error| error.with_span(current_span))
+}
+
+fn spanned_tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
+    tokens: Vec<SpannedToken<NumericTypes>>,
+    max_depth: Option<usize>,
+    current_span: &mut Option<Range<usize>>,
 ) -> EvalexprResult<Node<NumericTypes>, NumericTypes> {
+    // The root node itself is not an operator, so inserting may descend one node deeper than the maximum depth.
+    let depth_limit = max_depth.map(|max_depth| max_depth.saturating_add(1));
     let mut root_stack = vec![Node::root_node()];
+    let mut open_groups: Vec<OpenGroup<NumericTypes>> = Vec::new();
+    // The spans of the unclosed opening parentheses.
+    let mut open_braces: Vec<Range<usize>> = Vec::new();
     let mut last_token_is_rightsided_value = false;
-    let mut token_iter = tokens.iter().peekable();
+    let mut token_iter = tokens.into_iter().peekable();
 
-    while let Some(token) = token_iter.next().cloned() {
-        let next = token_iter.peek().cloned();
+    while let Some((token, span)) = token_iter.next() {
+        let next = token_iter.peek().map(|(token, _)| token.clone());
+        *current_span = Some(span.clone());
 
         let node = match token.clone() {
             Token::Plus => Some(Node::new(Operator::Add)),
@@ -842,6 +1429,7 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
             },
             Token::Star => Some(Node::new(Operator::Mul)),
             Token::Slash => Some(Node::new(Operator::Div)),
+            Token::DoubleSlash => Some(Node::new(Operator::IntDiv)),
             Token::Percent => Some(Node::new(Operator::Mod)),
             Token::Hat => Some(Node::new(Operator::Exp)),
 
@@ -855,19 +1443,82 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
             Token::Or => Some(Node::new(Operator::Or)),
             Token::Not => Some(Node::new(Operator::Not)),
 
+            Token::BitAnd => Some(Node::new(Operator::BitAnd)),
+            Token::BitOr => Some(Node::new(Operator::BitOr)),
+            Token::Shl => Some(Node::new(Operator::Shl)),
+            Token::Shr => Some(Node::new(Operator::Shr)),
+
+            Token::QuestionMark => Some(Node::new(Operator::Conditional)),
+            Token::Coalesce => Some(Node::new(Operator::Coalesce)),
+            Token::Colon => {
+                collapse_all_sequences(&mut root_stack)?;
+                close_group(
+                    &mut open_groups,
+                    &root_stack,
+                    Token::QuestionMark,
+                    EvalexprError::UnmatchedColon { span: None },
+                )?;
+                // The first branch is complete and inserted into the conditional like a parenthesized expression.
+                root_stack.pop()
+            },
+
             Token::LBrace => {
                 root_stack.push(Node::root_node());
+                open_braces.push(span.clone());
                 None
             },
             Token::RBrace => {
                 if root_stack.len() <= 1 {
-                    return Err(EvalexprError::UnmatchedRBrace);
+                    return Err(EvalexprError::UnmatchedRBrace { span: None });
                 } else {
                     collapse_all_sequences(&mut root_stack)?;
+                    match open_group_at_top(&open_groups, &root_stack) {
+                        Some((Token::QuestionMark, _, span)) => {
+                            return Err(EvalexprError::UnmatchedQuestionMark {
+                                span: Some(span.clone()),
+                            })
+                        },
+                        Some(_) => return Err(EvalexprError::UnmatchedRBrace { span: None }),
+                        None => {},
+                    }
+                    open_braces.pop();
                     root_stack.pop()
                 }
             },
 
+            Token::LCurlyBrace => {
+                root_stack.push(Node::root_node());
+                open_groups.push((Token::LCurlyBrace, root_stack.len(), span.clone()));
+                None
+            },
+            Token::RCurlyBrace => {
+                collapse_all_sequences(&mut root_stack)?;
+                close_group(
+                    &mut open_groups,
+                    &root_stack,
+                    Token::LCurlyBrace,
+                    EvalexprError::UnmatchedRCurlyBrace { span: None },
+                )?;
+                root_stack.pop().map(|content| {
+                    let mut array = Node::new(Operator::Array);
+                    array.children.push(content);
+                    array
+                })
+            },
+
+            Token::LBracket => Some(Node::new(Operator::Index)),
+            Token::RBracket => {
+                collapse_all_sequences(&mut root_stack)?;
+                close_group(
+                    &mut open_groups,
+                    &root_stack,
+                    Token::LBracket,
+                    EvalexprError::UnmatchedRBracket { span: None },
+                )?;
+                // The index is complete and inserted into the index operator like a parenthesized expression.
+                root_stack.pop()
+            },
+
             Token::Assign => Some(Node::new(Operator::Assign)),
             Token::PlusAssign => Some(Node::new(Operator::AddAssign)),
             Token::MinusAssign => Some(Node::new(Operator::SubAssign)),
@@ -880,6 +1531,7 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
 
             Token::Comma => Some(Node::new(Operator::Tuple)),
             Token::Semicolon => Some(Node::new(Operator::Chain)),
+            Token::Arrow => Some(Node::new(Operator::Lambda { params: Vec::new() })),
 
             Token::Identifier(identifier) => {
                 let mut result = Some(Node::new(Operator::variable_identifier_read(
@@ -899,6 +1551,7 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
             Token::Float(float) => Some(Node::new(Operator::value(Value::Float(float)))),
             Token::Int(int) => Some(Node::new(Operator::value(Value::Int(int)))),
             Token::Boolean(boolean) => Some(Node::new(Operator::value(Value::Boolean(boolean)))),
+            Token::Null => Some(Node::new(Operator::value(Value::Empty))),
             Token::String(string) => Some(Node::new(Operator::value(Value::String(string)))),
         };
 
@@ -943,7 +1596,7 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
                 // println!("Stack after sequence operation: {:?}", root_stack);
                 } else if root.operator().is_sequence() {
                     if let Some(mut last_root_child) = root.children.pop() {
-                        last_root_child.insert_back_prioritized(node, true)?;
+                        last_root_child.insert_back_prioritized(node, true, depth_limit)?;
                         root.children.push(last_root_child);
                         root_stack.push(root);
                     } else {
@@ -951,25 +1604,194 @@ pub(crate) fn tokens_to_operator_tree<NumericTypes: EvalexprNumericTypes>(
                         unreachable!()
                     }
                 } else {
-                    root.insert_back_prioritized(node, true)?;
+                    root.insert_back_prioritized(node, true, depth_limit)?;
                     root_stack.push(root);
                 }
             } else {
-                return Err(EvalexprError::UnmatchedRBrace);
+                return Err(EvalexprError::UnmatchedRBrace { span: None });
             }
         }
 
+        if matches!(token, Token::QuestionMark | Token::LBracket) {
+            // The first branch of a conditional and the index of an index operator are parsed like a parenthesized expression,
+            // which is terminated by the colon or the closing bracket.
+            root_stack.push(Node::root_node());
+            open_groups.push((token.clone(), root_stack.len(), span));
+        }
+
         last_token_is_rightsided_value = token.is_rightsided_value();
     }
 
+    *current_span = None;
+    match open_groups.pop() {
+        Some((Token::QuestionMark, _, span)) => {
+            return Err(EvalexprError::UnmatchedQuestionMark { span: Some(span) })
+        },
+        Some((Token::LCurlyBrace, _, span)) => {
+            return Err(EvalexprError::UnmatchedLCurlyBrace { span: Some(span) })
+        },
+        Some((_, _, span)) => return Err(EvalexprError::UnmatchedLBracket { span: Some(span) }),
+        None => {},
+    }
+
     // In the end, all sequences are implicitly terminated
     collapse_all_sequences(&mut root_stack)?;
 
     if root_stack.len() > 1 {
-        Err(EvalexprError::UnmatchedLBrace)
-    } else if let Some(root) = root_stack.pop() {
+        Err(EvalexprError::UnmatchedLBrace {
+            span: open_braces.pop(),
+        })
+    } else if let Some(mut root) = root_stack.pop() {
+        if let Some(max_depth) = max_depth {
+            if root.operator_depth() > max_depth {
+                return Err(EvalexprError::RecursionLimitExceeded);
+            }
+        }
+        root.resolve_lambda_parameters()?;
         Ok(root)
     } else {
-        Err(EvalexprError::UnmatchedRBrace)
+        Err(EvalexprError::UnmatchedRBrace { span: None })
+    }
+}
+
+/// Wraps a context trait object such that it can be used where a sized context is required.
+///
+/// It may bind the parameters of a lambda to its arguments, which shadow the variables of the wrapped context.
+struct DynContext<'context, NumericTypes: EvalexprNumericTypes> {
+    context: &'context dyn Context<NumericTypes = NumericTypes>,
+    params: &'context [String],
+    arguments: Vec<Value<NumericTypes>>,
+}
+
+impl<'context, NumericTypes: EvalexprNumericTypes> DynContext<'context, NumericTypes> {
+    fn new(context: &'context dyn Context<NumericTypes = NumericTypes>) -> Self {
+        Self::with_arguments(context, &[], Vec::new())
+    }
+
+    fn with_arguments(
+        context: &'context dyn Context<NumericTypes = NumericTypes>,
+        params: &'context [String],
+        arguments: Vec<Value<NumericTypes>>,
+    ) -> Self {
+        Self {
+            context,
+            params,
+            arguments,
+        }
+    }
+
+    fn get_argument(&self, identifier: &str) -> Option<&Value<NumericTypes>> {
+        self.params
+            .iter()
+            .position(|param| param == identifier)
+            .map(|index| &self.arguments[index])
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Context for DynContext<'_, NumericTypes> {
+    type NumericTypes = NumericTypes;
+
+    fn get_value(&self, identifier: &str) -> Option<&Value<Self::NumericTypes>> {
+        self.get_argument(identifier)
+            .or_else(|| self.context.get_value(identifier))
+    }
+
+    fn get_value_cow(&self, identifier: &str) -> Option<Cow<'_, Value<Self::NumericTypes>>> {
+        match self.get_argument(identifier) {
+            Some(argument) => Some(Cow::Borrowed(argument)),
+            None => self.context.get_value_cow(identifier),
+        }
+    }
+
+    fn get_function(&self, identifier: &str) -> Option<&Function<Self::NumericTypes>> {
+        self.context.get_function(identifier)
+    }
+
+    fn call_function(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.call_function_with_context(identifier, argument, self)
+    }
+
+    fn call_function_with_context(
+        &self,
+        identifier: &str,
+        argument: &Value<Self::NumericTypes>,
+        context: &dyn Context<NumericTypes = Self::NumericTypes>,
+    ) -> EvalexprResultValue<Self::NumericTypes> {
+        self.context
+            .call_function_with_context(identifier, argument, context)
+    }
+
+    fn known_identifiers(&self) -> Vec<String> {
+        let mut known_identifiers = self.params.to_vec();
+        known_identifiers.extend(self.context.known_identifiers());
+        known_identifiers
+    }
+
+    fn known_function_identifiers(&self) -> Vec<String> {
+        self.context.known_function_identifiers()
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.context.are_builtin_functions_disabled()
+    }
+
+    fn overflow_mode(&self) -> OverflowMode {
+        self.context.overflow_mode()
+    }
+
+    fn set_builtin_functions_disabled(
+        &mut self,
+        _disabled: bool,
+    ) -> EvalexprResult<(), Self::NumericTypes> {
+        Err(EvalexprError::ContextNotMutable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{build_operator_tree, DefaultNumericTypes, Operator, Value};
+
+    #[test]
+    fn test_is_constant_is_cached() {
+        let mut node = build_operator_tree::<DefaultNumericTypes>("1 + 2 * 3").unwrap();
+        assert_eq!(node.is_constant.get(), None);
+        assert!(node.is_constant());
+        assert!(node.iter().all(|node| node.is_constant.get() == Some(true)));
+
+        // Falsify a cached flag deep in the tree to show that it is not traversed again.
+        node.children[0].children[1].is_constant.set(false);
+        assert!(node.is_constant());
+        node.children[0].is_constant.reset();
+        assert!(!node.children[0].is_constant());
+        assert!(node.is_constant());
+    }
+
+    #[test]
+    fn test_is_constant_cache_is_reset_on_mutation() {
+        let mut node = build_operator_tree::<DefaultNumericTypes>("1 + 2 * 3").unwrap();
+        assert!(node.is_constant());
+        *node.children_mut()[0].children_mut()[1].children_mut()[0].operator_mut() =
+            Operator::VariableIdentifierRead {
+                identifier: "a".into(),
+            };
+        assert!(!node.is_constant());
+
+        let mut node = build_operator_tree::<DefaultNumericTypes>("1 + a").unwrap();
+        assert!(!node.is_constant());
+        for operator in node.iter_operators_mut() {
+            if let Operator::VariableIdentifierRead { .. } = operator {
+                *operator = Operator::value(Value::from_int(2));
+            }
+        }
+        assert!(node.is_constant());
+        assert_eq!(node.eval(), Ok(Value::from_int(3)));
+
+        let node = build_operator_tree::<DefaultNumericTypes>("f(1) + 2").unwrap();
+        assert!(!node.is_constant());
+        assert!(node.children[0].children[1].is_constant());
     }
 }
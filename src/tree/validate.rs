@@ -0,0 +1,102 @@
+use crate::{
+    error::{EvalexprError, EvalexprResult},
+    function::builtin::{BUILTIN_FUNCTION_IDENTIFIERS, BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS},
+    operator::Operator,
+    value::{numeric_types::EvalexprNumericTypes, Value},
+    Context, Node,
+};
+
+impl<NumericTypes: EvalexprNumericTypes> Node<NumericTypes> {
+    /// Checks the function calls of the operator tree rooted at this node against the given context, without evaluating it.
+    ///
+    /// Each called function must either be known to the context or be a builtin function, if builtin functions are not disabled.
+    /// A function is known to the context if it is returned by [`Context::get_function`] or listed by [`Context::known_function_identifiers`].
+    /// If the function advertises the amounts of arguments it accepts via [`Function::with_argument_amount`](crate::Function::with_argument_amount),
+    /// calls whose amount of arguments is known without evaluating them are checked against it.
+    /// This is the case if the argument is written out as a tuple like in `f(1, 2)`, is empty like in `f()`, or is a constant.
+    ///
+    /// Returns the error of the first invalid function call.
+    /// Unknown functions result in an `EvalexprError::FunctionIdentifierNotFound`,
+    /// and wrong amounts of arguments in an `EvalexprError::WrongFunctionArgumentAmount`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    /// context.set_function("add".into(), Function::new(|argument| {
+    ///     let arguments = argument.as_fixed_len_tuple(2)?;
+    ///     Ok(Value::from_int(arguments[0].as_int()? + arguments[1].as_int()?))
+    /// }).with_argument_amount(2..=2)).unwrap(); // Do proper error handling here
+    ///
+    /// let node = build_operator_tree("add(1, max(2, 3))").unwrap(); // Do proper error handling here
+    /// assert_eq!(node.validate_against_context(&context), Ok(()));
+    /// let node = build_operator_tree("add(1, 2, 3)").unwrap(); // Do proper error handling here
+    /// assert_eq!(
+    ///     node.validate_against_context(&context),
+    ///     Err(EvalexprError::wrong_function_argument_amount(3, 2))
+    /// );
+    /// ```
+    pub fn validate_against_context<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        context: &C,
+    ) -> EvalexprResult<(), NumericTypes> {
+        for node in self.iter() {
+            if let Operator::FunctionIdentifier { identifier } = node.operator() {
+                node.validate_function_call(identifier, context)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_function_call<C: Context<NumericTypes = NumericTypes>>(
+        &self,
+        identifier: &str,
+        context: &C,
+    ) -> EvalexprResult<(), NumericTypes> {
+        if let Some(function) = context.get_function(identifier) {
+            if let (Some(expected), Some(actual)) = (
+                function.argument_amount(),
+                self.children().first().and_then(Node::argument_amount),
+            ) {
+                if !expected.contains(&actual) {
+                    return Err(EvalexprError::wrong_function_argument_amount_range(
+                        actual,
+                        expected.clone(),
+                    ));
+                }
+            }
+            Ok(())
+        } else if context
+            .known_function_identifiers()
+            .iter()
+            .any(|known_identifier| known_identifier == identifier)
+            || (!context.are_builtin_functions_disabled()
+                && (BUILTIN_FUNCTION_IDENTIFIERS.contains(&identifier)
+                    || BUILTIN_FUNCTION_WITH_CONTEXT_IDENTIFIERS.contains(&identifier)))
+        {
+            Ok(())
+        } else {
+            Err(EvalexprError::FunctionIdentifierNotFound(
+                identifier.to_string(),
+            ))
+        }
+    }
+
+    /// Returns the amount of arguments this node passes to a function if it is the argument of a function call,
+    /// or `None` if it is not known without evaluating the node.
+    fn argument_amount(&self) -> Option<usize> {
+        match (self.operator(), self.children()) {
+            (Operator::RootNode, []) => Some(0),
+            (Operator::RootNode, [child]) => child.argument_amount(),
+            (Operator::Tuple, children) => Some(children.len()),
+            (Operator::Const { value }, _) => Some(match value {
+                Value::Tuple(tuple) => tuple.len(),
+                Value::Empty => 0,
+                _ => 1,
+            }),
+            _ => None,
+        }
+    }
+}
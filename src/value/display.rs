@@ -13,18 +13,32 @@ impl<NumericTypes: EvalexprNumericTypes> Display for Value<NumericTypes> {
             Value::Boolean(boolean) => write!(f, "{}", boolean),
             Value::Tuple(tuple) => {
                 write!(f, "(")?;
-                let mut once = false;
-                for value in tuple {
-                    if once {
-                        write!(f, ", ")?;
-                    } else {
-                        once = true;
-                    }
-                    value.fmt(f)?;
-                }
+                fmt_elements(tuple, f)?;
                 write!(f, ")")
             },
+            Value::Array(array) => {
+                write!(f, "{{")?;
+                fmt_elements(array, f)?;
+                write!(f, "}}")
+            },
             Value::Empty => write!(f, "()"),
         }
     }
 }
+
+/// Writes the given values separated by commas.
+fn fmt_elements<NumericTypes: EvalexprNumericTypes>(
+    values: &[Value<NumericTypes>],
+    f: &mut Formatter,
+) -> Result<(), Error> {
+    let mut once = false;
+    for value in values {
+        if once {
+            write!(f, ", ")?;
+        } else {
+            once = true;
+        }
+        value.fmt(f)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::Value;
+
+use super::numeric_types::EvalexprNumericTypes;
+
+/// Options that change how values are formatted into strings by [`Value::format_with`].
+///
+/// The default formatter formats values like [`Value::str_from`], i.e. like the `str::from` builtin function.
+///
+/// # Example
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let formatter = ValueFormatter::new()
+///     .with_float_decimals(Some(2))
+///     .with_thousands_separator(Some(','));
+/// let value: Value = Value::from_float(1234567.891);
+/// assert_eq!(value.format_with(&formatter), "1,234,567.89");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueFormatter {
+    float_decimals: Option<usize>,
+    thousands_separator: Option<char>,
+    tuple_delimiters: (String, String),
+    element_separator: String,
+}
+
+impl ValueFormatter {
+    /// Constructs the default formatter.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the amount of fractional digits floats are rounded to, or `None` to format floats with as many digits as needed to represent them exactly.
+    /// Defaults to `None`.
+    pub fn with_float_decimals(mut self, float_decimals: Option<usize>) -> Self {
+        self.float_decimals = float_decimals;
+        self
+    }
+
+    /// Returns the amount of fractional digits floats are rounded to, if any.
+    pub fn float_decimals(&self) -> Option<usize> {
+        self.float_decimals
+    }
+
+    /// Sets the character that separates groups of three digits in the integer part of ints and floats, like `,` in `1,000,000`.
+    /// Defaults to `None`, i.e. digits are not grouped.
+    pub fn with_thousands_separator(mut self, thousands_separator: Option<char>) -> Self {
+        self.thousands_separator = thousands_separator;
+        self
+    }
+
+    /// Returns the character that separates groups of three digits, if any.
+    pub fn thousands_separator(&self) -> Option<char> {
+        self.thousands_separator
+    }
+
+    /// Sets the strings that are written before and after the elements of a tuple.
+    /// Defaults to `(` and `)`.
+    pub fn with_tuple_delimiters(
+        mut self,
+        open: impl Into<String>,
+        close: impl Into<String>,
+    ) -> Self {
+        self.tuple_delimiters = (open.into(), close.into());
+        self
+    }
+
+    /// Returns the strings that are written before and after the elements of a tuple.
+    pub fn tuple_delimiters(&self) -> (&str, &str) {
+        (&self.tuple_delimiters.0, &self.tuple_delimiters.1)
+    }
+
+    /// Sets the string that is written between the elements of tuples and arrays.
+    /// Defaults to `, `.
+    pub fn with_element_separator(mut self, element_separator: impl Into<String>) -> Self {
+        self.element_separator = element_separator.into();
+        self
+    }
+
+    /// Returns the string that is written between the elements of tuples and arrays.
+    pub fn element_separator(&self) -> &str {
+        &self.element_separator
+    }
+
+    /// Inserts the thousands separator, if any, into the leading digits of the given formatted number.
+    fn group_digits(&self, number: String) -> String {
+        let Some(separator) = self.thousands_separator else {
+            return number;
+        };
+
+        let (sign, unsigned) = number.split_at(usize::from(number.starts_with('-')));
+        let digits_len = unsigned
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(unsigned.len());
+        let (digits, rest) = unsigned.split_at(digits_len);
+
+        let mut result = String::from(sign);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits_len - index) % 3 == 0 {
+                result.push(separator);
+            }
+            result.push(digit);
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+impl Default for ValueFormatter {
+    fn default() -> Self {
+        Self {
+            float_decimals: None,
+            thousands_separator: None,
+            tuple_delimiters: ("(".to_string(), ")".to_string()),
+            element_separator: ", ".to_string(),
+        }
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes> Value<NumericTypes> {
+    /// Formats this value into a string according to the given formatter.
+    ///
+    /// Like [`str_from`](Value::str_from), a string is formatted without quotes, unless it is an element of a tuple or an array.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let value: Value = Value::Tuple(vec![Value::from_int(1500), Value::from_float(0.125)]);
+    /// assert_eq!(value.format_with(&ValueFormatter::new()), "(1500, 0.125)");
+    ///
+    /// let formatter = ValueFormatter::new()
+    ///     .with_float_decimals(Some(1))
+    ///     .with_thousands_separator(Some('\''))
+    ///     .with_tuple_delimiters("[", "]")
+    ///     .with_element_separator("; ");
+    /// assert_eq!(value.format_with(&formatter), "[1'500; 0.1]");
+    /// ```
+    pub fn format_with(&self, formatter: &ValueFormatter) -> String {
+        match self {
+            Value::String(string) => string.clone(),
+            _ => self.format_element_with(formatter),
+        }
+    }
+
+    fn format_element_with(&self, formatter: &ValueFormatter) -> String {
+        match self {
+            Value::Float(float) => formatter.group_digits(match formatter.float_decimals {
+                Some(decimals) => format!("{:.*}", decimals, float),
+                None => float.to_string(),
+            }),
+            Value::Int(int) => formatter.group_digits(int.to_string()),
+            Value::Tuple(elements) => {
+                let (open, close) = formatter.tuple_delimiters();
+                format!(
+                    "{}{}{}",
+                    open,
+                    format_elements_with(elements, formatter),
+                    close
+                )
+            },
+            Value::Array(elements) => {
+                format!("{{{}}}", format_elements_with(elements, formatter))
+            },
+            value => value.to_string(),
+        }
+    }
+}
+
+/// Formats the given values separated by the element separator of the formatter.
+fn format_elements_with<NumericTypes: EvalexprNumericTypes>(
+    values: &[Value<NumericTypes>],
+    formatter: &ValueFormatter,
+) -> String {
+    values
+        .iter()
+        .map(|value| value.format_element_with(formatter))
+        .collect::<Vec<_>>()
+        .join(formatter.element_separator())
+}
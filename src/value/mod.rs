@@ -4,12 +4,18 @@ use std::{convert::TryFrom, ops::RangeInclusive};
 use self::numeric_types::{default_numeric_types::DefaultNumericTypes, EvalexprNumericTypes};
 
 mod display;
+mod formatter;
 pub mod numeric_types;
 pub mod value_type;
 
+pub use formatter::ValueFormatter;
+
 /// The type used to represent tuples in `Value::Tuple`.
 pub type TupleType<NumericTypes = DefaultNumericTypes> = Vec<Value<NumericTypes>>;
 
+/// The type used to represent arrays in `Value::Array`.
+pub type ArrayType<NumericTypes = DefaultNumericTypes> = Vec<Value<NumericTypes>>;
+
 /// The type used to represent empty values in `Value::Empty`.
 pub type EmptyType = ();
 
@@ -20,6 +26,13 @@ pub const EMPTY_VALUE: () = ();
 /// Values can be of different subtypes that are the variants of this enum.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "NumericTypes::Int: serde::Serialize, NumericTypes::Float: serde::Serialize",
+        deserialize = "NumericTypes::Int: serde::Deserialize<'de>, NumericTypes::Float: serde::Deserialize<'de>"
+    ))
+)]
 pub enum Value<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     /// A string value.
     String(String),
@@ -31,6 +44,10 @@ pub enum Value<NumericTypes: EvalexprNumericTypes = DefaultNumericTypes> {
     Boolean(bool),
     /// A tuple value.
     Tuple(TupleType<NumericTypes>),
+    /// An array value.
+    ///
+    /// Unlike a tuple, an array is never used as the argument list of a function, but always passed as a single argument.
+    Array(ArrayType<NumericTypes>),
     /// An empty value.
     Empty,
 }
@@ -65,6 +82,11 @@ impl<NumericTypes: EvalexprNumericTypes> Value<NumericTypes> {
         matches!(self, Value::Tuple(_))
     }
 
+    /// Returns true if `self` is a `Value::Array`.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
     /// Returns true if `self` is a `Value::Empty`.
     pub fn is_empty(&self) -> bool {
         matches!(self, Value::Empty)
@@ -157,6 +179,14 @@ impl<NumericTypes: EvalexprNumericTypes> Value<NumericTypes> {
         }
     }
 
+    /// Clones the value stored in `self` as `ArrayType`, or returns `Err` if `self` is not a `Value::Array`.
+    pub fn as_array(&self) -> EvalexprResult<ArrayType<NumericTypes>, NumericTypes> {
+        match self {
+            Value::Array(array) => Ok(array.clone()),
+            value => Err(EvalexprError::expected_array(value.clone())),
+        }
+    }
+
     /// Returns `()`, or returns`Err` if `self` is not a `Value::Tuple`.
     pub fn as_empty(&self) -> EvalexprResult<(), NumericTypes> {
         match self {
@@ -172,7 +202,7 @@ impl<NumericTypes: EvalexprNumericTypes> Value<NumericTypes> {
             Value::Float(v) => v.to_string(),
             Value::Int(v) => v.to_string(),
             Value::Boolean(v) => v.to_string(),
-            Value::Tuple(_) => self.to_string(),
+            Value::Tuple(_) | Value::Array(_) => self.to_string(),
             Value::Empty => String::from("()"),
         }
     }
@@ -206,9 +236,19 @@ impl<NumericTypes: EvalexprNumericTypes> From<bool> for Value<NumericTypes> {
     }
 }
 
-impl<NumericTypes: EvalexprNumericTypes> From<TupleType<NumericTypes>> for Value<NumericTypes> {
-    fn from(tuple: TupleType<NumericTypes>) -> Self {
-        Value::Tuple(tuple)
+impl<NumericTypes: EvalexprNumericTypes, T: Into<Value<NumericTypes>>> From<Vec<T>>
+    for Value<NumericTypes>
+{
+    fn from(tuple: Vec<T>) -> Self {
+        Value::Tuple(tuple.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<NumericTypes: EvalexprNumericTypes, T: Into<Value<NumericTypes>>, const N: usize> From<[T; N]>
+    for Value<NumericTypes>
+{
+    fn from(tuple: [T; N]) -> Self {
+        Value::Tuple(tuple.into_iter().map(Into::into).collect())
     }
 }
 
@@ -277,7 +317,7 @@ impl<NumericTypes: EvalexprNumericTypes> TryFrom<Value<NumericTypes>> for () {
 #[cfg(test)]
 mod tests {
     use crate::value::{
-        numeric_types::default_numeric_types::DefaultNumericTypes, TupleType, Value,
+        numeric_types::default_numeric_types::DefaultNumericTypes, ArrayType, TupleType, Value,
     };
 
     #[test]
@@ -299,6 +339,44 @@ mod tests {
             Value::<DefaultNumericTypes>::from(TupleType::new()).as_tuple(),
             Ok(TupleType::new())
         );
+        assert_eq!(
+            Value::<DefaultNumericTypes>::Array(vec![Value::from_int(1)]).as_array(),
+            Ok(vec![Value::from_int(1)])
+        );
+    }
+
+    #[test]
+    fn test_tuple_conversions() {
+        assert_eq!(
+            Value::<DefaultNumericTypes>::from(vec![1, 2, 3]),
+            Value::Tuple(vec![
+                Value::from_int(1),
+                Value::from_int(2),
+                Value::from_int(3)
+            ])
+        );
+        assert_eq!(
+            Value::<DefaultNumericTypes>::from([1.5, 2.5]),
+            Value::Tuple(vec![Value::from_float(1.5), Value::from_float(2.5)])
+        );
+        assert_eq!(
+            Value::<DefaultNumericTypes>::from(vec![
+                Value::from("a"),
+                Value::from_int(1),
+                Value::from(true),
+                Value::from(vec!["b", "c"]),
+            ]),
+            Value::Tuple(vec![
+                Value::String("a".into()),
+                Value::Int(1),
+                Value::Boolean(true),
+                Value::Tuple(vec![Value::String("b".into()), Value::String("c".into())]),
+            ])
+        );
+        assert_eq!(
+            Value::<DefaultNumericTypes>::from(Vec::<Value<DefaultNumericTypes>>::new()),
+            Value::Tuple(TupleType::new())
+        );
     }
 
     #[test]
@@ -308,6 +386,8 @@ mod tests {
         assert!(Value::<DefaultNumericTypes>::from_float(3.3).is_float());
         assert!(Value::<DefaultNumericTypes>::from(true).is_boolean());
         assert!(Value::<DefaultNumericTypes>::from(TupleType::new()).is_tuple());
+        assert!(Value::<DefaultNumericTypes>::Array(ArrayType::new()).is_array());
+        assert!(!Value::<DefaultNumericTypes>::from(TupleType::new()).is_array());
     }
 
     #[test]
@@ -7,6 +7,7 @@ use std::{
 use crate::EvalexprResult;
 
 pub mod default_numeric_types;
+pub mod i128_numeric_types;
 /*#[cfg(feature = "num-traits")]
 pub mod num_traits_numeric_types;*/
 
@@ -54,9 +55,15 @@ pub trait EvalexprInt<NumericTypes: EvalexprNumericTypes<Int = Self>>:
     #[expect(clippy::wrong_self_convention)]
     fn into_usize(&self) -> EvalexprResult<usize, NumericTypes>;
 
+    /// Parse `Self` from a string of digits in the given radix, which is between 2 and 36.
+    #[expect(clippy::result_unit_err)]
+    fn from_radix_str(literal: &str, radix: u32) -> Result<Self, ()>;
+
     /// Parse `Self` from a hex string.
     #[expect(clippy::result_unit_err)]
-    fn from_hex_str(literal: &str) -> Result<Self, ()>;
+    fn from_hex_str(literal: &str) -> Result<Self, ()> {
+        Self::from_radix_str(literal, 16)
+    }
 
     /// Perform an addition operation, returning an error on overflow.
     fn checked_add(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes>;
@@ -79,6 +86,70 @@ pub trait EvalexprInt<NumericTypes: EvalexprNumericTypes<Int = Self>>:
     /// Compute the absolute value, returning an error on overflow.
     fn abs(&self) -> EvalexprResult<Self, NumericTypes>;
 
+    /// Perform an addition operation, wrapping around at the boundary of the type on overflow.
+    fn wrapping_add(&self, rhs: &Self) -> Self;
+
+    /// Perform a subtraction operation, wrapping around at the boundary of the type on overflow.
+    fn wrapping_sub(&self, rhs: &Self) -> Self;
+
+    /// Perform a negation operation, wrapping around at the boundary of the type on overflow.
+    fn wrapping_neg(&self) -> Self;
+
+    /// Perform a multiplication operation, wrapping around at the boundary of the type on overflow.
+    fn wrapping_mul(&self, rhs: &Self) -> Self;
+
+    /// Perform an addition operation, saturating at the boundary of the type on overflow.
+    ///
+    /// The default implementation is based on [`checked_add`](EvalexprInt::checked_add).
+    fn saturating_add(&self, rhs: &Self) -> Self {
+        self.checked_add(rhs).unwrap_or_else(|_| {
+            if is_negative::<NumericTypes>(rhs) {
+                Self::MIN
+            } else {
+                Self::MAX
+            }
+        })
+    }
+
+    /// Perform a subtraction operation, saturating at the boundary of the type on overflow.
+    ///
+    /// The default implementation is based on [`checked_sub`](EvalexprInt::checked_sub).
+    fn saturating_sub(&self, rhs: &Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_else(|_| {
+            if is_negative::<NumericTypes>(rhs) {
+                Self::MAX
+            } else {
+                Self::MIN
+            }
+        })
+    }
+
+    /// Perform a negation operation, saturating at the boundary of the type on overflow.
+    ///
+    /// The default implementation is based on [`checked_neg`](EvalexprInt::checked_neg).
+    fn saturating_neg(&self) -> Self {
+        self.checked_neg().unwrap_or_else(|_| {
+            if is_negative::<NumericTypes>(self) {
+                Self::MAX
+            } else {
+                Self::MIN
+            }
+        })
+    }
+
+    /// Perform a multiplication operation, saturating at the boundary of the type on overflow.
+    ///
+    /// The default implementation is based on [`checked_mul`](EvalexprInt::checked_mul).
+    fn saturating_mul(&self, rhs: &Self) -> Self {
+        self.checked_mul(rhs).unwrap_or_else(|_| {
+            if is_negative::<NumericTypes>(self) != is_negative::<NumericTypes>(rhs) {
+                Self::MIN
+            } else {
+                Self::MAX
+            }
+        })
+    }
+
     /// Perform a bitand operation.
     fn bitand(&self, rhs: &Self) -> Self;
 
@@ -91,11 +162,16 @@ pub trait EvalexprInt<NumericTypes: EvalexprNumericTypes<Int = Self>>:
     /// Perform a bitnot operation.
     fn bitnot(&self) -> Self;
 
-    /// Perform a shl operation.
-    fn bit_shift_left(&self, rhs: &Self) -> Self;
+    /// Perform a shl operation, returning an error if the shift amount is negative or not less than the bit width of the type.
+    fn bit_shift_left(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes>;
+
+    /// Perform a shr operation, returning an error if the shift amount is negative or not less than the bit width of the type.
+    fn bit_shift_right(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes>;
+}
 
-    /// Perform a shr operation.
-    fn bit_shift_right(&self, rhs: &Self) -> Self;
+/// Returns `true` if the given integer is less than zero.
+fn is_negative<NumericTypes: EvalexprNumericTypes>(int: &NumericTypes::Int) -> bool {
+    NumericTypes::Int::from_usize(0).map_or(false, |zero| *int < zero)
 }
 
 /// A float type that can be used by `evalexpr`.
@@ -123,6 +199,18 @@ pub trait EvalexprFloat<NumericTypes: EvalexprNumericTypes<Float = Self>>:
     /// Typically, this is positive infinity.
     const MAX: Self;
 
+    /// The smallest finite floating point value.
+    const MIN_FINITE: Self;
+
+    /// The largest finite floating point value.
+    const MAX_FINITE: Self;
+
+    /// Positive infinity.
+    const INFINITY: Self;
+
+    /// A NaN ("not a number") value.
+    const NAN: Self;
+
     /// Perform a power operation.
     fn pow(&self, exponent: &Self) -> Self;
 
@@ -1,8 +1,8 @@
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+#[cfg(not(feature = "rand"))]
+use crate::EvalexprError;
+use crate::{EvalexprResult, Value};
 
-use crate::{EvalexprError, EvalexprResult, Value};
-
-use super::{EvalexprFloat, EvalexprInt, EvalexprNumericTypes};
+use super::{EvalexprFloat, EvalexprNumericTypes};
 
 /// See [`EvalexprNumericTypes`].
 ///
@@ -23,132 +23,205 @@ impl EvalexprNumericTypes for DefaultNumericTypes {
     }
 }
 
-impl<NumericTypes: EvalexprNumericTypes<Int = Self>> EvalexprInt<NumericTypes> for i64 {
-    const MIN: Self = Self::MIN;
-    const MAX: Self = Self::MAX;
-
-    fn from_usize(int: usize) -> EvalexprResult<Self, NumericTypes> {
-        int.try_into()
-            .map_err(|_| EvalexprError::IntFromUsize { usize_int: int })
-    }
-
-    fn into_usize(&self) -> EvalexprResult<usize, NumericTypes> {
-        if *self >= 0 {
-            (*self as u64)
-                .try_into()
-                .map_err(|_| EvalexprError::IntIntoUsize { int: *self })
-        } else {
-            Err(EvalexprError::IntIntoUsize { int: *self })
-        }
-    }
-
-    fn from_hex_str(literal: &str) -> Result<Self, ()> {
-        Self::from_str_radix(literal, 16).map_err(|_| ())
-    }
-
-    fn checked_add(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_add(*rhs);
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::addition_error(
-                Value::<NumericTypes>::from_int(*self),
-                Value::<NumericTypes>::from_int(*rhs),
-            ))
-        }
-    }
-
-    fn checked_sub(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_sub(*rhs);
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::subtraction_error(
-                Value::<NumericTypes>::from_int(*self),
-                Value::<NumericTypes>::from_int(*rhs),
-            ))
-        }
-    }
-
-    fn checked_neg(&self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_neg();
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::negation_error(
-                Value::<NumericTypes>::from_int(*self),
-            ))
-        }
-    }
-
-    fn checked_mul(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_mul(*rhs);
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::multiplication_error(
-                Value::<NumericTypes>::from_int(*self),
-                Value::<NumericTypes>::from_int(*rhs),
-            ))
-        }
+impl From<i64> for Value<DefaultNumericTypes> {
+    fn from(int: i64) -> Self {
+        Value::Int(int)
     }
+}
 
-    fn checked_div(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_div(*rhs);
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::division_error(
-                Value::<NumericTypes>::from_int(*self),
-                Value::<NumericTypes>::from_int(*rhs),
-            ))
-        }
+impl From<f64> for Value<DefaultNumericTypes> {
+    fn from(float: f64) -> Self {
+        Value::Float(float)
     }
+}
 
-    fn checked_rem(&self, rhs: &Self) -> EvalexprResult<Self, NumericTypes> {
-        let result = (*self).checked_rem(*rhs);
-        if let Some(result) = result {
-            Ok(result)
-        } else {
-            Err(EvalexprError::modulation_error(
-                Value::<NumericTypes>::from_int(*self),
-                Value::<NumericTypes>::from_int(*rhs),
-            ))
+/// Implements [`EvalexprInt`] for a primitive signed integer type, using its checked, wrapping and saturating operations.
+macro_rules! impl_evalexpr_int {
+    ($int:ty) => {
+        impl<NumericTypes: $crate::EvalexprNumericTypes<Int = Self>>
+            $crate::EvalexprInt<NumericTypes> for $int
+        {
+            const MIN: Self = Self::MIN;
+            const MAX: Self = Self::MAX;
+
+            fn from_usize(int: usize) -> $crate::EvalexprResult<Self, NumericTypes> {
+                int.try_into()
+                    .map_err(|_| $crate::EvalexprError::IntFromUsize { usize_int: int })
+            }
+
+            fn into_usize(&self) -> $crate::EvalexprResult<usize, NumericTypes> {
+                (*self)
+                    .try_into()
+                    .map_err(|_| $crate::EvalexprError::IntIntoUsize { int: *self })
+            }
+
+            fn from_radix_str(literal: &str, radix: u32) -> Result<Self, ()> {
+                Self::from_str_radix(literal, radix).map_err(|_| ())
+            }
+
+            fn checked_add(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_add(*rhs);
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::addition_error(
+                        $crate::Value::<NumericTypes>::from_int(*self),
+                        $crate::Value::<NumericTypes>::from_int(*rhs),
+                    ))
+                }
+            }
+
+            fn checked_sub(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_sub(*rhs);
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::subtraction_error(
+                        $crate::Value::<NumericTypes>::from_int(*self),
+                        $crate::Value::<NumericTypes>::from_int(*rhs),
+                    ))
+                }
+            }
+
+            fn checked_neg(&self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_neg();
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::negation_error($crate::Value::<
+                        NumericTypes,
+                    >::from_int(
+                        *self
+                    )))
+                }
+            }
+
+            fn checked_mul(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_mul(*rhs);
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::multiplication_error(
+                        $crate::Value::<NumericTypes>::from_int(*self),
+                        $crate::Value::<NumericTypes>::from_int(*rhs),
+                    ))
+                }
+            }
+
+            fn checked_div(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_div(*rhs);
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::division_error(
+                        $crate::Value::<NumericTypes>::from_int(*self),
+                        $crate::Value::<NumericTypes>::from_int(*rhs),
+                    ))
+                }
+            }
+
+            fn checked_rem(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                let result = (*self).checked_rem(*rhs);
+                if let Some(result) = result {
+                    Ok(result)
+                } else {
+                    Err($crate::EvalexprError::modulation_error(
+                        $crate::Value::<NumericTypes>::from_int(*self),
+                        $crate::Value::<NumericTypes>::from_int(*rhs),
+                    ))
+                }
+            }
+
+            fn abs(&self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                Ok((*self).abs())
+            }
+
+            fn wrapping_add(&self, rhs: &Self) -> Self {
+                (*self).wrapping_add(*rhs)
+            }
+
+            fn wrapping_sub(&self, rhs: &Self) -> Self {
+                (*self).wrapping_sub(*rhs)
+            }
+
+            fn wrapping_neg(&self) -> Self {
+                (*self).wrapping_neg()
+            }
+
+            fn wrapping_mul(&self, rhs: &Self) -> Self {
+                (*self).wrapping_mul(*rhs)
+            }
+
+            fn saturating_add(&self, rhs: &Self) -> Self {
+                (*self).saturating_add(*rhs)
+            }
+
+            fn saturating_sub(&self, rhs: &Self) -> Self {
+                (*self).saturating_sub(*rhs)
+            }
+
+            fn saturating_neg(&self) -> Self {
+                (*self).saturating_neg()
+            }
+
+            fn saturating_mul(&self, rhs: &Self) -> Self {
+                (*self).saturating_mul(*rhs)
+            }
+
+            fn bitand(&self, rhs: &Self) -> Self {
+                std::ops::BitAnd::bitand(*self, *rhs)
+            }
+
+            fn bitor(&self, rhs: &Self) -> Self {
+                std::ops::BitOr::bitor(*self, *rhs)
+            }
+
+            fn bitxor(&self, rhs: &Self) -> Self {
+                std::ops::BitXor::bitxor(*self, *rhs)
+            }
+
+            fn bitnot(&self) -> Self {
+                std::ops::Not::not(*self)
+            }
+
+            fn bit_shift_left(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                u32::try_from(*rhs)
+                    .ok()
+                    .and_then(|rhs| (*self).checked_shl(rhs))
+                    .ok_or_else(|| {
+                        $crate::EvalexprError::shift_error(
+                            $crate::Value::<NumericTypes>::from_int(*self),
+                            $crate::Value::<NumericTypes>::from_int(*rhs),
+                        )
+                    })
+            }
+
+            fn bit_shift_right(&self, rhs: &Self) -> $crate::EvalexprResult<Self, NumericTypes> {
+                u32::try_from(*rhs)
+                    .ok()
+                    .and_then(|rhs| (*self).checked_shr(rhs))
+                    .ok_or_else(|| {
+                        $crate::EvalexprError::shift_error(
+                            $crate::Value::<NumericTypes>::from_int(*self),
+                            $crate::Value::<NumericTypes>::from_int(*rhs),
+                        )
+                    })
+            }
         }
-    }
-
-    fn abs(&self) -> EvalexprResult<Self, NumericTypes> {
-        Ok((*self).abs())
-    }
-
-    fn bitand(&self, rhs: &Self) -> Self {
-        BitAnd::bitand(*self, *rhs)
-    }
-
-    fn bitor(&self, rhs: &Self) -> Self {
-        BitOr::bitor(*self, *rhs)
-    }
-
-    fn bitxor(&self, rhs: &Self) -> Self {
-        BitXor::bitxor(*self, *rhs)
-    }
-
-    fn bitnot(&self) -> Self {
-        Not::not(*self)
-    }
+    };
+}
 
-    fn bit_shift_left(&self, rhs: &Self) -> Self {
-        Shl::shl(*self, *rhs)
-    }
+pub(super) use impl_evalexpr_int;
 
-    fn bit_shift_right(&self, rhs: &Self) -> Self {
-        Shr::shr(*self, *rhs)
-    }
-}
+impl_evalexpr_int!(i64);
 
 impl<NumericTypes: EvalexprNumericTypes<Float = Self>> EvalexprFloat<NumericTypes> for f64 {
     const MIN: Self = Self::NEG_INFINITY;
     const MAX: Self = Self::INFINITY;
+    const MIN_FINITE: Self = Self::MIN;
+    const MAX_FINITE: Self = Self::MAX;
+    const INFINITY: Self = Self::INFINITY;
+    const NAN: Self = Self::NAN;
 
     fn pow(&self, exponent: &Self) -> Self {
         (*self).powf(*exponent)
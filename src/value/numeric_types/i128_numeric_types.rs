@@ -0,0 +1,36 @@
+use super::{default_numeric_types::impl_evalexpr_int, EvalexprNumericTypes};
+
+/// See [`EvalexprNumericTypes`].
+///
+/// This empty struct uses [`i128`] as its integer type and [`f64`] as its float type.
+/// The larger integer type allows for example to compute with amounts of money in small units without overflowing.
+/// Integer arithmetic is checked for overflow like with [`DefaultNumericTypes`](crate::DefaultNumericTypes).
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let node = build_operator_tree::<I128NumericTypes>("9223372036854775807 * 4").unwrap(); // Do proper error handling here
+/// assert_eq!(node.eval(), Ok(Value::from_int(36893488147419103228)));
+///
+/// let node = build_operator_tree::<I128NumericTypes>("170141183460469231731687303715884105727 + 1").unwrap(); // Do proper error handling here
+/// assert!(matches!(node.eval(), Err(EvalexprError::AdditionError { .. })));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct I128NumericTypes;
+
+impl EvalexprNumericTypes for I128NumericTypes {
+    type Int = i128;
+    type Float = f64;
+
+    fn int_as_float(int: &Self::Int) -> Self::Float {
+        *int as Self::Float
+    }
+
+    fn float_as_int(float: &Self::Float) -> Self::Int {
+        *float as Self::Int
+    }
+}
+
+impl_evalexpr_int!(i128);
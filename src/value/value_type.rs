@@ -15,6 +15,8 @@ pub enum ValueType {
     Boolean,
     /// The `Value::Tuple` type.
     Tuple,
+    /// The `Value::Array` type.
+    Array,
     /// The `Value::Empty` type.
     Empty,
 }
@@ -27,6 +29,7 @@ impl<NumericTypes: EvalexprNumericTypes> From<&Value<NumericTypes>> for ValueTyp
             Value::Int(_) => ValueType::Int,
             Value::Boolean(_) => ValueType::Boolean,
             Value::Tuple(_) => ValueType::Tuple,
+            Value::Array(_) => ValueType::Array,
             Value::Empty => ValueType::Empty,
         }
     }
@@ -0,0 +1,34 @@
+#![cfg(not(tarpaulin_include))]
+#![cfg(feature = "chrono")]
+
+use evalexpr::*;
+
+#[test]
+fn test_time_format_iso() {
+    assert_eq!(
+        eval("time::format_iso(0)"),
+        Ok(Value::from("1970-01-01T00:00:00Z"))
+    );
+    assert_eq!(
+        eval("time::format_iso(1700000000)"),
+        Ok(Value::from("2023-11-14T22:13:20Z"))
+    );
+    assert_eq!(
+        eval("time::format_iso(-86400)"),
+        Ok(Value::from("1969-12-31T00:00:00Z"))
+    );
+    assert_eq!(
+        eval("time::format_iso(time::add_days(1700000000, 1))"),
+        Ok(Value::from("2023-11-15T22:13:20Z"))
+    );
+    assert_eq!(
+        eval("time::format_iso(9223372036854775807)"),
+        Err(EvalexprError::CustomMessage(
+            "time::format_iso expected a timestamp within the supported range of dates, but got 9223372036854775807".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("time::format_iso(1.5)"),
+        Err(EvalexprError::expected_int(Value::from_float(1.5)))
+    );
+}
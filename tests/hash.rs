@@ -0,0 +1,32 @@
+#![cfg(not(tarpaulin_include))]
+#![cfg(feature = "hash")]
+
+use evalexpr::*;
+
+#[test]
+fn test_hash_functions() {
+    assert_eq!(
+        eval("hash::md5(\"\")"),
+        Ok(Value::from("d41d8cd98f00b204e9800998ecf8427e"))
+    );
+    assert_eq!(
+        eval("hash::md5(\"The quick brown fox jumps over the lazy dog\")"),
+        Ok(Value::from("9e107d9d372bb6826bd81d3542a419d6"))
+    );
+    assert_eq!(
+        eval("hash::sha256(\"\")"),
+        Ok(Value::from(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        ))
+    );
+    assert_eq!(
+        eval("hash::sha256(\"abc\")"),
+        Ok(Value::from(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        ))
+    );
+    assert_eq!(
+        eval("hash::sha256(1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
+    );
+}
@@ -1,7 +1,7 @@
 #![cfg(not(tarpaulin_include))]
 
 use evalexpr::{error::*, *};
-use std::convert::TryFrom;
+use std::{borrow::Cow, collections::HashMap, convert::TryFrom};
 
 #[test]
 fn test_unary_examples() {
@@ -97,6 +97,19 @@ fn test_pow_examples() {
     );
     assert_eq!(eval("1 ^ 4 + 2"), Ok(Value::Float(3.0)));
     assert_eq!(eval("2 ^ (4 + 2)"), Ok(Value::Float(64.0)));
+
+    // The double star is an alternative spelling.
+    assert_eq!(eval("2 ** 10"), Ok(Value::Float(1024.0)));
+    assert_eq!(eval("2**10"), Ok(Value::Float(1024.0)));
+    assert_eq!(eval("2 **10"), Ok(Value::Float(1024.0)));
+    assert_eq!(eval("2** 10"), Ok(Value::Float(1024.0)));
+    assert_eq!(eval("3 * 2 ** 2"), Ok(Value::Float(12.0)));
+    assert_eq!(eval("-2 ** 2"), Ok(Value::Float(-4.0)));
+    assert_eq!(eval("2 ** 3 ** 2"), eval("2 ^ 3 ^ 2"));
+    assert_eq!(eval("a = 3.0; a **= 2; a"), Ok(Value::Float(9.0)));
+    // There is no unary star, so separated stars are not an exponentiation.
+    assert!(eval("2 * * 10").is_err());
+    assert!(eval("2 ***10").is_err());
 }
 
 #[test]
@@ -110,6 +123,55 @@ fn test_boolean_examples() {
     assert_eq!(eval("5.0 <= 4.9 || !(4 > 3.5)"), Ok(Value::Boolean(false)));
 }
 
+#[test]
+fn test_mixed_numeric_equality() {
+    assert_eq!(eval("2 == 2.0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("0.0 == 0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("-0.0 == 0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("2 != 2.0"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("1 == 1.5"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("1 != 1.5"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("0 == 0.0 / 0.0"), Ok(Value::Boolean(false)));
+    // Ints that cannot be represented exactly as float are not equal to the float they are rounded to.
+    assert_eq!(
+        eval("9007199254740993 == 9007199254740992.0"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("9007199254740992 == 9007199254740992.0"),
+        Ok(Value::Boolean(true))
+    );
+    // Floats outside of the int range are not equal to the int they saturate to.
+    assert_eq!(
+        eval("9223372036854775807 == 9223372036854775808.0"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("9223372036854775807 != 1e300"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("-9223372036854775807 - 1 == -9223372036854775808.0"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("-9223372036854775807 - 1 == -1e300"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("9223372036854775807 == 1.0 / 0.0"),
+        Ok(Value::Boolean(false))
+    );
+    // Other types are not converted.
+    assert_eq!(eval("1 == \"1\""), Ok(Value::Boolean(false)));
+    assert_eq!(eval("1 == true"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("\"1.0\" != 1.0"), Ok(Value::Boolean(true)));
+    // Tuples are compared structurally.
+    assert_eq!(eval("(1, 2) == (1, 2)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("(1, 2) == (1, 2.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("(1, 2) != (1.0, 2)"), Ok(Value::Boolean(true)));
+}
+
 #[test]
 fn test_with_context() {
     let mut context = HashMapContext::<DefaultNumericTypes>::new();
@@ -322,11 +384,106 @@ fn test_capturing_functions() {
     );
 }
 
+#[test]
+fn test_functions_propagating_std_errors() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_function(
+            "parse_int".into(),
+            Function::new(|argument| {
+                let int: i64 = argument.as_string()?.trim().parse()?;
+                Ok(Value::Int(int))
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "fallible".into(),
+            Function::new(|argument| {
+                let result: Result<(), Box<dyn std::error::Error + Send + Sync>> =
+                    if argument.as_boolean()? {
+                        Ok(())
+                    } else {
+                        Err("something went wrong".into())
+                    };
+                result?;
+                Ok(Value::Empty)
+            }),
+        )
+        .unwrap();
+
+    assert_eq!(
+        eval_with_context("parse_int(\" 42 \")", &context),
+        Ok(Value::Int(42))
+    );
+    assert_eq!(
+        eval_with_context("parse_int(\"4x2\")", &context),
+        Err(EvalexprError::CustomMessage(
+            "invalid digit found in string".into()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("parse_int(42)", &context),
+        Err(EvalexprError::expected_string(Value::Int(42)))
+    );
+    assert_eq!(
+        eval_with_context("fallible(true)", &context),
+        Ok(Value::Empty)
+    );
+    assert_eq!(
+        eval_with_context("fallible(false)", &context),
+        Err(EvalexprError::CustomMessage("something went wrong".into()))
+    );
+}
+
 #[test]
 fn test_builtin_functions() {
     // Log
     assert_eq!(eval("math::ln(2.718281828459045)"), Ok(Value::Float(1.0)));
     assert_eq!(eval("math::log(9, 9)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log(8, 2)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::log(100, 10)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("math::log(2.718281828459045)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log(1)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval("math::log(-1)"),
+        Err(EvalexprError::CustomMessage(
+            "math::log is only defined for positive numbers, but got -1".into()
+        ))
+    );
+    assert_eq!(
+        eval("math::log(0, 10)"),
+        Err(EvalexprError::CustomMessage(
+            "math::log is only defined for positive numbers, but got 0".into()
+        ))
+    );
+    assert_eq!(
+        eval("math::log(8, -2)"),
+        Err(EvalexprError::CustomMessage(
+            "math::log is only defined for positive bases other than 1, but got -2".into()
+        ))
+    );
+    assert_eq!(
+        eval("math::log(8, 1)"),
+        Err(EvalexprError::CustomMessage(
+            "math::log is only defined for positive bases other than 1, but got 1".into()
+        ))
+    );
+    assert_eq!(
+        eval("math::log(8, 2, 2)"),
+        Err(EvalexprError::ExpectedFixedLengthTuple {
+            expected_length: 2,
+            actual: Value::from(vec![
+                Value::from_int(8),
+                Value::from_int(2),
+                Value::from_int(2)
+            ])
+        })
+    );
+    assert_eq!(
+        eval("math::log(\"8\")"),
+        Err(EvalexprError::expected_number(Value::from("8")))
+    );
     assert_eq!(eval("math::log2(2)"), Ok(Value::Float(1.0)));
     assert_eq!(eval("math::log10(10)"), Ok(Value::Float(1.0)));
     // Powers
@@ -388,6 +545,32 @@ fn test_builtin_functions() {
     assert_eq!(eval("round(1.9)"), Ok(Value::Float(2.0)));
     assert_eq!(eval("ceil(1.1)"), Ok(Value::Float(2.0)));
     assert_eq!(eval("ceil(1.9)"), Ok(Value::Float(2.0)));
+    assert_eq!(
+        eval("round_to(3.14159, 2) == 3.14"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(eval("round_to(1.23456, 3)"), Ok(Value::Float(1.235)));
+    assert_eq!(eval("round_to(-2.5, 0)"), Ok(Value::Float(-3.0)));
+    assert_eq!(eval("round_to(1234, -2)"), Ok(Value::Float(1200.0)));
+    assert_eq!(eval("round_to(1250, -2)"), Ok(Value::Float(1300.0)));
+    assert_eq!(eval("round_to(1e300, 20)"), Ok(Value::Float(1e300)));
+    assert_eq!(eval("floor_to(1.23956, 2)"), Ok(Value::Float(1.23)));
+    assert_eq!(eval("floor_to(-3.14159, 2)"), Ok(Value::Float(-3.15)));
+    assert_eq!(eval("floor_to(1299, -2)"), Ok(Value::Float(1200.0)));
+    assert_eq!(eval("ceil_to(3.14159, 2)"), Ok(Value::Float(3.15)));
+    assert_eq!(eval("ceil_to(1201, -2)"), Ok(Value::Float(1300.0)));
+    assert!(matches!(
+        eval("ceil_to(1, -400)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert_eq!(
+        eval("round_to(\"3.14\", 1)"),
+        Err(EvalexprError::expected_number(Value::from("3.14")))
+    );
+    assert_eq!(
+        eval("round_to(3.14, 1.5)"),
+        Err(EvalexprError::expected_int(Value::from_float(1.5)))
+    );
     assert_eq!(eval("math::is_nan(1.0/0.0)"), Ok(Value::Boolean(false)));
     assert_eq!(eval("math::is_nan(0.0/0.0)"), Ok(Value::Boolean(true)));
     assert_eq!(eval("math::is_finite(1.0/0.0)"), Ok(Value::Boolean(false)));
@@ -400,2177 +583,6150 @@ fn test_builtin_functions() {
     assert_eq!(eval("math::is_infinite(1.0/0.0)"), Ok(Value::Boolean(true)));
     assert_eq!(eval("math::is_normal(1.0/0.0)"), Ok(Value::Boolean(false)));
     assert_eq!(eval("math::is_normal(0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_valid(1.5)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_valid(-3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_valid(0.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_valid(-1.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_valid(\"1.5\")"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_valid((1, 2))"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_valid(())"), Ok(Value::Boolean(false)));
+    // Numeric limits
+    assert_eq!(eval("math::int_min()"), Ok(Value::Int(i64::MIN)));
+    assert_eq!(eval("math::int_max()"), Ok(Value::Int(i64::MAX)));
+    assert_eq!(eval("math::float_min()"), Ok(Value::Float(f64::MIN)));
+    assert_eq!(eval("math::float_max()"), Ok(Value::Float(f64::MAX)));
+    assert_eq!(eval("math::inf()"), Ok(Value::Float(f64::INFINITY)));
+    assert_eq!(eval("-math::inf()"), Ok(Value::Float(f64::NEG_INFINITY)));
+    assert_eq!(eval("math::is_nan(math::nan())"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("math::is_finite(math::float_max())"),
+        Ok(Value::Boolean(true))
+    );
+    assert!(matches!(
+        eval("math::int_max() + 1"),
+        Err(EvalexprError::AdditionError { .. })
+    ));
+    assert_eq!(
+        build_operator_tree::<I128NumericTypes>("math::int_max()")
+            .unwrap()
+            .eval(),
+        Ok(Value::Int(i128::MAX))
+    );
+    assert_eq!(
+        build_operator_tree::<I128NumericTypes>("math::int_min()")
+            .unwrap()
+            .eval(),
+        Ok(Value::Int(i128::MIN))
+    );
+    assert_eq!(
+        eval("math::int_max(1)"),
+        Err(EvalexprError::expected_empty(Value::from_int(1)))
+    );
     // Absolute
     assert_eq!(eval("math::abs(15.4)"), Ok(Value::Float(15.4)));
     assert_eq!(eval("math::abs(-15.4)"), Ok(Value::Float(15.4)));
     assert_eq!(eval("math::abs(15)"), Ok(Value::Int(15)));
     assert_eq!(eval("math::abs(-15)"), Ok(Value::Int(15)));
-    // Other
-    assert_eq!(eval("typeof(4.0, 3)"), Ok(Value::String("tuple".into())));
-    assert_eq!(eval("typeof(4.0)"), Ok(Value::String("float".into())));
-    assert_eq!(eval("typeof(4)"), Ok(Value::String("int".into())));
-    assert_eq!(eval("typeof(\"\")"), Ok(Value::String("string".into())));
-    assert_eq!(eval("typeof(true)"), Ok(Value::String("boolean".into())));
-    assert_eq!(eval("typeof()"), Ok(Value::String("empty".into())));
-    assert_eq!(eval("min(4.0, 3)"), Ok(Value::Int(3)));
-    assert_eq!(eval("max(4.0, 3)"), Ok(Value::Float(4.0)));
-    assert_eq!(eval("len(\"foobar\")"), Ok(Value::Int(6)));
-    assert_eq!(eval("len(\"a\", \"b\")"), Ok(Value::Int(2)));
-    //Contians
+    // Normalize
+    assert_eq!(eval("math::normalize(15, 10, 20)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("math::normalize(10, 10, 20)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::normalize(20, 10, 20)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::normalize(5, 10, 20)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::normalize(25.5, 10, 20)"), Ok(Value::Float(1.0)));
     assert_eq!(
-        eval("contains(1, 2, 3)"),
-        Err(EvalexprError::expected_fixed_len_tuple(
-            2,
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        eval("math::normalize(0.25, 0, 1.0)"),
+        Ok(Value::Float(0.25))
+    );
+    assert_eq!(eval("math::normalize(12, 20, 10)"), Ok(Value::Float(0.8)));
+    assert_eq!(eval("math::normalize(25, 20, 10)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval("math::normalize(3, 2, 2)"),
+        Err(EvalexprError::CustomMessage(
+            "math::normalize requires a non-empty range, but the lower and upper bound are both 2"
+                .into()
         ))
     );
+    assert_eq!(eval("math::wrap(370, 0, 360)"), Ok(Value::from_int(10)));
+    assert_eq!(eval("math::wrap(720, 0, 360)"), Ok(Value::from_int(0)));
+    assert_eq!(eval("math::wrap(-90, 0, 360)"), Ok(Value::from_int(270)));
+    assert_eq!(eval("math::wrap(-720, 0, 360)"), Ok(Value::from_int(0)));
+    assert_eq!(eval("math::wrap(45, 0, 360)"), Ok(Value::from_int(45)));
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), \"bar\")"),
-        Ok(Value::Boolean(true))
+        eval("math::wrap(190, -180, 180)"),
+        Ok(Value::from_int(-170))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), \"buzz\")"),
-        Ok(Value::Boolean(false)),
+        eval("math::wrap(-181, -180, 180)"),
+        Ok(Value::from_int(179))
     );
     assert_eq!(
-        eval("contains(\"foo\", \"bar\")"),
-        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
+        eval("math::wrap(180, -180, 180)"),
+        Ok(Value::from_int(-180))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\", 123), 123)"),
-        Ok(Value::Boolean(true))
+        eval("math::wrap(370.5, 0, 360)"),
+        Ok(Value::from_float(10.5))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
-        Err(EvalexprError::type_error(
-            Value::Tuple(vec![
-                Value::String("buzz".into()),
-                Value::String("bazz".into())
-            ]),
-            vec![
-                ValueType::String,
-                ValueType::Int,
-                ValueType::Float,
-                ValueType::Boolean
-            ]
-        ))
+        eval("math::wrap(-0.5, 0, 360)"),
+        Ok(Value::from_float(359.5))
     );
-    //Contains Any
+    assert_eq!(eval("math::wrap(7.5, 2, 4.5)"), Ok(Value::from_float(2.5)));
     assert_eq!(
-        eval("contains_any(1, 2, 3)"),
-        Err(EvalexprError::expected_fixed_len_tuple(
-            2,
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
-        ))
+        eval("math::wrap(-1e-20, 0, 1.0)"),
+        Ok(Value::from_float(0.0))
     );
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"bar\", \"buzz\"))"),
-        Ok(Value::Boolean(true))
+        eval("math::wrap(5, 10, 10)"),
+        Err(EvalexprError::CustomMessage(
+            "math::wrap requires the lower bound 10 to be less than the upper bound 10".into()
+        ))
     );
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
-        Ok(Value::Boolean(false)),
+        eval("math::wrap(5, 2.5, 1)"),
+        Err(EvalexprError::CustomMessage(
+            "math::wrap requires the lower bound 2.5 to be less than the upper bound 1".into()
+        ))
     );
+    for expression in [
+        "math::wrap(0.0/0.0, 0, 360)",
+        "math::wrap(10, 0.0/0.0, 360)",
+        "math::wrap(10, 0, 0.0/0.0)",
+    ] {
+        assert!(
+            matches!(eval(expression), Ok(Value::Float(wrapped)) if wrapped.is_nan()),
+            "{expression}"
+        );
+    }
     assert_eq!(
-        eval("contains_any((1,2,3), (3,4,5))"),
-        Ok(Value::Boolean(true))
+        eval("math::wrap(0, -9223372036854775807 - 1, 9223372036854775807)"),
+        Ok(Value::from_int(0))
     );
     assert_eq!(
-        eval("contains_any((1,2,3), (4,5,6))"),
-        Ok(Value::Boolean(false))
+        eval("math::wrap(9223372036854775807, -9223372036854775807 - 1, 9223372036854775807)"),
+        Ok(Value::from_int(i64::MIN))
     );
     assert_eq!(
-        eval("contains_any((true, false, true, true), (false, false, false))"),
-        Ok(Value::Boolean(true))
+        eval("math::wrap(9223372036854775807, -1, 9223372036854775806)"),
+        Ok(Value::from_int(0))
     );
     assert_eq!(
-        eval("contains_any(\"foo\", \"bar\")"),
-        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
+        eval("math::wrap(-9223372036854775807 - 1, -2, 9223372036854775807)"),
+        Ok(Value::from_int(1))
     );
+    assert_eq!(eval("math::clamp01(0.25)"), Ok(Value::Float(0.25)));
+    assert_eq!(eval("math::clamp01(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::clamp01(1)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::clamp01(-0.5)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::clamp01(3)"), Ok(Value::Float(1.0)));
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), \"buzz\")"),
-        Err(EvalexprError::expected_tuple(Value::String("buzz".into())))
+        eval("math::clamp01(\"1\")"),
+        Err(EvalexprError::expected_number(Value::from("1")))
     );
+    assert_eq!(eval("math::gamma(0.25, 2)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("math::gamma(0.25, 0.5)"), Ok(Value::Float(0.0625)));
+    assert_eq!(eval("math::gamma(0, 2.2)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::gamma(1, 2.2)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::gamma(-1, 2.2)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::gamma(2, 2.2)"), Ok(Value::Float(1.0)));
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", (1, 2, 3)))"),
-        Err(EvalexprError::type_error(
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
-            vec![
-                ValueType::String,
-                ValueType::Int,
-                ValueType::Float,
-                ValueType::Boolean
-            ]
+        eval("math::gamma(0.5, 0)"),
+        Err(EvalexprError::CustomMessage(
+            "math::gamma is undefined for a gamma of zero".into()
         ))
     );
-    // String
+    // Statistics
+    let data = "(2, 4, 4, 4, 5.0, 5, 7, 9)";
+    assert_eq!(eval(&format!("stats::mean{}", data)), Ok(Value::Float(5.0)));
     assert_eq!(
-        eval("str::to_lowercase(\"FOOBAR\")"),
-        Ok(Value::from("foobar"))
+        eval(&format!("stats::median{}", data)),
+        Ok(Value::Float(4.5))
     );
     assert_eq!(
-        eval("str::to_uppercase(\"foobar\")"),
-        Ok(Value::from("FOOBAR"))
+        eval(&format!("stats::variance{}", data)),
+        Ok(Value::Float(32.0 / 7.0))
     );
     assert_eq!(
-        eval("str::trim(\"  foo  bar \")"),
-        Ok(Value::from("foo  bar"))
+        eval(&format!("stats::stdev{}", data)),
+        Ok(Value::Float((32.0f64 / 7.0).sqrt()))
     );
+    assert_eq!(eval("stats::median(3, 1, 2)"), Ok(Value::Float(2.0)));
+    let latencies = "(120, 80, 100, 95, 300, 110, 90, 105, 85, 150)";
     assert_eq!(
-        eval("str::from(\"a\")"),
-        Ok(Value::String(String::from("a")))
+        eval(&format!("stats::percentile({}, 50)", latencies)),
+        Ok(Value::Float(102.5))
     );
-    assert_eq!(eval("str::from(1.0)"), Ok(Value::String(String::from("1"))));
+    let p95 = eval_float(&format!("stats::percentile({}, 95)", latencies)).unwrap();
+    assert!((p95 - 232.5).abs() < 1e-9);
     assert_eq!(
-        eval("str::from(4.2)"),
-        Ok(Value::String(String::from("4.2")))
+        eval(&format!("stats::percentile({}, 0)", latencies)),
+        Ok(Value::Float(80.0))
     );
-    assert_eq!(eval("str::from(1)"), Ok(Value::String(String::from("1"))));
     assert_eq!(
-        eval("str::from(true)"),
-        Ok(Value::String(String::from("true")))
+        eval(&format!("stats::percentile({}, 100)", latencies)),
+        Ok(Value::Float(300.0))
     );
     assert_eq!(
-        eval(r#"str::from((1, "foo", , false))"#),
-        Ok(Value::String(String::from(r#"(1, "foo", (), false)"#)))
+        eval(&format!("stats::percentile({}, 95) < 200", latencies)),
+        Ok(Value::Boolean(false))
     );
     assert_eq!(
-        eval("str::from(true)"),
-        Ok(Value::String(String::from("true")))
+        eval(&format!("stats::percentile({}, 100.5)", latencies)),
+        Err(EvalexprError::CustomMessage(
+            "stats::percentile requires a percentile between 0 and 100, but got 100.5".into()
+        ))
     );
     assert_eq!(
-        eval("str::from(1, 2, 3)"),
-        Ok(Value::String(String::from("(1, 2, 3)")))
+        eval(&format!("stats::percentile({}, -1)", latencies)),
+        Err(EvalexprError::CustomMessage(
+            "stats::percentile requires a percentile between 0 and 100, but got -1".into()
+        ))
     );
-    assert_eq!(eval("str::from()"), Ok(Value::String(String::from("()"))));
+    let context: HashMapContext<DefaultNumericTypes> =
+        context_map! { "empty" => Value::Tuple(vec![]) }.unwrap();
     assert_eq!(
-        eval("str::substring(\"foobar\", 3)"),
-        Ok(Value::String(String::from("bar")))
+        eval_with_context("stats::percentile(empty, 50)", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            0,
+            1..=usize::MAX
+        ))
     );
+    assert_eq!(eval("stats::mean(1, 2)"), Ok(Value::Float(1.5)));
+    assert_eq!(eval("stats::variance(1, 1)"), Ok(Value::Float(0.0)));
+    assert!(matches!(
+        eval("stats::median(1, math::sqrt(-1), 2)"),
+        Ok(Value::Float(median)) if median.is_nan()
+    ));
     assert_eq!(
-        eval("str::substring(\"foobar\", 3, 3)"),
-        Ok(Value::String(String::from("")))
+        eval("stats::mean()"),
+        Err(EvalexprError::expected_tuple(Value::Empty))
     );
+    let context: HashMapContext<DefaultNumericTypes> =
+        context_map! { "single" => Value::from(vec![Value::from_int(5)]) }.unwrap();
     assert_eq!(
-        eval("str::substring(\"foobar\", 3, 4)"),
-        Ok(Value::String(String::from("b")))
+        eval_with_context("stats::mean(single)", &context),
+        Ok(Value::Float(5.0))
     );
-    assert!(eval("str::substring()").is_err());
-    assert!(eval("str::substring(\"foobar\")").is_err());
-    assert!(eval("str::substring(\"foobar\", 2, 1)").is_err());
-    assert!(eval("str::substring(\"foobar\", 99999)").is_err());
-    assert!(eval("str::substring(\"foobar\", -1)").is_err());
-    assert!(eval("str::substring(\"foobar\", 0, -1)").is_err());
-    assert!(eval("str::substring(\"foobar\", 0, 1, 1)").is_err());
-    // Bitwise
-    assert_eq!(eval("bitand(5, -1)"), Ok(Value::Int(5)));
-    assert_eq!(eval("bitand(6, 5)"), Ok(Value::Int(4)));
-    assert_eq!(eval("bitor(5, -1)"), Ok(Value::Int(-1)));
-    assert_eq!(eval("bitor(6, 5)"), Ok(Value::Int(7)));
-    assert_eq!(eval("bitxor(5, -1)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("bitxor(6, 5)"), Ok(Value::Int(3)));
-    assert_eq!(eval("bitnot(5)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("bitnot(-1)"), Ok(Value::Int(0)));
-    assert_eq!(eval("shl(5, 1)"), Ok(Value::Int(10)));
-    assert_eq!(eval("shl(-6, 5)"), Ok(Value::Int(-192)));
-    assert_eq!(eval("shr(5, 1)"), Ok(Value::Int(2)));
-    assert_eq!(eval("shr(-6, 5)"), Ok(Value::Int(-1)));
-    assert_eq!(eval("if(true, -6, 5)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("if(false, -6, 5)"), Ok(Value::Int(5)));
     assert_eq!(
-        eval("if(2-1==1, \"good\", 0)"),
-        Ok(Value::String(String::from("good")))
+        eval_with_context("stats::variance(single)", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            1,
+            2..=usize::MAX
+        ))
     );
-}
-
-#[test]
-fn test_errors() {
     assert_eq!(
-        eval("-true"),
-        Err(EvalexprError::expected_number(Value::Boolean(true)))
+        eval("stats::stdev(1, \"2\")"),
+        Err(EvalexprError::expected_number(Value::from("2")))
     );
+    assert_eq!(eval("stats::zscore(7, 5, 2)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("stats::zscore(2, 5, 2.0)"), Ok(Value::Float(-1.5)));
+    assert_eq!(eval("stats::zscore(5, 5, 3)"), Ok(Value::Float(0.0)));
     assert_eq!(
-        eval("1-true"),
-        Err(EvalexprError::expected_number(Value::Boolean(true)))
+        eval(&format!(
+            "stats::zscore(9, stats::mean{0}, stats::stdev{0})",
+            data
+        )),
+        Ok(Value::Float(4.0 / (32.0f64 / 7.0).sqrt()))
     );
     assert_eq!(
-        eval("true-"),
-        Err(EvalexprError::WrongOperatorArgumentAmount {
-            actual: 1,
-            expected: 2,
-        })
+        eval("stats::zscore(7, 5, 0)"),
+        Err(EvalexprError::CustomMessage(
+            "stats::zscore is undefined for a standard deviation of zero".into()
+        ))
     );
-    assert_eq!(eval("!(()true)"), Err(EvalexprError::AppendedToLeafNode));
     assert_eq!(
-        eval("math::is_nan(\"xxx\")"),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::String("xxx".to_string())
-        })
-    );
-}
-
-#[test]
-fn test_no_panic() {
-    assert!(eval(&format!(
-        "{} + {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "-{} - {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "-(-{} - 1)",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "{} * {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "{} / {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        0
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "{} % {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        0
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "{} ^ {}",
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
-        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
-    ))
-    .is_ok());
-    assert!(eval("if").is_err());
-    assert!(eval("if()").is_err());
-    assert!(eval("if(true, 1)").is_err());
-    assert!(eval("if(false, 2)").is_err());
-    assert!(eval("if(1,1,1)").is_err());
-    assert!(eval("if(true,1,1,1)").is_err());
-}
-
-#[test]
-fn test_shortcut_functions() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    context
-        .set_value("string".into(), Value::from("a string"))
-        .unwrap();
-
-    assert_eq!(eval_string("\"3.3\""), Ok("3.3".to_owned()));
-    assert_eq!(
-        eval_string("3.3"),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
+        eval("stats::zscore(7, 5)"),
+        Err(EvalexprError::ExpectedFixedLengthTuple {
+            expected_length: 3,
+            actual: Value::from(vec![Value::from_int(7), Value::from_int(5)])
         })
     );
     assert_eq!(
-        eval_string("3..3"),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("stats::weighted_mean((90, 80, 70), (1, 2, 1))"),
+        Ok(Value::Float(80.0))
     );
     assert_eq!(
-        eval_string_with_context("string", &context),
-        Ok("a string".to_owned())
+        eval("stats::weighted_mean((2.0, 4), (0.25, 0.75))"),
+        Ok(Value::Float(3.5))
     );
     assert_eq!(
-        eval_string_with_context("3.3", &context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("stats::weighted_mean((5, 7), (0, 3))"),
+        Ok(Value::Float(7.0))
     );
     assert_eq!(
-        eval_string_with_context("3..3", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("stats::weighted_mean((5, 7), (0, 0))"),
+        Err(EvalexprError::CustomMessage(
+            "stats::weighted_mean is undefined for a total weight of zero".into()
+        ))
     );
     assert_eq!(
-        eval_string_with_context_mut("string", &mut context),
-        Ok("a string".to_string())
+        eval("stats::weighted_mean((5, 7), (1, -1))"),
+        Err(EvalexprError::CustomMessage(
+            "stats::weighted_mean is undefined for a total weight of zero".into()
+        ))
     );
     assert_eq!(
-        eval_string_with_context_mut("3.3", &mut context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
+        eval("stats::weighted_mean((1, 2, 3), (1, 2))"),
+        Err(EvalexprError::ExpectedFixedLengthTuple {
+            expected_length: 3,
+            actual: Value::from(vec![Value::from_int(1), Value::from_int(2)])
         })
     );
     assert_eq!(
-        eval_string_with_context_mut("3..3", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
-    );
-
-    assert_eq!(eval_float("3.3"), Ok(3.3));
-    assert_eq!(
-        eval_float("33"),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval("stats::weighted_mean((1, \"2\"), (1, 2))"),
+        Err(EvalexprError::expected_number(Value::from("2")))
     );
+    let series = "(1, 2, 3.0, 4, 8)";
     assert_eq!(
-        eval_float("asd()"),
-        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+        eval(&format!("stats::sma({}, 2)", series)),
+        Ok(Value::from(vec![1.5, 2.5, 3.5, 6.0]))
     );
-    assert_eq!(eval_float_with_context("3.3", &context), Ok(3.3));
     assert_eq!(
-        eval_float_with_context("33", &context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval(&format!("stats::sma({}, 3)", series)),
+        Ok(Value::from(vec![2.0, 3.0, 5.0]))
     );
     assert_eq!(
-        eval_float_with_context("asd)", &context),
-        Err(EvalexprError::UnmatchedRBrace)
+        eval(&format!("stats::sma({}, 1)", series)),
+        Ok(Value::from(vec![1.0, 2.0, 3.0, 4.0, 8.0]))
     );
-    assert_eq!(eval_float_with_context_mut("3.3", &mut context), Ok(3.3));
     assert_eq!(
-        eval_float_with_context_mut("33", &mut context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval(&format!("stats::sma({}, 5)", series)),
+        Ok(Value::from(vec![3.6]))
     );
     assert_eq!(
-        eval_float_with_context_mut("asd(", &mut context),
-        Err(EvalexprError::UnmatchedLBrace)
+        eval(&format!("stats::sma({}, 6)", series)),
+        Err(EvalexprError::CustomMessage(
+            "stats::sma requires a window between 1 and the length 5 of the tuple, but got 6"
+                .into()
+        ))
     );
-
-    assert_eq!(eval_int("3"), Ok(3));
     assert_eq!(
-        eval_int("3.3"),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval(&format!("stats::sma({}, 0)", series)),
+        Err(EvalexprError::CustomMessage(
+            "stats::sma requires a window between 1 and the length 5 of the tuple, but got 0"
+                .into()
+        ))
     );
     assert_eq!(
-        eval_int("(,);."),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval(&format!("stats::ema({}, 0.5)", series)),
+        Ok(Value::from(vec![1.0, 1.5, 2.25, 3.125, 5.5625]))
     );
-    assert_eq!(eval_int_with_context("3", &context), Ok(3));
     assert_eq!(
-        eval_int_with_context("3.3", &context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval(&format!("stats::ema({}, 1)", series)),
+        Ok(Value::from(vec![1.0, 2.0, 3.0, 4.0, 8.0]))
     );
     assert_eq!(
-        eval_int_with_context("(,);.", &context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval(&format!("stats::ema({}, 0)", series)),
+        Err(EvalexprError::CustomMessage(
+            "stats::ema requires a smoothing factor greater than 0 and at most 1, but got 0".into()
+        ))
     );
-    assert_eq!(eval_int_with_context_mut("3", &mut context), Ok(3));
     assert_eq!(
-        eval_int_with_context_mut("3.3", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval(&format!("stats::ema({}, 1.5)", series)),
+        Err(EvalexprError::CustomMessage(
+            "stats::ema requires a smoothing factor greater than 0 and at most 1, but got 1.5"
+                .into()
+        ))
     );
+    // Finance
+    assert_eq!(eval("finance::percent_of(200, 15)"), Ok(Value::Float(30.0)));
     assert_eq!(
-        eval_int_with_context_mut("(,);.", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval("finance::percent_of(50.0, -10)"),
+        Ok(Value::Float(-5.0))
     );
-
-    assert_eq!(eval_number("3"), Ok(3.0));
+    assert_eq!(eval("finance::pct_change(80, 100)"), Ok(Value::Float(25.0)));
     assert_eq!(
-        eval_number("true"),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("finance::pct_change(100, 80)"),
+        Ok(Value::Float(-20.0))
     );
     assert_eq!(
-        eval_number("abc"),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("finance::pct_change(-50, -25)"),
+        Ok(Value::Float(-50.0))
     );
-    assert_eq!(eval_number_with_context("3.5", &context), Ok(3.5));
-    assert_eq!(eval_number_with_context("3", &context), Ok(3.0));
     assert_eq!(
-        eval_number_with_context("true", &context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("finance::pct_change(0, 5)"),
+        Err(EvalexprError::CustomMessage(
+            "finance::pct_change is undefined for an old value of zero".into()
+        ))
     );
+    assert_eq!(eval("finance::bps(20000, 25)"), Ok(Value::Float(50.0)));
+    assert_eq!(eval("finance::bps(100, 1.5)"), Ok(Value::Float(0.015)));
     assert_eq!(
-        eval_number_with_context("abc", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("finance::bps(100)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(100)))
     );
-    assert_eq!(eval_number_with_context_mut("3.5", &mut context), Ok(3.5));
-    assert_eq!(eval_number_with_context_mut("3", &mut context), Ok(3.0));
     assert_eq!(
-        eval_number_with_context_mut("true", &mut context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("finance::percent_of(100, \"5\")"),
+        Err(EvalexprError::expected_number(Value::from("5")))
     );
     assert_eq!(
-        eval_number_with_context_mut("abc", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("finance::compound(1000, 0.05, 2)"),
+        Ok(Value::Float(1000.0 * 1.05 * 1.05))
     );
-
-    assert_eq!(eval_boolean("true"), Ok(true));
     assert_eq!(
-        eval_boolean("4"),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("round(finance::compound(1000, 0.05, 10) * 100) / 100"),
+        Ok(Value::Float(1628.89))
     );
     assert_eq!(
-        eval_boolean("trueee"),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("finance::compound(1500, 0.1, 0)"),
+        Ok(Value::Float(1500.0))
     );
-    assert_eq!(eval_boolean_with_context("true", &context), Ok(true));
     assert_eq!(
-        eval_boolean_with_context("4", &context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("finance::compound(100, -0.5, 1)"),
+        Ok(Value::Float(50.0))
     );
     assert_eq!(
-        eval_boolean_with_context("trueee", &context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
+        eval("finance::compound(100, 0.05)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            3,
+            Value::from(vec![Value::from_int(100), Value::from_float(0.05)])
         ))
     );
-    assert_eq!(
-        eval_boolean_with_context_mut("true", &mut context),
-        Ok(true)
+    // Other
+    assert_eq!(eval("typeof(4.0, 3)"), Ok(Value::String("tuple".into())));
+    assert_eq!(eval("typeof(4.0)"), Ok(Value::String("float".into())));
+    assert_eq!(eval("typeof(4)"), Ok(Value::String("int".into())));
+    assert_eq!(eval("typeof(\"\")"), Ok(Value::String("string".into())));
+    assert_eq!(eval("typeof(true)"), Ok(Value::String("boolean".into())));
+    assert_eq!(eval("typeof()"), Ok(Value::String("empty".into())));
+    assert_eq!(eval("strict_eq(1, 1)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("strict_eq(1, 1.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("strict_eq(1.5, 1.5)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("strict_eq(1, 2)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("strict_eq(\"1\", 1)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("strict_eq(\"a\", \"a\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("strict_eq((), ())"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("strict_eq((1, \"a\"), (1, \"a\"))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("4", &mut context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("strict_eq((1, 2), (1, 2.0))"),
+        Ok(Value::Boolean(false))
     );
+    assert_eq!(eval("strict_eq({1}, (1))"), Ok(Value::Boolean(false)));
     assert_eq!(
-        eval_boolean_with_context_mut("trueee", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("strict_eq(1)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
-
-    assert_eq!(eval_tuple("3,3"), Ok(vec![Value::Int(3), Value::Int(3)]));
+    assert_eq!(eval("1 == 1.0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("(1, 2) == (1, 2)"), eval("strict_eq((1, 2), (1, 2))"));
+    assert_eq!(eval("min(4.0, 3)"), Ok(Value::Int(3)));
+    assert_eq!(eval("max(4.0, 3)"), Ok(Value::Float(4.0)));
+    assert_eq!(eval("min(4.0, 4)"), Ok(Value::Float(4.0)));
+    assert_eq!(eval("max(-1, -3, -2)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("min(2.5, 1.5, 3)"), Ok(Value::Float(1.5)));
+    // NaN propagates through min and max
     assert_eq!(
-        eval_tuple("33"),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("math::is_nan(min(0.0/0.0, 1.0))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        eval_tuple("3a3"),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("math::is_nan(min(1, 0.0/0.0))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        eval_tuple_with_context("3,3", &context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("math::is_nan(max(1.0, 0.0/0.0, 2))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        eval_tuple_with_context("33", &context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("math::is_nan(max(0.0/0.0, 0.0/0.0))"),
+        Ok(Value::Boolean(true))
     );
+    // but is ignored by math::fmin and math::fmax
+    assert_eq!(eval("math::fmin(0.0/0.0, 1.0)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::fmin(2, 0.0/0.0, 3.5)"), Ok(Value::Int(2)));
+    assert_eq!(eval("math::fmax(0.0/0.0, 1.0, 3)"), Ok(Value::Int(3)));
+    assert_eq!(eval("math::fmax(1.5, 0.0/0.0)"), Ok(Value::Float(1.5)));
     assert_eq!(
-        eval_tuple_with_context("3a3", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("math::is_nan(math::fmax(0.0/0.0, 0.0/0.0))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        eval_tuple_with_context_mut("3,3", &mut context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("math::fmin(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
     );
+    assert_eq!(eval("argmin((3, 1, 2))"), Ok(Value::Int(1)));
+    assert_eq!(eval("argmax((3, 1, 2))"), Ok(Value::Int(0)));
+    assert_eq!(eval("argmin(2, 0.5, 1)"), Ok(Value::Int(1)));
+    assert_eq!(eval("argmax(1, 4.5, 2, 4.5)"), Ok(Value::Int(1)));
+    assert_eq!(eval("argmin((5, 1, 3, 1))"), Ok(Value::Int(1)));
+    assert_eq!(eval("argmax((-2, -1, -1))"), Ok(Value::Int(1)));
+    assert_eq!(eval("argmax(0.0/0.0, 1, 2)"), Ok(Value::Int(2)));
+    assert_eq!(eval("argmin(3, 0.0/0.0, 1)"), Ok(Value::Int(2)));
+    assert_eq!(eval("argmin(0.0/0.0, 0.0/0.0)"), Ok(Value::Int(0)));
     assert_eq!(
-        eval_tuple_with_context_mut("33", &mut context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("argmax(9007199254740992, 9007199254740993)"),
+        Ok(Value::Int(1))
     );
     assert_eq!(
-        eval_tuple_with_context_mut("3a3", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("argmin(9007199254740993, 9007199254740992)"),
+        Ok(Value::Int(1))
     );
-
-    assert_eq!(eval_empty(""), Ok(EMPTY_VALUE));
-    assert_eq!(eval_empty("()"), Ok(EMPTY_VALUE));
     assert_eq!(
-        eval_empty("(,)"),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval("argmax(9007199254740993, 9007199254740992.0)"),
+        Ok(Value::Int(0))
     );
+    assert_eq!(eval("argmax(1, 0.0/0.0, 2)"), Ok(Value::Int(2)));
     assert_eq!(
-        eval_empty("xaq"),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context(
+            "min(empty)",
+            &context_map! { "empty" => Value::<DefaultNumericTypes>::Tuple(vec![]) }.unwrap()
+        ),
+        Err(EvalexprError::CustomMessage(
+            "min requires a non-empty tuple".into()
+        ))
     );
-    assert_eq!(eval_empty_with_context("", &context), Ok(EMPTY_VALUE));
-    assert_eq!(eval_empty_with_context("()", &context), Ok(EMPTY_VALUE));
     assert_eq!(
-        eval_empty_with_context("(,)", &context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval("argmin(())"),
+        Err(EvalexprError::expected_tuple(Value::Empty))
     );
     assert_eq!(
-        eval_empty_with_context("xaq", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context(
+            "argmax(empty)",
+            &context_map! { "empty" => Value::<DefaultNumericTypes>::Tuple(vec![]) }.unwrap()
+        ),
+        Err(EvalexprError::CustomMessage(
+            "argmax requires a non-empty tuple".into()
+        ))
     );
     assert_eq!(
-        eval_empty_with_context_mut("", &mut context),
-        Ok(EMPTY_VALUE)
-    );
-    assert_eq!(
-        eval_empty_with_context_mut("()", &mut context),
-        Ok(EMPTY_VALUE)
+        eval("argmax((1, \"a\"))"),
+        Err(EvalexprError::expected_number(Value::from("a")))
     );
+    assert_eq!(eval("len(\"foobar\")"), Ok(Value::Int(6)));
+    assert_eq!(eval("len(\"a\", \"b\")"), Ok(Value::Int(2)));
+    //Contians
     assert_eq!(
-        eval_empty_with_context_mut("(,)", &mut context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval("contains(1, 2, 3)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        ))
     );
     assert_eq!(
-        eval_empty_with_context_mut("xaq", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval("contains((\"foo\", \"bar\"), \"bar\")"),
+        Ok(Value::Boolean(true))
     );
-
-    // With detour via build_operator_tree
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("\"3.3\"")
-            .unwrap()
-            .eval_string(),
-        Ok("3.3".to_owned())
+        eval("contains((\"foo\", \"bar\"), \"buzz\")"),
+        Ok(Value::Boolean(false)),
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3.3")
-            .unwrap()
-            .eval_string(),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("contains(\"foo\", \"bar\")"),
+        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3..3")
-            .unwrap()
-            .eval_string(),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("contains((\"foo\", \"bar\", 123), 123)"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        build_operator_tree("string")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Ok("a string".to_owned())
+        eval("contains((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
+        Err(EvalexprError::type_error(
+            Value::Tuple(vec![
+                Value::String("buzz".into()),
+                Value::String("bazz".into())
+            ]),
+            vec![
+                ValueType::String,
+                ValueType::Int,
+                ValueType::Float,
+                ValueType::Boolean
+            ]
+        ))
     );
+    //Contains Any
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("contains_any(1, 2, 3)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        ))
     );
     assert_eq!(
-        build_operator_tree("3..3")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("contains_any((\"foo\", \"bar\"), (\"bar\", \"buzz\"))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        build_operator_tree("string")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Ok("a string".to_string())
+        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
+        Ok(Value::Boolean(false)),
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("contains_any((1,2,3), (3,4,5))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        build_operator_tree("3..3")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("contains_any((1,2,3), (4,5,6))"),
+        Ok(Value::Boolean(false))
     );
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3.3")
-            .unwrap()
-            .eval_float(),
-        Ok(3.3)
+        eval("contains_any((true, false, true, true), (false, false, false))"),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("33")
-            .unwrap()
-            .eval_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval("contains_any(\"foo\", \"bar\")"),
+        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("asd()")
-            .unwrap()
-            .eval_float(),
-        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+        eval("contains_any((\"foo\", \"bar\"), \"buzz\")"),
+        Err(EvalexprError::expected_tuple(Value::String("buzz".into())))
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Ok(3.3)
+        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", (1, 2, 3)))"),
+        Err(EvalexprError::type_error(
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
+            vec![
+                ValueType::String,
+                ValueType::Int,
+                ValueType::Float,
+                ValueType::Boolean
+            ]
+        ))
     );
+    // In
+    assert_eq!(eval("in(2, 1, 2, 3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("in(4, 1, 2, 3)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("in(\"b\", \"a\", \"b\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("in(\"c\", \"a\", \"b\")"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("in(1, \"1\", true)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("in(1.0, 1, 2)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("in(2, 1.0, 2.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("in(1.5, 1, 2)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("in((1, 2), 1, (1, 2))"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("in(1, 1)"), Ok(Value::Boolean(true)));
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval("in(1)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
+    // Tuple length
     assert_eq!(
-        build_operator_tree("asd")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+        eval("tuple::len((1, \"abc\", (2, 3)))"),
+        Ok(Value::from_int(3))
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Ok(3.3)
+        eval("tuple::len(())"),
+        Err(EvalexprError::expected_tuple(Value::Empty))
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
+        eval("tuple::len(\"abc\")"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::from("abc")
         })
     );
+    // Tuple count
+    assert_eq!(eval("tuple::count((1, 2, 3), 4)"), Ok(Value::from_int(0)));
+    assert_eq!(eval("tuple::count((1, 2, 3), 2)"), Ok(Value::from_int(1)));
     assert_eq!(
-        build_operator_tree("asd")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+        eval("tuple::count((1, \"a\", 1.0, true, 1), 1)"),
+        Ok(Value::from_int(3))
     );
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3")
-            .unwrap()
-            .eval_int(),
-        Ok(3)
+        eval("tuple::count((\"a\", \"b\", \"a\"), \"a\")"),
+        Ok(Value::from_int(2))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3.3")
-            .unwrap()
-            .eval_int(),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval("tuple::count(((1, 2), (1, 2), 1), (1, 2))"),
+        Ok(Value::from_int(2))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("(,);.")
-            .unwrap()
-            .eval_int(),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval("tuple::count(\"abc\", \"a\")"),
+        Err(EvalexprError::expected_tuple(Value::from("abc")))
     );
+    // With index
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Ok(3)
+        eval("with_index((1, 2, 3), 0, 4)"),
+        Ok(Value::from(vec![4, 2, 3]))
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval("with_index((1, 2, 3), 1, \"a\")"),
+        Ok(Value::from(vec![
+            Value::from_int(1),
+            Value::from("a"),
+            Value::from_int(3)
+        ]))
     );
     assert_eq!(
-        build_operator_tree("(,);.")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval("a = (1, 2, 3); b = with_index(a, 2, (4, 5)); (a, b)"),
+        Ok(Value::from(vec![
+            Value::from(vec![1, 2, 3]),
+            Value::from(vec![
+                Value::from_int(1),
+                Value::from_int(2),
+                Value::from(vec![4, 5])
+            ])
+        ]))
     );
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Ok(3)
+        eval("with_index((1, 2, 3), 3, 4)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval("with_index((1, 2, 3), -1, 4)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
     assert_eq!(
-        build_operator_tree("(,);.")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval("with_index(1, 0, 4)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
-
+    // Slice
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3")
-            .unwrap()
-            .eval_number(),
-        Ok(3.0)
+        eval("slice((1, 2, 3, 4, 5), 1, 3)"),
+        Ok(Value::from(vec![2, 3]))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("true")
-            .unwrap()
-            .eval_number(),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("slice((1, 2, 3, 4, 5), 2)"),
+        Ok(Value::from(vec![3, 4, 5]))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("abc")
-            .unwrap()
-            .eval_number(),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("slice((1, 2, 3, 4, 5), -2)"),
+        Ok(Value::from(vec![4, 5]))
     );
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Ok(3.0)
+        eval("slice((1, 2, 3, 4, 5), -4, -1)"),
+        Ok(Value::from(vec![2, 3, 4]))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("slice((1, 2, 3, 4, 5), -10, 2)"),
+        Ok(Value::from(vec![1, 2]))
     );
     assert_eq!(
-        build_operator_tree("abc")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("slice((1, 2, 3, 4, 5), 3, 100)"),
+        Ok(Value::from(vec![4, 5]))
     );
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Ok(3.0)
+        eval("slice((1, 2, 3, 4, 5), 10, 20)"),
+        Ok(Value::Tuple(vec![]))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("slice((1, 2, 3, 4, 5), 3, 1)"),
+        Ok(Value::Tuple(vec![]))
     );
     assert_eq!(
-        build_operator_tree("abc")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("slice((1, 2, 3), -9223372036854775807 - 1, 9223372036854775807)"),
+        Ok(Value::from(vec![1, 2, 3]))
     );
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("true")
-            .unwrap()
-            .eval_boolean(),
-        Ok(true)
+        eval("slice(\"abc\", 1)"),
+        Err(EvalexprError::expected_tuple(Value::from("abc")))
     );
+    assert!(eval("slice((1, 2, 3))").is_err());
+    // Zip
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("4")
-            .unwrap()
-            .eval_boolean(),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("zip((1, 2), (3, 4))"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from_int(1), Value::from_int(3)]),
+            Value::from(vec![Value::from_int(2), Value::from_int(4)])
+        ]))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("trueee")
-            .unwrap()
-            .eval_boolean(),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
+        eval("zip((\"a\", 1, true), (2.5, \"b\", (1, 2)))"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from("a"), Value::from_float(2.5)]),
+            Value::from(vec![Value::from_int(1), Value::from("b")]),
+            Value::from(vec![
+                Value::from(true),
+                Value::from(vec![Value::from_int(1), Value::from_int(2)])
+            ])
+        ]))
+    );
+    assert_eq!(
+        eval("zip((1, 2, 3), (4, 5))"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            3,
+            Value::from(vec![Value::from_int(4), Value::from_int(5)])
         ))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Ok(true)
+        eval("zip((1, 2), 3)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(3)))
     );
     assert_eq!(
-        build_operator_tree("4")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("zip(1, (2, 3))"),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
+    // Enumerate
     assert_eq!(
-        build_operator_tree("trueee")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("enumerate((\"a\", \"b\", 3))"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from_int(0), Value::from("a")]),
+            Value::from(vec![Value::from_int(1), Value::from("b")]),
+            Value::from(vec![Value::from_int(2), Value::from_int(3)])
+        ]))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Ok(true)
+        eval("enumerate(\"ab\")"),
+        Err(EvalexprError::expected_tuple(Value::from("ab")))
     );
+    // Flatten
     assert_eq!(
-        build_operator_tree("4")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("flatten((1, (2, (3, 4))))"),
+        Ok(Value::from(vec![
+            Value::from_int(1),
+            Value::from_int(2),
+            Value::from_int(3),
+            Value::from_int(4)
+        ]))
     );
     assert_eq!(
-        build_operator_tree("trueee")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("flatten(((((1, \"a\"), ((true, 2.5), 3)), 4), (5, 6)))"),
+        Ok(Value::from(vec![
+            Value::from_int(1),
+            Value::from("a"),
+            Value::from(true),
+            Value::from_float(2.5),
+            Value::from_int(3),
+            Value::from_int(4),
+            Value::from_int(5),
+            Value::from_int(6)
+        ]))
     );
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3,3")
-            .unwrap()
-            .eval_tuple(),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("flatten((1, 2))"),
+        Ok(Value::from(vec![Value::from_int(1), Value::from_int(2)]))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("33")
-            .unwrap()
-            .eval_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("flatten(1)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
+    // Chunk
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("3a3")
-            .unwrap()
-            .eval_tuple(),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("chunk((1, 2, 3, 4, 5), 2)"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from_int(1), Value::from_int(2)]),
+            Value::from(vec![Value::from_int(3), Value::from_int(4)]),
+            Value::from(vec![Value::from_int(5)])
+        ]))
     );
     assert_eq!(
-        build_operator_tree("3,3")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("chunk((1, 2, 3, 4), 2)"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from_int(1), Value::from_int(2)]),
+            Value::from(vec![Value::from_int(3), Value::from_int(4)])
+        ]))
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("chunk((1, 2, 3), 5)"),
+        Ok(Value::from(vec![Value::from(vec![
+            Value::from_int(1),
+            Value::from_int(2),
+            Value::from_int(3)
+        ])]))
     );
     assert_eq!(
-        build_operator_tree("3a3")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("chunk((1, 2, 3), 0)"),
+        Err(EvalexprError::CustomMessage(
+            "chunk requires a positive chunk size, but got 0".into()
+        ))
     );
     assert_eq!(
-        build_operator_tree("3,3")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("chunk((1, 2, 3), -1)"),
+        Err(EvalexprError::CustomMessage(
+            "chunk requires a positive chunk size, but got -1".into()
+        ))
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("chunk((1, 2, 3), 1.5)"),
+        Err(EvalexprError::expected_int(Value::from_float(1.5)))
     );
+    // All and any
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_function(
+            "is_positive".into(),
+            Function::new(|argument| Ok(Value::from(argument.as_number()? > 0.0))),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "is_even".into(),
+            Function::new(|argument| Ok(Value::from(argument.as_int()? % 2 == 0))),
+        )
+        .unwrap();
+    context
+        .set_value("empty".into(), Value::Tuple(vec![]))
+        .unwrap();
     assert_eq!(
-        build_operator_tree("3a3")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval_with_context("all((1, 2.5, 3), \"is_positive\")", &context),
+        Ok(Value::from(true))
     );
-
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("")
-            .unwrap()
-            .eval_empty(),
-        Ok(EMPTY_VALUE)
+        eval_with_context("all((1, -2, 3), \"is_positive\")", &context),
+        Ok(Value::from(false))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("()")
-            .unwrap()
-            .eval_empty(),
-        Ok(EMPTY_VALUE)
+        eval_with_context("any((1, 3, 4), \"is_even\")", &context),
+        Ok(Value::from(true))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("(,)")
-            .unwrap()
-            .eval_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval_with_context("any((1, 3, 5), \"is_even\")", &context),
+        Ok(Value::from(false))
     );
     assert_eq!(
-        build_operator_tree::<DefaultNumericTypes>("xaq")
-            .unwrap()
-            .eval_empty(),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context("all(empty, \"is_even\")", &context),
+        Ok(Value::from(true))
     );
     assert_eq!(
-        build_operator_tree("")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("any(empty, \"is_even\")", &context),
+        Ok(Value::from(false))
     );
+    // Evaluation stops at the first element deciding the result.
     assert_eq!(
-        build_operator_tree("()")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("any((2, \"a\"), \"is_even\")", &context),
+        Ok(Value::from(true))
     );
     assert_eq!(
-        build_operator_tree("(,)")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval_with_context("all((1, \"a\"), \"is_even\")", &context),
+        Ok(Value::from(false))
     );
     assert_eq!(
-        build_operator_tree("xaq")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context("all((1, 2), \"math::is_finite\")", &context),
+        Ok(Value::from(true))
     );
     assert_eq!(
-        build_operator_tree("")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("all((1, 2), \"math::abs\")", &context),
+        Err(EvalexprError::expected_boolean(Value::from_int(1)))
     );
     assert_eq!(
-        build_operator_tree("()")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("any((1, 2), \"unknown\")", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("unknown".into()))
     );
     assert_eq!(
-        build_operator_tree("(,)")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval_with_context("all((1, 2), is_even)", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("is_even".into()))
     );
+    // Map and filter
+    context
+        .set_function(
+            "double".into(),
+            Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
+        )
+        .unwrap();
     assert_eq!(
-        build_operator_tree("xaq")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context("map((1, 2, 3), \"double\")", &context),
+        Ok(Value::from(vec![
+            Value::from_int(2),
+            Value::from_int(4),
+            Value::from_int(6)
+        ]))
     );
-}
-
-#[test]
-fn test_whitespace() {
-    assert!(eval_boolean("2 < = 3").is_err());
-}
-
-#[test]
-fn test_assignment() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
     assert_eq!(
-        eval_empty_with_context_mut("int = 3", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map((-1, 2.5), \"math::abs\")", &context),
+        Ok(Value::from(vec![
+            Value::from_int(1),
+            Value::from_float(2.5)
+        ]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("float = 2.0", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(empty, \"double\")", &context),
+        Ok(Value::Tuple(vec![]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("tuple = (1,1)", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map((1, 2.5), \"double\")", &context),
+        Err(EvalexprError::expected_int(Value::from_float(2.5)))
     );
     assert_eq!(
-        eval_empty_with_context_mut("empty = ()", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("filter((1, 2, 3, 4), \"is_even\")", &context),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(4)]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("boolean = false", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context(
+            "filter(map((1, -2, 3), \"double\"), \"is_positive\")",
+            &context
+        ),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(6)]))
     );
-
-    assert_eq!(eval_int_with_context("int", &context), Ok(3));
-    assert_eq!(eval_float_with_context("float", &context), Ok(2.0));
     assert_eq!(
-        eval_tuple_with_context("tuple", &context),
-        Ok(vec![Value::from_int(1), Value::from_int(1)])
+        eval_with_context("filter(empty, \"is_even\")", &context),
+        Ok(Value::Tuple(vec![]))
     );
-    assert_eq!(eval_empty_with_context("empty", &context), Ok(EMPTY_VALUE));
-    assert_eq!(eval_boolean_with_context("boolean", &context), Ok(false));
-
     assert_eq!(
-        eval_empty_with_context_mut("b = a = 5", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("filter((1, 2), \"double\")", &context),
+        Err(EvalexprError::expected_boolean(Value::from_int(2)))
     );
-    assert_eq!(eval_empty_with_context("b", &context), Ok(EMPTY_VALUE));
-}
-
-#[test]
-fn test_expression_chaining() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
     assert_eq!(
-        eval_int_with_context_mut("a = 5; a = a + 2; a", &mut context),
-        Ok(7)
+        eval_with_context("map((1, 2), \"unknown\")", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("unknown".into()))
     );
-}
-
-#[test]
-fn test_strings() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    assert_eq!(eval("\"string\""), Ok(Value::from("string")));
+    // Lookup
     assert_eq!(
-        eval_with_context_mut("a = \"a string\"", &mut context),
-        Ok(Value::Empty)
+        eval("lookup(((\"a\", 1), (\"b\", 2), (\"b\", 3)), \"b\")"),
+        Ok(Value::from_int(2))
     );
     assert_eq!(
-        eval_boolean_with_context("a == \"a string\"", &context),
-        Ok(true)
+        eval("lookup(((1, \"one\"), (2, \"two\")), 1)"),
+        Ok(Value::from("one"))
     );
-    assert_eq!(eval("\"a\" + \"b\""), Ok(Value::from("ab")));
-    assert_eq!(eval("\"a\" > \"b\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"a\" < \"b\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" >= \"b\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"a\" <= \"b\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" >= \"a\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" <= \"a\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"xa\" > \"xb\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"xa\" < \"xb\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"{}\" != \"{}\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"{}\" == \"{}\""), Ok(Value::from(true)));
-}
-
-#[test]
-fn test_string_escaping() {
     assert_eq!(
-        eval("\"\\\"str\\\\ing\\\"\""),
-        Ok(Value::from("\"str\\ing\""))
+        eval("lookup(((\"a\", 1), (\"b\", 2)), \"c\")"),
+        Ok(Value::Empty)
     );
-}
-
-#[test]
-fn test_tuple_definitions() {
-    assert_eq!(eval_empty("()"), Ok(()));
-    assert_eq!(eval_int("(3)"), Ok(3));
     assert_eq!(
-        eval_tuple("(3, 4)"),
-        Ok(vec![Value::from_int(3), Value::from_int(4)])
+        eval("lookup(((\"a\", 1), (\"b\", 2, 3)), \"c\")"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::from(vec![
+                Value::from("b"),
+                Value::from_int(2),
+                Value::from_int(3)
+            ])
+        ))
     );
     assert_eq!(
-        eval_tuple("2, (5, 6)"),
-        Ok(vec![
-            Value::from_int(2),
-            Value::from(vec![Value::from_int(5), Value::from_int(6)])
-        ])
+        eval("lookup(\"a\", \"a\")"),
+        Err(EvalexprError::expected_tuple(Value::from("a")))
     );
+    assert_eq!(eval("clamp_int(100, 0, 255)"), Ok(Value::from_int(100)));
+    assert_eq!(eval("clamp_int(300, 0, 255)"), Ok(Value::from_int(255)));
+    assert_eq!(eval("clamp_int(-5, 0, 255)"), Ok(Value::from_int(0)));
+    assert_eq!(eval("clamp_int(127.9, 0, 255)"), Ok(Value::from_int(127)));
+    assert_eq!(eval("clamp_int(-0.9, -10, 10)"), Ok(Value::from_int(0)));
+    assert_eq!(eval("clamp_int(255.5, 0, 255)"), Ok(Value::from_int(255)));
+    assert_eq!(eval("clamp_int(1e300, 0, 255)"), Ok(Value::from_int(255)));
     assert_eq!(
-        eval_tuple("1, 2"),
-        Ok(vec![Value::from_int(1), Value::from_int(2)])
+        eval("clamp_int(5, 10, 0)"),
+        Err(EvalexprError::CustomMessage(
+            "clamp_int requires the lower bound 10 to be at most the upper bound 0".into()
+        ))
     );
     assert_eq!(
-        eval_tuple("1, 2, 3, 4"),
-        Ok(vec![
-            Value::from_int(1),
-            Value::from_int(2),
-            Value::from_int(3),
-            Value::from_int(4)
-        ])
+        eval("clamp_int(5, 0.0, 10)"),
+        Err(EvalexprError::expected_int(Value::from_float(0.0)))
     );
     assert_eq!(
-        eval_tuple("(1, 2, 3), 5, 6, (true, false, 0)"),
-        Ok(vec![
-            Value::from(vec![
-                Value::from_int(1),
-                Value::from_int(2),
-                Value::from_int(3)
-            ]),
-            Value::from_int(5),
-            Value::from_int(6),
-            Value::from(vec![
-                Value::from(true),
-                Value::from(false),
-                Value::from_int(0)
-            ])
-        ])
+        eval("clamp_int(\"5\", 0, 10)"),
+        Err(EvalexprError::expected_int(Value::from("5")))
     );
+    assert_eq!(eval("nearest(7, (1, 5, 10, 20))"), Ok(Value::from_int(5)));
+    assert_eq!(eval("nearest(8, (1, 5, 10, 20))"), Ok(Value::from_int(10)));
+    assert_eq!(eval("nearest(10, (1, 5, 10, 20))"), Ok(Value::from_int(10)));
     assert_eq!(
-        eval_tuple("1, (2)"),
-        Ok(vec![Value::from_int(1), Value::from_int(2)])
+        eval("nearest(0.3, (0, 0.25, 0.5))"),
+        Ok(Value::from_float(0.25))
     );
+    assert_eq!(eval("nearest(7.5, (5, 10))"), Ok(Value::from_int(5)));
+    assert_eq!(eval("nearest(7.5, (10, 5))"), Ok(Value::from_int(10)));
+    assert_eq!(eval("nearest(-100, (1, 5, 10))"), Ok(Value::from_int(1)));
     assert_eq!(
-        eval_tuple("1, ()"),
-        Ok(vec![Value::from_int(1), Value::from(())])
+        eval("nearest(1, (1, \"2\"))"),
+        Err(EvalexprError::expected_number(Value::from("2")))
     );
     assert_eq!(
-        eval_tuple("1, ((2))"),
-        Ok(vec![Value::from_int(1), Value::from_int(2)])
+        eval("nearest(1, 2)"),
+        Err(EvalexprError::expected_tuple(Value::from_int(2)))
     );
-}
-
-#[test]
-fn test_implicit_context() {
     assert_eq!(
-        eval("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
-        Ok(Value::from(true))
+        eval_with_context(
+            "nearest(1, options)",
+            &context_map! { "options" => Value::<DefaultNumericTypes>::Tuple(vec![]) }.unwrap()
+        ),
+        Err(EvalexprError::CustomMessage(
+            "nearest requires a non-empty tuple".into()
+        ))
     );
+    // String
     assert_eq!(
-        eval_boolean("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
-        Ok(true)
+        eval("str::to_lowercase(\"FOOBAR\")"),
+        Ok(Value::from("foobar"))
     );
-    assert_eq!(eval_int("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0));
     assert_eq!(
-        eval_float("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b + 0.5"),
-        Ok(0.5)
+        eval("str::to_uppercase(\"foobar\")"),
+        Ok(Value::from("FOOBAR"))
     );
-    assert_eq!(eval_number("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0.0));
-    assert_eq!(eval_empty("a = 2 + 4 * 2; b = -5 + 3 * 5;"), Ok(()));
     assert_eq!(
-        eval_tuple("a = 2 + 4 * 2; b = -5 + 3 * 5; a, b + 0.5"),
-        Ok(vec![Value::from_int(10), Value::from_float(10.5)])
+        eval("str::capitalize(\"hELLO wORLD\")"),
+        Ok(Value::from("Hello world"))
     );
+    assert_eq!(eval("str::capitalize(\"a\")"), Ok(Value::from("A")));
+    assert_eq!(eval("str::capitalize(\"\")"), Ok(Value::from("")));
+    assert_eq!(eval("str::capitalize(\"ärger\")"), Ok(Value::from("Ärger")));
+    assert_eq!(eval("str::capitalize(\"ßA\")"), Ok(Value::from("SSa")));
     assert_eq!(
-        eval_string("a = \"xyz\"; b = \"abc\"; c = a + b; c"),
-        Ok("xyzabc".to_string())
+        eval("str::capitalize(\" leading\")"),
+        Ok(Value::from(" leading"))
     );
-}
-
-#[test]
-fn test_operator_assignments() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    assert_eq!(eval_empty_with_context_mut("a = 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a += 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a -= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a *= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b = 5.0", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b /= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b %= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b ^= 5", &mut context), Ok(()));
-    assert_eq!(
-        eval_empty_with_context_mut("c = true", &mut context),
-        Ok(())
+    assert_eq!(
+        eval("str::title_case(\"the quick  bROWN\tfox\")"),
+        Ok(Value::from("The Quick  Brown\tFox"))
     );
     assert_eq!(
-        eval_empty_with_context_mut("c &&= false", &mut context),
-        Ok(())
+        eval("str::title_case(\"élan vital ÖL\")"),
+        Ok(Value::from("Élan Vital Öl"))
     );
+    assert_eq!(eval("str::title_case(\"  \")"), Ok(Value::from("  ")));
     assert_eq!(
-        eval_empty_with_context_mut("c ||= true", &mut context),
-        Ok(())
+        eval("str::title_case(1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
-
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    assert_eq!(eval_int_with_context_mut("a = 5; a", &mut context), Ok(5));
-    assert_eq!(eval_int_with_context_mut("a += 3; a", &mut context), Ok(8));
-    assert_eq!(eval_int_with_context_mut("a -= 5; a", &mut context), Ok(3));
-    assert_eq!(eval_int_with_context_mut("a *= 5; a", &mut context), Ok(15));
     assert_eq!(
-        eval_float_with_context_mut("b = 5.0; b", &mut context),
-        Ok(5.0)
+        eval("str::trim(\"  foo  bar \")"),
+        Ok(Value::from("foo  bar"))
     );
     assert_eq!(
-        eval_float_with_context_mut("b /= 2; b", &mut context),
-        Ok(2.5)
+        eval("str::trim_matches(\"\\\"hi, you!\\\"\", \"\\\"!\")"),
+        Ok(Value::from("hi, you"))
     );
     assert_eq!(
-        eval_float_with_context_mut("b %= 2; b", &mut context),
-        Ok(0.5)
+        eval("str::trim_matches(\"..-a.b-..\", \".-\")"),
+        Ok(Value::from("a.b"))
     );
     assert_eq!(
-        eval_float_with_context_mut("b ^= 2; b", &mut context),
-        Ok(0.25)
+        eval("str::trim_matches(\"  a  \", \"\")"),
+        Ok(Value::from("  a  "))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("c = true; c", &mut context),
-        Ok(true)
+        eval("str::trim_matches(\"xxx\", \"x\")"),
+        Ok(Value::from(""))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("c &&= false; c", &mut context),
-        Ok(false)
+        eval("str::trim_start_matches(\"**äb**\", \"*\")"),
+        Ok(Value::from("äb**"))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("c ||= true; c", &mut context),
-        Ok(true)
+        eval("str::trim_end_matches(\"**äb**\", \"*\")"),
+        Ok(Value::from("**äb"))
     );
-}
-
-#[test]
-fn test_type_errors_in_binary_operators() {
-    // Only addition supports incompatible types, all others work only on numbers or only on booleans.
-    // So only addition requires the more fancy error message.
     assert_eq!(
-        eval("4 + \"abc\""),
-        Err(EvalexprError::wrong_type_combination(
-            Operator::Add,
-            vec![ValueType::Int, ValueType::String]
-        ))
+        eval("str::trim_matches(\"a\")"),
+        Err(EvalexprError::expected_tuple(Value::from("a")))
     );
     assert_eq!(
-        eval("\"abc\" + 4"),
-        Err(EvalexprError::wrong_type_combination(
-            Operator::Add,
-            vec![ValueType::String, ValueType::Int]
-        ))
+        eval("str::trim_end_matches(\"a\", 1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
-}
-
-#[test]
-fn test_empty_context() {
-    let mut context = EmptyContext::<DefaultNumericTypes>::default();
-    assert_eq!(context.get_value("abc"), None);
     assert_eq!(
-        context.call_function("abc", &Value::Empty),
-        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
+        eval("str::from(\"a\")"),
+        Ok(Value::String(String::from("a")))
     );
+    assert_eq!(eval("str::from(1.0)"), Ok(Value::String(String::from("1"))));
     assert_eq!(
-        eval_with_context("max(1,3)", &context),
-        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
-            "max"
-        )))
+        eval("str::from(4.2)"),
+        Ok(Value::String(String::from("4.2")))
     );
-    assert_eq!(context.set_builtin_functions_disabled(true), Ok(()));
+    assert_eq!(eval("str::from(1)"), Ok(Value::String(String::from("1"))));
     assert_eq!(
-        context.set_builtin_functions_disabled(false),
-        Err(EvalexprError::BuiltinFunctionsCannotBeEnabled)
-    )
-}
-
-#[test]
-fn test_empty_context_with_builtin_functions() {
-    let mut context = EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default();
-    assert_eq!(context.get_value("abc"), None);
+        eval("str::from(true)"),
+        Ok(Value::String(String::from("true")))
+    );
     assert_eq!(
-        context.call_function("abc", &Value::Empty),
-        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
+        eval(r#"str::from((1, "foo", , false))"#),
+        Ok(Value::String(String::from(r#"(1, "foo", (), false)"#)))
     );
-    assert_eq!(eval_with_context("max(1,3)", &context), Ok(Value::Int(3)));
-    assert_eq!(context.set_builtin_functions_disabled(false), Ok(()));
     assert_eq!(
-        context.set_builtin_functions_disabled(true),
-        Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
+        eval("str::from(true)"),
+        Ok(Value::String(String::from("true")))
     );
-}
-
-#[test]
-fn test_hashmap_context_type_safety() {
-    let mut context: HashMapContext<DefaultNumericTypes> =
-        context_map! {"a" => int 5, "b" => float 5.0}.unwrap();
     assert_eq!(
-        eval_with_context_mut("a = 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::from(1, 2, 3)"),
+        Ok(Value::String(String::from("(1, 2, 3)")))
     );
+    assert_eq!(eval("str::from()"), Ok(Value::String(String::from("()"))));
     assert_eq!(
-        eval_with_context_mut("a = 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(4.0)
-        })
+        eval("str::substring(\"foobar\", 3)"),
+        Ok(Value::String(String::from("bar")))
     );
     assert_eq!(
-        eval_with_context_mut("a += 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(8.0)
-        })
+        eval("str::substring(\"foobar\", 3, 3)"),
+        Ok(Value::String(String::from("")))
     );
     assert_eq!(
-        eval_with_context_mut("a -= 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(0.0)
-        })
+        eval("str::substring(\"foobar\", 3, 4)"),
+        Ok(Value::String(String::from("b")))
     );
+    assert!(eval("str::substring()").is_err());
+    assert!(eval("str::substring(\"foobar\")").is_err());
+    assert!(eval("str::substring(\"foobar\", 2, 1)").is_err());
+    assert!(eval("str::substring(\"foobar\", 99999)").is_err());
+    assert!(eval("str::substring(\"foobar\", -1)").is_err());
+    assert!(eval("str::substring(\"foobar\", 0, -1)").is_err());
+    assert!(eval("str::substring(\"foobar\", 0, 1, 1)").is_err());
     assert_eq!(
-        eval_with_context_mut("a *= 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(16.0)
-        })
+        eval("str::substring(\"äb\", 1)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
+    assert_eq!(eval("str::substring(\"äb\", 2)"), Ok(Value::from("b")));
+    assert_eq!(eval("substring(\"foobar\", 1, 3)"), Ok(Value::from("oob")));
+    assert_eq!(eval("substring(\"foobar\", 3, 10)"), Ok(Value::from("bar")));
+    assert_eq!(eval("substring(\"foobar\", 6, 1)"), Ok(Value::from("")));
+    assert_eq!(eval("substring(\"foobar\", 2, -1)"), Ok(Value::from("")));
+    assert_eq!(eval("substring(\"häßlich\", 1, 3)"), Ok(Value::from("äßl")));
     assert_eq!(
-        eval_with_context_mut("a /= 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(1.0)
-        })
+        eval("substring(\"foobar\", 7, 1)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
     assert_eq!(
-        eval_with_context_mut("a %= 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(0.0)
-        })
+        eval("substring(\"foobar\", -1, 1)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
     assert_eq!(
-        eval_with_context_mut("a ^= 4.0", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(256.0)
-        })
+        eval("substring(\"foobar\", 1)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            3,
+            Value::from(vec![Value::from("foobar"), Value::from_int(1)])
+        ))
     );
-
+    assert_eq!(eval("str::char_at(\"häß\", 1)"), Ok(Value::from("ä")));
+    assert_eq!(eval("str::char_at(\"häß\", 2)"), Ok(Value::from("ß")));
     assert_eq!(
-        eval_with_context_mut("b = 4.0", &mut context),
-        Ok(Value::Empty)
+        eval("str::char_at(\"häß\", 3)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
     assert_eq!(
-        eval_with_context_mut("b = 4", &mut context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(4)
-        })
+        eval("str::char_at(\"häß\", -1)"),
+        Err(EvalexprError::OutOfBoundsAccess)
     );
+    assert_eq!(eval("str::len(\"häß\")"), Ok(Value::from_int(3)));
+    assert_eq!(eval("str::len(\"\")"), Ok(Value::from_int(0)));
     assert_eq!(
-        eval_with_context_mut("b += 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::len((1, 2))"),
+        Err(EvalexprError::expected_string(Value::from(vec![
+            Value::from_int(1),
+            Value::from_int(2)
+        ])))
     );
     assert_eq!(
-        eval_with_context_mut("b -= 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::split_once(\"key=value=1\", \"=\")"),
+        Ok(Value::from(vec!["key", "value=1"]))
     );
     assert_eq!(
-        eval_with_context_mut("b *= 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::split_once(\"a, b, c\", \", \")"),
+        Ok(Value::from(vec!["a", "b, c"]))
     );
     assert_eq!(
-        eval_with_context_mut("b /= 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::split_once(\"=value\", \"=\")"),
+        Ok(Value::from(vec!["", "value"]))
     );
+    assert_eq!(eval("str::split_once(\"key\", \"=\")"), Ok(Value::Empty));
     assert_eq!(
-        eval_with_context_mut("b %= 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::split_once(\"key\", 1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
+    assert!(eval("str::split_once(\"key\")").is_err());
+    assert_eq!(eval("str::count(\"abc\", \"x\")"), Ok(Value::from_int(0)));
+    assert_eq!(eval("str::count(\"abc\", \"b\")"), Ok(Value::from_int(1)));
     assert_eq!(
-        eval_with_context_mut("b ^= 4", &mut context),
-        Ok(Value::Empty)
+        eval("str::count(\"a,b,,c\", \",\")"),
+        Ok(Value::from_int(3))
     );
-}
-
-#[test]
-fn test_hashmap_context_clone_debug() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    // this variable is captured by the function
-    let three = 3;
-    context
-        .set_function(
-            "mult_3".into(),
-            Function::new(move |argument| {
-                if let Value::Int(int) = argument {
-                    Ok(Value::Int(int * three))
-                } else if let Value::Float(float) = argument {
-                    Ok(Value::Float(
-                        float * three as <DefaultNumericTypes as EvalexprNumericTypes>::Float,
-                    ))
-                } else {
-                    Err(EvalexprError::expected_number(argument.clone()))
-                }
-            }),
-        )
-        .unwrap();
-
-    let four = 4;
-    context
-        .set_function(
-            "function_four".into(),
-            Function::new(move |_| Ok(Value::Int(four))),
-        )
-        .unwrap();
-    context
-        .set_value("variable_five".into(), Value::from_int(5))
-        .unwrap();
-    let context = context;
-    #[allow(clippy::redundant_clone)]
-    let cloned_context = context.clone();
-
-    assert_eq!(format!("{:?}", &context), format!("{:?}", &cloned_context));
+    assert_eq!(eval("str::count(\"aaaa\", \"aa\")"), Ok(Value::from_int(2)));
+    assert_eq!(eval("str::count(\"aaa\", \"aa\")"), Ok(Value::from_int(1)));
+    assert_eq!(eval("str::count(\"\", \"a\")"), Ok(Value::from_int(0)));
     assert_eq!(
-        cloned_context.get_value("variable_five"),
-        Some(&Value::from_int(5))
+        eval("str::count(\"abc\", \"\")"),
+        Err(EvalexprError::CustomMessage(
+            "str::count requires a non-empty needle".into()
+        ))
     );
     assert_eq!(
-        eval_with_context("mult_3 2", &cloned_context),
-        Ok(Value::Int(6))
+        eval("str::count(\"abc\", 1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
+    assert_eq!(eval("str::repeat(\"ab\", 3)"), Ok(Value::from("ababab")));
+    assert_eq!(eval("str::repeat(\"ab\", 1)"), Ok(Value::from("ab")));
+    assert_eq!(eval("str::repeat(\"ab\", 0)"), Ok(Value::from("")));
+    assert_eq!(eval("str::repeat(\"\", 5)"), Ok(Value::from("")));
     assert_eq!(
-        eval_with_context("mult_3(3)", &cloned_context),
-        Ok(Value::Int(9))
+        eval("str::repeat(\"ab\", -1)"),
+        Err(EvalexprError::IntIntoUsize { int: -1 })
     );
     assert_eq!(
-        eval_with_context("mult_3(function_four())", &cloned_context),
-        Ok(Value::Int(12))
+        eval("str::repeat(\"ab\", 9223372036854775807)"),
+        Err(EvalexprError::CustomMessage(
+            "str::repeat cannot construct strings longer than 16777216 bytes".into()
+        ))
     );
-}
-
-#[test]
-fn test_error_constructors() {
     assert_eq!(
-        eval("a = true + \"4\""),
-        Err(EvalexprError::ExpectedNumberOrString {
-            actual: Value::Boolean(true)
-        })
+        eval("str::repeat(\"ab\", 8388609)"),
+        Err(EvalexprError::CustomMessage(
+            "str::repeat cannot construct strings longer than 16777216 bytes".into()
+        ))
     );
     assert_eq!(
-        eval("a = true && \"4\""),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::from("4")
-        })
+        eval("str::len(str::repeat(\"ab\", 8388608))"),
+        Ok(Value::from_int(16777216))
     );
     assert_eq!(
-        eval_tuple("4"),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(4)
-        })
+        eval("str::repeat(\"\", 9223372036854775807)"),
+        Ok(Value::from(""))
     );
     assert_eq!(
-        Value::Tuple(vec![Value::<DefaultNumericTypes>::Int(4), Value::Int(5)])
-            .as_fixed_len_tuple(3),
-        Err(EvalexprError::ExpectedFixedLengthTuple {
-            expected_length: 3,
-            actual: Value::Tuple(vec![Value::Int(4), Value::Int(5)])
-        })
+        eval("str::repeat(\"ab\", 2.0)"),
+        Err(EvalexprError::expected_int(Value::from_float(2.0)))
     );
     assert_eq!(
-        eval_empty("4"),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Int(4)
-        })
+        eval("str::indent(\"a\n  b\n\nc\", 2)"),
+        Ok(Value::from("  a\n    b\n\n  c"))
     );
+    assert_eq!(eval("str::indent(\"a\", 0)"), Ok(Value::from("a")));
+    assert_eq!(eval("str::indent(\"\", 4)"), Ok(Value::from("")));
     assert_eq!(
-        eval("&"),
-        Err(EvalexprError::UnmatchedPartialToken {
-            first: PartialToken::Ampersand,
-            second: None
-        })
+        eval("str::indent(\"a\", -1)"),
+        Err(EvalexprError::IntIntoUsize { int: -1 })
     );
-
     assert_eq!(
-        expect_function_argument_amount::<DefaultNumericTypes>(2, 2),
-        Ok(())
+        eval("str::indent(\"a\", 9223372036854775807)"),
+        Err(EvalexprError::CustomMessage(
+            "str::indent cannot construct strings longer than 16777216 bytes".into()
+        ))
     );
     assert_eq!(
-        expect_function_argument_amount::<DefaultNumericTypes>(2, 3),
-        Err(EvalexprError::WrongFunctionArgumentAmount {
-            expected: 3..=3,
-            actual: 2
-        })
+        eval("str::indent(str::repeat(\"a\n\", 10), 2000000)"),
+        Err(EvalexprError::CustomMessage(
+            "str::indent cannot construct strings longer than 16777216 bytes".into()
+        ))
     );
-}
-
-#[test]
-fn test_iterators() {
-    let tree =
-        build_operator_tree::<DefaultNumericTypes>("writevar = 5 + 3 + fun(4) + var").unwrap();
-    let mut iter = tree.iter_identifiers();
-    assert_eq!(iter.next(), Some("writevar"));
-    assert_eq!(iter.next(), Some("fun"));
-    assert_eq!(iter.next(), Some("var"));
-    assert_eq!(iter.next(), None);
-
-    let mut iter = tree.iter_variable_identifiers();
-    assert_eq!(iter.next(), Some("writevar"));
-    assert_eq!(iter.next(), Some("var"));
-    assert_eq!(iter.next(), None);
-
-    let mut iter = tree.iter_read_variable_identifiers();
-    assert_eq!(iter.next(), Some("var"));
-    assert_eq!(iter.next(), None);
-
-    let mut iter = tree.iter_write_variable_identifiers();
-    assert_eq!(iter.next(), Some("writevar"));
-    assert_eq!(iter.next(), None);
-
-    let mut iter = tree.iter_function_identifiers();
-    assert_eq!(iter.next(), Some("fun"));
-    assert_eq!(iter.next(), None);
-}
-
-#[test]
-fn test_same_operator_chains() {
-    #![allow(clippy::eq_op)]
     assert_eq!(
-        eval("3.0 / 3.0 / 3.0 / 3.0"),
-        Ok(Value::from_float(3.0 / 3.0 / 3.0 / 3.0))
+        eval("str::wrap(\"the quick brown fox jumps over the lazy dog\", 10)"),
+        Ok(Value::from(
+            "the quick\nbrown fox\njumps over\nthe lazy\ndog"
+        ))
     );
     assert_eq!(
-        eval("3.0 - 3.0 - 3.0 - 3.0"),
-        Ok(Value::from_float(3.0 - 3.0 - 3.0 - 3.0))
-    );
-}
-
-#[test]
-fn test_long_expression_i89() {
-    let tree = build_operator_tree::<DefaultNumericTypes>(
-        "x*0.2*5/4+x*2*4*1*1*1*1*1*1*1+7*math::sin(y)-z/math::sin(3.0/2.0/(1-x*4*1*1*1*1))",
-    )
-    .unwrap();
-    let x = 0.0;
-    let y: <DefaultNumericTypes as EvalexprNumericTypes>::Float = 3.0;
-    let z = 4.0;
-    let context = context_map! {
-        "x" => float 0.0,
-        "y" => float 3.0,
-        "z" => float 4.0
+        eval("str::wrap(\"first line\nsecond  line is longer\", 11)"),
+        Ok(Value::from("first line\nsecond line\nis longer"))
+    );
+    assert_eq!(
+        eval("str::wrap(\"a supercalifragilistic word\", 6)"),
+        Ok(Value::from("a\nsupercalifragilistic\nword"))
+    );
+    assert_eq!(eval("str::wrap(\"short\", 80)"), Ok(Value::from("short")));
+    assert_eq!(eval("str::ljust(\"ab\", 5)"), Ok(Value::from("ab   ")));
+    assert_eq!(eval("str::rjust(\"ab\", 5)"), Ok(Value::from("   ab")));
+    assert_eq!(
+        eval("str::rjust(str::from(42), 4)"),
+        Ok(Value::from("  42"))
+    );
+    assert_eq!(eval("str::center(\"ab\", 6)"), Ok(Value::from("  ab  ")));
+    assert_eq!(eval("str::center(\"ab\", 5)"), Ok(Value::from(" ab  ")));
+    assert_eq!(eval("str::center(\"abc\", 4)"), Ok(Value::from("abc ")));
+    assert_eq!(eval("str::center(\"äö\", 4)"), Ok(Value::from(" äö ")));
+    assert_eq!(eval("str::ljust(\"äö\", 3)"), Ok(Value::from("äö ")));
+    assert_eq!(
+        eval("str::rjust(\"a\", 65536)"),
+        Ok(Value::from(format!("{}a", " ".repeat(65535))))
+    );
+    assert_eq!(
+        eval("str::center(\"a\", 65537)"),
+        Ok(Value::from(format!("{0}a{0}", " ".repeat(32768))))
+    );
+    for function in ["str::ljust", "str::rjust", "str::center"] {
+        assert_eq!(
+            eval(&format!("{function}(\"abc\", 2)")),
+            Ok(Value::from("abc"))
+        );
+        assert_eq!(
+            eval(&format!("{function}(\"abc\", 3)")),
+            Ok(Value::from("abc"))
+        );
+        assert_eq!(
+            eval(&format!("{function}(\"abc\", -1)")),
+            Err(EvalexprError::IntIntoUsize { int: -1 })
+        );
+        assert_eq!(
+            eval(&format!("str::len({function}(\"abc\", 70000))")),
+            Ok(Value::from_int(70000))
+        );
+        assert_eq!(
+            eval(&format!("{function}(\"abc\", 9223372036854775807)")),
+            Err(EvalexprError::CustomMessage(format!(
+                "{function} cannot construct strings longer than 16777216 bytes"
+            )))
+        );
     }
-    .unwrap();
-    let expected = x * 0.2 * 5.0 / 4.0
-        + x * 2.0 * 4.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0
-        + 7.0 * y.sin()
-        - z / (3.0 / 2.0 / (1.0 - x * 4.0 * 1.0 * 1.0 * 1.0 * 1.0)).sin();
-    let actual: <DefaultNumericTypes as EvalexprNumericTypes>::Float =
-        tree.eval_float_with_context(&context).unwrap();
-    assert!(
-        (expected - actual).abs() < expected.abs().min(actual.abs()) * 1e-12,
-        "expected: {}, actual: {}",
-        expected,
-        actual
+    assert_eq!(eval("str::hex(255)"), Ok(Value::from("ff")));
+    assert_eq!(eval("str::hex(255, true)"), Ok(Value::from("0xff")));
+    assert_eq!(eval("str::hex(0)"), Ok(Value::from("0")));
+    assert_eq!(eval("str::hex(-30, true)"), Ok(Value::from("-0x1e")));
+    assert_eq!(
+        eval("str::hex(-9223372036854775807 - 1)"),
+        Ok(Value::from("-8000000000000000"))
     );
-}
-
-#[test]
-fn test_value_type() {
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::String(String::new())),
-        ValueType::String
+        eval("str::hex(9223372036854775807)"),
+        Ok(Value::from("7fffffffffffffff"))
     );
+    assert_eq!(eval("str::oct(8)"), Ok(Value::from("10")));
+    assert_eq!(eval("str::oct(-511, true)"), Ok(Value::from("-0o777")));
+    assert_eq!(eval("str::bin(10)"), Ok(Value::from("1010")));
+    assert_eq!(eval("str::bin(5, false)"), Ok(Value::from("101")));
+    assert_eq!(eval("str::bin(-5, true)"), Ok(Value::from("-0b101")));
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::Float(0.0)),
-        ValueType::Float
+        eval("str::hex(2.5)"),
+        Err(EvalexprError::expected_int(Value::from_float(2.5)))
     );
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::Int(0)),
-        ValueType::Int
+        eval("str::bin(5, 1)"),
+        Err(EvalexprError::expected_boolean(Value::from_int(1)))
     );
+    // String similarity
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::Boolean(true)),
-        ValueType::Boolean
+        eval("str::similarity(\"evalexpr\", \"evalexpr\")"),
+        Ok(Value::from_float(1.0))
     );
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::Tuple(Vec::new())),
-        ValueType::Tuple
+        eval("str::similarity(\"abc\", \"xyz\")"),
+        Ok(Value::from_float(0.0))
     );
     assert_eq!(
-        ValueType::from(&Value::<DefaultNumericTypes>::Empty),
-        ValueType::Empty
+        eval("str::similarity(\"kitten\", \"sitting\")"),
+        Ok(Value::from_float(4.0 / 7.0))
     );
-
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::String(String::new())),
-        ValueType::String
+        eval("str::similarity(\"abcd\", \"abce\")"),
+        Ok(Value::from_float(0.75))
     );
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::Float(0.0)),
-        ValueType::Float
+        eval("str::similarity(\"\", \"\")"),
+        Ok(Value::from_float(1.0))
     );
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::Int(0)),
-        ValueType::Int
+        eval("str::similarity(\"\", \"ab\")"),
+        Ok(Value::from_float(0.0))
     );
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::Boolean(true)),
-        ValueType::Boolean
+        eval("str::similarity(\"abc\", 1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
+    assert_eq!(eval("str::si(1500)"), Ok(Value::from("1.5k")));
+    assert_eq!(eval("str::si(2000000)"), Ok(Value::from("2M")));
+    assert_eq!(eval("str::si(999)"), Ok(Value::from("999")));
+    assert_eq!(eval("str::si(1000)"), Ok(Value::from("1k")));
+    assert_eq!(eval("str::si(999999)"), Ok(Value::from("1M")));
+    assert_eq!(eval("str::si(999999, 3)"), Ok(Value::from("999.999k")));
+    assert_eq!(eval("str::si(1234567, 1)"), Ok(Value::from("1.2M")));
+    assert_eq!(eval("str::si(3.5e9)"), Ok(Value::from("3.5G")));
+    assert_eq!(eval("str::si(1.5e20)"), Ok(Value::from("150E")));
+    assert_eq!(eval("str::si(-1500)"), Ok(Value::from("-1.5k")));
+    assert_eq!(eval("str::si(12.345, 0)"), Ok(Value::from("12")));
+    assert_eq!(eval("str::si(100, 0)"), Ok(Value::from("100")));
+    assert_eq!(eval("str::si(0)"), Ok(Value::from("0")));
+    assert_eq!(eval("str::si(0.5)"), Ok(Value::from("500m")));
+    assert_eq!(eval("str::si(0.0000025)"), Ok(Value::from("2.5µ")));
+    assert_eq!(eval("str::si(0.000999999)"), Ok(Value::from("1m")));
+    assert_eq!(eval("str::si(3e-9)"), Ok(Value::from("3n")));
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::Tuple(Vec::new())),
-        ValueType::Tuple
+        eval("str::si(\"1k\")"),
+        Err(EvalexprError::expected_number(Value::from("1k")))
     );
     assert_eq!(
-        ValueType::from(&mut Value::<DefaultNumericTypes>::Empty),
-        ValueType::Empty
+        eval("str::si(1, -1)"),
+        Err(EvalexprError::IntIntoUsize { int: -1 })
     );
-
-    assert!(!Value::<DefaultNumericTypes>::String(String::new()).is_number());
-    assert!(Value::<DefaultNumericTypes>::Float(0.0).is_number());
-    assert!(Value::<DefaultNumericTypes>::Int(0).is_number());
-    assert!(!Value::<DefaultNumericTypes>::Boolean(true).is_number());
-    assert!(!Value::<DefaultNumericTypes>::Tuple(Vec::new()).is_number());
-    assert!(!Value::<DefaultNumericTypes>::Empty.is_number());
-
-    assert!(!Value::<DefaultNumericTypes>::String(String::new()).is_empty());
-    assert!(!Value::<DefaultNumericTypes>::Float(0.0).is_empty());
-    assert!(!Value::<DefaultNumericTypes>::Int(0).is_empty());
-    assert!(!Value::<DefaultNumericTypes>::Boolean(true).is_empty());
-    assert!(!Value::<DefaultNumericTypes>::Tuple(Vec::new()).is_empty());
-    assert!(Value::<DefaultNumericTypes>::Empty.is_empty());
-
+    assert_eq!(eval("str::si(1.5, 17)"), Ok(Value::from("1.5")));
     assert_eq!(
-        Value::<DefaultNumericTypes>::String(String::new()).as_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::String(String::new())
-        })
+        eval("str::si(1.5, 400)"),
+        Err(EvalexprError::CustomMessage(
+            "str::si supports at most 17 fractional digits, but got 400".into()
+        ))
     );
-    assert_eq!(Value::<DefaultNumericTypes>::Float(0.0).as_float(), Ok(0.0));
+    assert_eq!(eval("str::percent(0.1234, 2)"), Ok(Value::from("12.34%")));
+    assert_eq!(eval("str::percent(0.5, 0)"), Ok(Value::from("50%")));
+    assert_eq!(eval("str::percent(0.1234, 0)"), Ok(Value::from("12%")));
+    assert_eq!(eval("str::percent(1, 1)"), Ok(Value::from("100.0%")));
+    assert_eq!(eval("str::percent(0, 2)"), Ok(Value::from("0.00%")));
+    assert_eq!(eval("str::percent(-0.25, 1)"), Ok(Value::from("-25.0%")));
+    assert_eq!(eval("str::percent(1.5, 0)"), Ok(Value::from("150%")));
     assert_eq!(
-        Value::<DefaultNumericTypes>::Int(0).as_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(0)
-        })
+        eval("str::percent(\"1\", 2)"),
+        Err(EvalexprError::expected_number(Value::from("1")))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Boolean(true).as_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Boolean(true)
-        })
+        eval("str::percent(0.5, -1)"),
+        Err(EvalexprError::IntIntoUsize { int: -1 })
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Tuple(Vec::new())
-        })
+        eval("str::percent(0.5, 17)"),
+        Ok(Value::from("50.00000000000000000%"))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Empty.as_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Empty
-        })
+        eval("str::percent(0.5, 70000)"),
+        Err(EvalexprError::CustomMessage(
+            "str::percent supports at most 17 fractional digits, but got 70000".into()
+        ))
     );
-
     assert_eq!(
-        Value::<DefaultNumericTypes>::String(String::new()).as_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::String(String::new())
-        })
+        eval("str::percent(0.5)"),
+        Err(EvalexprError::expected_tuple(Value::from_float(0.5)))
     );
+    // URL encoding
     assert_eq!(
-        Value::<DefaultNumericTypes>::Float(0.0).as_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Float(0.0)
-        })
+        eval("url::encode(\"hello world\")"),
+        Ok(Value::from("hello%20world"))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Int(0).as_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(0)
-        })
+        eval("url::encode(\"a=1&b=2/3?x#y+z\")"),
+        Ok(Value::from("a%3D1%26b%3D2%2F3%3Fx%23y%2Bz"))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Boolean(true).as_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Boolean(true)
-        })
+        eval("url::encode(\"Az09-._~\")"),
+        Ok(Value::from("Az09-._~"))
     );
+    assert_eq!(eval("url::encode(\"é\")"), Ok(Value::from("%C3%A9")));
     assert_eq!(
-        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_tuple(),
-        Ok(Vec::new())
+        eval("url::decode(\"hello%20world%2f%3F+\")"),
+        Ok(Value::from("hello world/?+"))
     );
+    assert_eq!(eval("url::decode(\"%C3%A9\")"), Ok(Value::from("é")));
     assert_eq!(
-        Value::<DefaultNumericTypes>::Empty.as_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Empty
-        })
+        eval("url::decode(url::encode(\"key=värde & more/ü\"))"),
+        Ok(Value::from("key=värde & more/ü"))
     );
-
     assert_eq!(
-        Value::<DefaultNumericTypes>::String(String::new()).as_fixed_len_tuple(0),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::String(String::new())
-        })
+        eval("url::decode(\"100%\")"),
+        Err(EvalexprError::CustomMessage(
+            "url::decode found a malformed percent-encoding at byte 3 of \"100%\"".into()
+        ))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Float(0.0).as_fixed_len_tuple(0),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Float(0.0)
-        })
+        eval("url::decode(\"%2g\")"),
+        Err(EvalexprError::CustomMessage(
+            "url::decode found a malformed percent-encoding at byte 0 of \"%2g\"".into()
+        ))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Int(0).as_fixed_len_tuple(0),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(0)
-        })
+        eval("url::decode(\"%FF\")"),
+        Err(EvalexprError::CustomMessage(
+            "invalid utf-8 sequence of 1 bytes from index 0".into()
+        ))
     );
+    // CRC-32
+    assert_eq!(eval("hash::crc32(\"\")"), Ok(Value::from_int(0)));
+    assert_eq!(eval("hash::crc32(\"a\")"), Ok(Value::from_int(0xE8B7BE43)));
     assert_eq!(
-        Value::<DefaultNumericTypes>::Boolean(true).as_fixed_len_tuple(0),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Boolean(true)
-        })
+        eval("hash::crc32(\"123456789\")"),
+        Ok(Value::from_int(0xCBF43926))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_fixed_len_tuple(0),
-        Ok(Vec::new())
+        eval("hash::crc32(\"The quick brown fox jumps over the lazy dog\")"),
+        Ok(Value::from_int(0x414FA339))
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Empty.as_fixed_len_tuple(0),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Empty
-        })
+        eval("str::hex(hash::crc32(\"123456789\"))"),
+        Ok(Value::from("cbf43926"))
     );
-
     assert_eq!(
-        Value::<DefaultNumericTypes>::String(String::new()).as_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::String(String::new())
-        })
+        eval("hash::crc32(1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
     );
+    // Durations
     assert_eq!(
-        Value::<DefaultNumericTypes>::Float(0.0).as_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Float(0.0)
+        eval("duration::parse(\"1h30m\")"),
+        Ok(Value::from_int(5400))
+    );
+    assert_eq!(
+        eval("duration::parse(\"2d3h4m5s\")"),
+        Ok(Value::from_int(183_845))
+    );
+    assert_eq!(eval("duration::parse(\"45s\")"), Ok(Value::from_int(45)));
+    assert_eq!(eval("duration::parse(\"90m\")"), Ok(Value::from_int(5400)));
+    assert_eq!(eval("duration::parse(\"1d\")"), Ok(Value::from_int(86_400)));
+    assert_eq!(eval("duration::parse(\"0s\")"), Ok(Value::from_int(0)));
+    for malformed in ["", "1", "h", "1h30", "1x", "1.5h", "-1h", "1h 30m", "1H"] {
+        assert_eq!(
+            eval(&format!("duration::parse({:?})", malformed)),
+            Err(EvalexprError::CustomMessage(format!(
+                "duration::parse expected a duration like \"1h30m\", but got {:?}",
+                malformed
+            )))
+        );
+    }
+    assert!(eval("duration::parse(\"99999999999999999d\")").is_err());
+    assert_eq!(
+        eval("duration::parse(90)"),
+        Err(EvalexprError::expected_string(Value::from_int(90)))
+    );
+    // Timestamps
+    assert_eq!(
+        eval("time::in_range(1500, 1000, 2000)"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("time::in_range(1000, 1000, 2000)"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("time::in_range(2000, 1000, 2000)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("time::in_range(999, 1000, 2000)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("time::in_range(1500, 2000, 1000)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("time::in_range(1500.0, 1000, 2000)"),
+        Err(EvalexprError::expected_int(Value::from_float(1500.0)))
+    );
+    assert_eq!(
+        eval("time::add_days(1700000000, 1)"),
+        Ok(Value::from_int(1_700_086_400))
+    );
+    assert_eq!(
+        eval("time::add_days(1700000000, -2)"),
+        Ok(Value::from_int(1_699_827_200))
+    );
+    assert_eq!(eval("time::add_days(0, 0)"), Ok(Value::from_int(0)));
+    assert!(matches!(
+        eval("time::add_days(0, 9223372036854775807)"),
+        Err(EvalexprError::MultiplicationError { .. })
+    ));
+    assert_eq!(
+        eval("time::add_days(0, 1.5)"),
+        Err(EvalexprError::expected_int(Value::from_float(1.5)))
+    );
+    // Base64
+    assert_eq!(eval("base64::encode(\"\")"), Ok(Value::from("")));
+    assert_eq!(eval("base64::encode(\"f\")"), Ok(Value::from("Zg==")));
+    assert_eq!(eval("base64::encode(\"fo\")"), Ok(Value::from("Zm8=")));
+    assert_eq!(eval("base64::encode(\"foo\")"), Ok(Value::from("Zm9v")));
+    assert_eq!(
+        eval("base64::encode(\"foobar\")"),
+        Ok(Value::from("Zm9vYmFy"))
+    );
+    assert_eq!(eval("base64::encode(\"ü?>\")"), Ok(Value::from("w7w/Pg==")));
+    assert_eq!(
+        eval("base64::decode(\"Zm9vYg==\")"),
+        Ok(Value::from("foob"))
+    );
+    assert_eq!(eval("base64::decode(\"w7w/Pg==\")"), Ok(Value::from("ü?>")));
+    assert_eq!(
+        eval("base64::decode(base64::encode(\"key=värde & more/ü\"))"),
+        Ok(Value::from("key=värde & more/ü"))
+    );
+    assert_eq!(
+        eval("base64::decode(\"Zm9\")"),
+        Err(EvalexprError::CustomMessage(
+            "base64::decode found invalid base64 at byte 3 of \"Zm9\"".into()
+        ))
+    );
+    assert_eq!(
+        eval("base64::decode(\"Zm=v\")"),
+        Err(EvalexprError::CustomMessage(
+            "base64::decode found invalid base64 at byte 2 of \"Zm=v\"".into()
+        ))
+    );
+    assert_eq!(
+        eval("base64::decode(\"Zm9v-A==\")"),
+        Err(EvalexprError::CustomMessage(
+            "base64::decode found invalid base64 at byte 4 of \"Zm9v-A==\"".into()
+        ))
+    );
+    assert_eq!(
+        eval("base64::decode(\"/w==\")"),
+        Err(EvalexprError::CustomMessage(
+            "invalid utf-8 sequence of 1 bytes from index 0".into()
+        ))
+    );
+    assert_eq!(
+        eval("url::encode(1)"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
+    );
+    // Bitwise
+    assert_eq!(eval("bitand(5, -1)"), Ok(Value::Int(5)));
+    assert_eq!(eval("bitand(6, 5)"), Ok(Value::Int(4)));
+    assert_eq!(eval("bitor(5, -1)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("bitor(6, 5)"), Ok(Value::Int(7)));
+    assert_eq!(eval("bitxor(5, -1)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("bitxor(6, 5)"), Ok(Value::Int(3)));
+    assert_eq!(eval("bitnot(5)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("bitnot(-1)"), Ok(Value::Int(0)));
+    assert_eq!(eval("shl(5, 1)"), Ok(Value::Int(10)));
+    assert_eq!(eval("shl(-6, 5)"), Ok(Value::Int(-192)));
+    assert_eq!(eval("shr(5, 1)"), Ok(Value::Int(2)));
+    assert_eq!(eval("shr(-6, 5)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("shl(1, 63)"), Ok(Value::Int(i64::MIN)));
+    assert_eq!(
+        eval("shl(1, 64)"),
+        Err(EvalexprError::ShiftError {
+            value: Value::Int(1),
+            amount: Value::Int(64)
+        })
+    );
+    assert_eq!(
+        eval("shr(1, -1)"),
+        Err(EvalexprError::ShiftError {
+            value: Value::Int(1),
+            amount: Value::Int(-1)
+        })
+    );
+    assert_eq!(eval("if(true, -6, 5)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("if(false, -6, 5)"), Ok(Value::Int(5)));
+    assert_eq!(
+        eval("if(2-1==1, \"good\", 0)"),
+        Ok(Value::String(String::from("good")))
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        eval("-true"),
+        Err(EvalexprError::boolean_in_arithmetic(Operator::Neg, true))
+    );
+    assert_eq!(
+        eval("1-true"),
+        Err(EvalexprError::boolean_in_arithmetic(Operator::Sub, true))
+    );
+    assert_eq!(
+        eval("true + 1"),
+        Err(EvalexprError::BooleanInArithmetic {
+            operator: Operator::Add,
+            actual: true
+        })
+    );
+    assert_eq!(
+        eval("2 * (1 > 2)"),
+        Err(EvalexprError::BooleanInArithmetic {
+            operator: Operator::Mul,
+            actual: false
+        })
+    );
+    assert_eq!(
+        eval("true + 1").unwrap_err().to_string(),
+        "The arithmetic operator + cannot be applied to the boolean true, as booleans are not \
+         numbers. Use if(true, 1, 0) to convert it into a number."
+    );
+    assert_eq!(
+        eval("true-"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            actual: 1,
+            expected: 2,
+        })
+    );
+    assert_eq!(eval("!(()true)"), Err(EvalexprError::AppendedToLeafNode));
+    assert_eq!(
+        eval("math::is_nan(\"xxx\")"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::String("xxx".to_string())
+        })
+    );
+}
+
+#[test]
+fn test_no_panic() {
+    assert!(eval(&format!(
+        "{} + {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "-{} - {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "-(-{} - 1)",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "{} * {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "{} / {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        0
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "{} % {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        0
+    ))
+    .is_err());
+    assert!(eval(&format!(
+        "{} ^ {}",
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX,
+        <DefaultNumericTypes as EvalexprNumericTypes>::Int::MAX
+    ))
+    .is_ok());
+    assert!(eval("if").is_err());
+    assert!(eval("if()").is_err());
+    assert!(eval("if(true, 1)").is_err());
+    assert!(eval("if(false, 2)").is_err());
+    assert!(eval("if(1,1,1)").is_err());
+    assert!(eval("if(true,1,1,1)").is_err());
+}
+
+#[test]
+fn test_overflow_mode() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_value("max".into(), Value::from_int(i64::MAX))
+        .unwrap();
+    context
+        .set_value("min".into(), Value::from_int(i64::MIN))
+        .unwrap();
+    assert_eq!(context.overflow_mode(), OverflowMode::Error);
+    assert_eq!(
+        eval_with_context("max + 1", &context),
+        Err(EvalexprError::AdditionError {
+            augend: Value::from_int(i64::MAX),
+            addend: Value::from_int(1)
+        })
+    );
+
+    context.set_overflow_mode(OverflowMode::PromoteToFloat);
+    assert_eq!(
+        eval_with_context("max + 1", &context),
+        Ok(Value::from_float(i64::MAX as f64 + 1.0))
+    );
+    assert_eq!(
+        eval_with_context("min - 1", &context),
+        Ok(Value::from_float(i64::MIN as f64 - 1.0))
+    );
+    assert_eq!(
+        eval_with_context("-min", &context),
+        Ok(Value::from_float(-(i64::MIN as f64)))
+    );
+    assert_eq!(
+        eval_with_context("max * 2", &context),
+        Ok(Value::from_float(i64::MAX as f64 * 2.0))
+    );
+    assert_eq!(eval_with_context("2 + 3", &context), Ok(Value::from_int(5)));
+
+    context.set_overflow_mode(OverflowMode::Wrapping);
+    assert_eq!(
+        eval_with_context("max + 1", &context),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_context("min - 1", &context),
+        Ok(Value::from_int(i64::MAX))
+    );
+    assert_eq!(
+        eval_with_context("-min", &context),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_context("max * 2", &context),
+        Ok(Value::from_int(-2))
+    );
+
+    context.set_overflow_mode(OverflowMode::Saturating);
+    assert_eq!(
+        eval_with_context("max + 1", &context),
+        Ok(Value::from_int(i64::MAX))
+    );
+    assert_eq!(
+        eval_with_context("min - 1", &context),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_context("-min", &context),
+        Ok(Value::from_int(i64::MAX))
+    );
+    assert_eq!(
+        eval_with_context("min * 2", &context),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_context("min * -2", &context),
+        Ok(Value::from_int(i64::MAX))
+    );
+    assert_eq!(
+        eval_with_context("max - -1", &context),
+        Ok(Value::from_int(i64::MAX))
+    );
+    assert_eq!(
+        eval_with_context("min + -1", &context),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_context_mut("a = max; a += 5; a", &mut context),
+        Ok(Value::from_int(i64::MAX))
+    );
+
+    // Division by zero is not an overflow.
+    assert_eq!(
+        eval_with_context("1 / 0", &context),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from_int(1),
+            divisor: Value::from_int(0)
+        })
+    );
+
+    // The result of integer arithmetic is unknown if it may be promoted to a float.
+    assert_eq!(
+        build_operator_tree("max + 1").unwrap().infer_type(&context),
+        Ok(ValueType::Int)
+    );
+    context.set_overflow_mode(OverflowMode::PromoteToFloat);
+    assert!(matches!(
+        build_operator_tree("max + 1").unwrap().infer_type(&context),
+        Err(EvalexprError::TypeUnknown { .. })
+    ));
+    assert_eq!(
+        build_operator_tree("max / 2").unwrap().infer_type(&context),
+        Ok(ValueType::Int)
+    );
+}
+
+#[test]
+fn test_shortcut_functions() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_value("string".into(), Value::from("a string"))
+        .unwrap();
+
+    assert_eq!(eval_string("\"3.3\""), Ok("3.3".to_owned()));
+    assert_eq!(
+        eval_string("3.3"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_string("3..3"),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        eval_string_with_context("string", &context),
+        Ok("a string".to_owned())
+    );
+    assert_eq!(
+        eval_string_with_context("3.3", &context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
         })
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Int(0).as_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Int(0)
+        eval_string_with_context("3..3", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        eval_string_with_context_mut("string", &mut context),
+        Ok("a string".to_string())
+    );
+    assert_eq!(
+        eval_string_with_context_mut("3.3", &mut context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_string_with_context_mut("3..3", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+
+    assert_eq!(eval_float("3.3"), Ok(3.3));
+    assert_eq!(
+        eval_float("33"),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float("asd()"),
+        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(eval_float_with_context("3.3", &context), Ok(3.3));
+    assert_eq!(
+        eval_float_with_context("33", &context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float_with_context("asd)", &context),
+        Err(EvalexprError::UnmatchedRBrace { span: Some(3..4) })
+    );
+    assert_eq!(eval_float_with_context_mut("3.3", &mut context), Ok(3.3));
+    assert_eq!(
+        eval_float_with_context_mut("33", &mut context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float_with_context_mut("asd(", &mut context),
+        Err(EvalexprError::UnmatchedLBrace { span: Some(3..4) })
+    );
+
+    assert_eq!(eval_int("3"), Ok(3));
+    assert_eq!(
+        eval_int("3.3"),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int("(,);."),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(eval_int_with_context("3", &context), Ok(3));
+    assert_eq!(
+        eval_int_with_context("3.3", &context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int_with_context("(,);.", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(eval_int_with_context_mut("3", &mut context), Ok(3));
+    assert_eq!(
+        eval_int_with_context_mut("3.3", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int_with_context_mut("(,);.", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+
+    assert_eq!(eval_number("3"), Ok(3.0));
+    assert_eq!(
+        eval_number("true"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number("abc"),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(eval_number_with_context("3.5", &context), Ok(3.5));
+    assert_eq!(eval_number_with_context("3", &context), Ok(3.0));
+    assert_eq!(
+        eval_number_with_context("true", &context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number_with_context("abc", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(eval_number_with_context_mut("3.5", &mut context), Ok(3.5));
+    assert_eq!(eval_number_with_context_mut("3", &mut context), Ok(3.0));
+    assert_eq!(
+        eval_number_with_context_mut("true", &mut context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number_with_context_mut("abc", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+
+    assert_eq!(eval_boolean("true"), Ok(true));
+    assert_eq!(
+        eval_boolean("4"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean("trueee"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(eval_boolean_with_context("true", &context), Ok(true));
+    assert_eq!(
+        eval_boolean_with_context("4", &context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean_with_context("trueee", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("true", &mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("4", &mut context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("trueee", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+
+    assert_eq!(eval_tuple("3,3"), Ok(vec![Value::Int(3), Value::Int(3)]));
+    assert_eq!(
+        eval_tuple("33"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple("3a3"),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        eval_tuple_with_context("3,3", &context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        eval_tuple_with_context("33", &context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple_with_context("3a3", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("3,3", &mut context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("33", &mut context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("3a3", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+
+    assert_eq!(eval_empty(""), Ok(EMPTY_VALUE));
+    assert_eq!(eval_empty("()"), Ok(EMPTY_VALUE));
+    assert_eq!(
+        eval_empty("(,)"),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty("xaq"),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(eval_empty_with_context("", &context), Ok(EMPTY_VALUE));
+    assert_eq!(eval_empty_with_context("()", &context), Ok(EMPTY_VALUE));
+    assert_eq!(
+        eval_empty_with_context("(,)", &context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty_with_context("xaq", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("()", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("(,)", &mut context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("xaq", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+
+    // With detour via build_operator_tree
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("\"3.3\"")
+            .unwrap()
+            .eval_string(),
+        Ok("3.3".to_owned())
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3.3")
+            .unwrap()
+            .eval_string(),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3..3")
+            .unwrap()
+            .eval_string(),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("string")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Ok("a string".to_owned())
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3..3")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("string")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Ok("a string".to_string())
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3..3")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3.3")
+            .unwrap()
+            .eval_float(),
+        Ok(3.3)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("33")
+            .unwrap()
+            .eval_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("asd()")
+            .unwrap()
+            .eval_float(),
+        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Ok(3.3)
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("asd")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
+        Ok(3.3)
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("asd")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3")
+            .unwrap()
+            .eval_int(),
+        Ok(3)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3.3")
+            .unwrap()
+            .eval_int(),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("(,);.")
+            .unwrap()
+            .eval_int(),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Ok(3)
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("(,);.")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Ok(3)
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("(,);.")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3")
+            .unwrap()
+            .eval_number(),
+        Ok(3.0)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("true")
+            .unwrap()
+            .eval_number(),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("abc")
+            .unwrap()
+            .eval_number(),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Ok(3.0)
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("abc")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Ok(3.0)
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("abc")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("true")
+            .unwrap()
+            .eval_boolean(),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("4")
+            .unwrap()
+            .eval_boolean(),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("trueee")
+            .unwrap()
+            .eval_boolean(),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree("4")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("trueee")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree("4")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("trueee")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3,3")
+            .unwrap()
+            .eval_tuple(),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("33")
+            .unwrap()
+            .eval_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("3a3")
+            .unwrap()
+            .eval_tuple(),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3,3")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3a3")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3,3")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3a3")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("")
+            .unwrap()
+            .eval_empty(),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("()")
+            .unwrap()
+            .eval_empty(),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("(,)")
+            .unwrap()
+            .eval_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("xaq")
+            .unwrap()
+            .eval_empty(),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("()")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("(,)")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        build_operator_tree("xaq")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("()")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("(,)")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        build_operator_tree("xaq")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+}
+
+#[test]
+fn test_whitespace() {
+    assert!(eval_boolean("2 < = 3").is_err());
+}
+
+#[test]
+fn test_assignment() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(eval_int_with_context_mut("int = 3", &mut context), Ok(3));
+    assert_eq!(
+        eval_float_with_context_mut("float = 2.0", &mut context),
+        Ok(2.0)
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("tuple = (1,1)", &mut context),
+        Ok(vec![Value::from_int(1), Value::from_int(1)])
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("empty = ()", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("boolean = false", &mut context),
+        Ok(false)
+    );
+
+    assert_eq!(eval_int_with_context("int", &context), Ok(3));
+    assert_eq!(eval_float_with_context("float", &context), Ok(2.0));
+    assert_eq!(
+        eval_tuple_with_context("tuple", &context),
+        Ok(vec![Value::from_int(1), Value::from_int(1)])
+    );
+    assert_eq!(eval_empty_with_context("empty", &context), Ok(EMPTY_VALUE));
+    assert_eq!(eval_boolean_with_context("boolean", &context), Ok(false));
+
+    assert_eq!(eval_int_with_context_mut("b = a = 5", &mut context), Ok(5));
+    assert_eq!(eval_int_with_context("a", &context), Ok(5));
+    assert_eq!(eval_int_with_context("b", &context), Ok(5));
+
+    // Assignments evaluate to the assigned value, so they compose within chains.
+    assert_eq!(eval("a = 3; b = a = 4; b"), Ok(Value::from_int(4)));
+    assert_eq!(
+        eval("a = 3; b = (a = 4) + 1; a + b"),
+        Ok(Value::from_int(9))
+    );
+    assert_eq!(eval("a = 3; a + 1"), Ok(Value::from_int(4)));
+    assert_eq!(eval("a = 3; a = 4"), Ok(Value::from_int(4)));
+    assert_eq!(eval("a = 3;"), Ok(Value::Empty));
+    assert_eq!(eval("a = 3; a += 1"), Ok(Value::from_int(4)));
+}
+
+#[test]
+fn test_null_literal() {
+    assert_eq!(eval("null"), Ok(Value::Empty));
+    assert_eq!(eval("null == ()"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("x = null; x == null"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("x = 1; x == null"), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval("(1, null)"),
+        Ok(Value::from(vec![Value::from_int(1), Value::Empty]))
+    );
+    assert_eq!(eval("typeof(null)"), Ok(Value::from("empty")));
+    assert_eq!(
+        eval("nullable"),
+        Err(EvalexprError::VariableIdentifierNotFound("nullable".into()))
+    );
+    assert_eq!(
+        tokenize::<DefaultNumericTypes>("a != null"),
+        Ok(vec![Token::Identifier("a".into()), Token::Neq, Token::Null])
+    );
+
+    // The keyword wins over a variable of the same name.
+    let context: HashMapContext<DefaultNumericTypes> = context_map! { "null" => int 1 }.unwrap();
+    assert_eq!(eval_with_context("null", &context), Ok(Value::Empty));
+    assert!(eval("null = 1").is_err());
+}
+
+#[test]
+fn test_expression_chaining() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(
+        eval_int_with_context_mut("a = 5; a = a + 2; a", &mut context),
+        Ok(7)
+    );
+}
+
+#[test]
+fn test_strings() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(eval("\"string\""), Ok(Value::from("string")));
+    assert_eq!(
+        eval_with_context_mut("a = \"a string\"", &mut context),
+        Ok(Value::from("a string"))
+    );
+    assert_eq!(
+        eval_boolean_with_context("a == \"a string\"", &context),
+        Ok(true)
+    );
+    assert_eq!(eval("\"a\" + \"b\""), Ok(Value::from("ab")));
+    assert_eq!(eval("\"a\" > \"b\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"a\" < \"b\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" >= \"b\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"a\" <= \"b\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" >= \"a\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" <= \"a\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"xa\" > \"xb\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"xa\" < \"xb\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"{}\" != \"{}\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"{}\" == \"{}\""), Ok(Value::from(true)));
+}
+
+#[test]
+fn test_string_escaping() {
+    assert_eq!(
+        eval("\"\\\"str\\\\ing\\\"\""),
+        Ok(Value::from("\"str\\ing\""))
+    );
+}
+
+#[test]
+fn test_tuple_definitions() {
+    assert_eq!(eval_empty("()"), Ok(()));
+    assert_eq!(eval_int("(3)"), Ok(3));
+    assert_eq!(
+        eval_tuple("(3, 4)"),
+        Ok(vec![Value::from_int(3), Value::from_int(4)])
+    );
+    assert_eq!(
+        eval_tuple("2, (5, 6)"),
+        Ok(vec![
+            Value::from_int(2),
+            Value::from(vec![Value::from_int(5), Value::from_int(6)])
+        ])
+    );
+    assert_eq!(
+        eval_tuple("1, 2"),
+        Ok(vec![Value::from_int(1), Value::from_int(2)])
+    );
+    assert_eq!(
+        eval_tuple("1, 2, 3, 4"),
+        Ok(vec![
+            Value::from_int(1),
+            Value::from_int(2),
+            Value::from_int(3),
+            Value::from_int(4)
+        ])
+    );
+    assert_eq!(
+        eval_tuple("(1, 2, 3), 5, 6, (true, false, 0)"),
+        Ok(vec![
+            Value::from(vec![
+                Value::from_int(1),
+                Value::from_int(2),
+                Value::from_int(3)
+            ]),
+            Value::from_int(5),
+            Value::from_int(6),
+            Value::from(vec![
+                Value::from(true),
+                Value::from(false),
+                Value::from_int(0)
+            ])
+        ])
+    );
+    assert_eq!(
+        eval_tuple("1, (2)"),
+        Ok(vec![Value::from_int(1), Value::from_int(2)])
+    );
+    assert_eq!(
+        eval_tuple("1, ()"),
+        Ok(vec![Value::from_int(1), Value::from(())])
+    );
+    assert_eq!(
+        eval_tuple("1, ((2))"),
+        Ok(vec![Value::from_int(1), Value::from_int(2)])
+    );
+}
+
+#[test]
+fn test_implicit_context() {
+    assert_eq!(
+        eval("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
+        Ok(Value::from(true))
+    );
+    assert_eq!(
+        eval_boolean("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
+        Ok(true)
+    );
+    assert_eq!(eval_int("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0));
+    assert_eq!(
+        eval_float("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b + 0.5"),
+        Ok(0.5)
+    );
+    assert_eq!(eval_number("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0.0));
+    assert_eq!(eval_empty("a = 2 + 4 * 2; b = -5 + 3 * 5;"), Ok(()));
+    assert_eq!(
+        eval_tuple("a = 2 + 4 * 2; b = -5 + 3 * 5; a, b + 0.5"),
+        Ok(vec![Value::from_int(10), Value::from_float(10.5)])
+    );
+    assert_eq!(
+        eval_string("a = \"xyz\"; b = \"abc\"; c = a + b; c"),
+        Ok("xyzabc".to_string())
+    );
+}
+
+#[test]
+fn test_operator_assignments() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(eval_int_with_context_mut("a = 5", &mut context), Ok(5));
+    assert_eq!(eval_int_with_context_mut("a += 5", &mut context), Ok(10));
+    assert_eq!(eval_int_with_context_mut("a -= 5", &mut context), Ok(5));
+    assert_eq!(eval_int_with_context_mut("a *= 5", &mut context), Ok(25));
+    assert_eq!(
+        eval_float_with_context_mut("b = 5.0", &mut context),
+        Ok(5.0)
+    );
+    assert_eq!(eval_float_with_context_mut("b /= 5", &mut context), Ok(1.0));
+    assert_eq!(eval_float_with_context_mut("b %= 5", &mut context), Ok(1.0));
+    assert_eq!(eval_float_with_context_mut("b ^= 5", &mut context), Ok(1.0));
+    assert_eq!(
+        eval_boolean_with_context_mut("c = true", &mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c &&= false", &mut context),
+        Ok(false)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c ||= true", &mut context),
+        Ok(true)
+    );
+
+    // Operator-assignments evaluate to the stored value, so they compose with other assignments.
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(
+        eval_tuple_with_context_mut("a = 1; b = (a += 1); c = (a *= b); (a, b, c)", &mut context),
+        Ok(vec![
+            Value::from_int(4),
+            Value::from_int(2),
+            Value::from_int(4)
+        ])
+    );
+    assert_eq!(context.get_value("b"), Some(&Value::from_int(2)));
+    assert_eq!(context.get_value("c"), Some(&Value::from_int(4)));
+
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(eval_int_with_context_mut("a = 5; a", &mut context), Ok(5));
+    assert_eq!(eval_int_with_context_mut("a += 3; a", &mut context), Ok(8));
+    assert_eq!(eval_int_with_context_mut("a -= 5; a", &mut context), Ok(3));
+    assert_eq!(eval_int_with_context_mut("a *= 5; a", &mut context), Ok(15));
+    assert_eq!(
+        eval_float_with_context_mut("b = 5.0; b", &mut context),
+        Ok(5.0)
+    );
+    assert_eq!(
+        eval_float_with_context_mut("b /= 2; b", &mut context),
+        Ok(2.5)
+    );
+    assert_eq!(
+        eval_float_with_context_mut("b %= 2; b", &mut context),
+        Ok(0.5)
+    );
+    assert_eq!(
+        eval_float_with_context_mut("b ^= 2; b", &mut context),
+        Ok(0.25)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c = true; c", &mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c &&= false; c", &mut context),
+        Ok(false)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c ||= true; c", &mut context),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_type_errors_in_binary_operators() {
+    // Only addition supports incompatible types, all others work only on numbers or only on booleans.
+    // So only addition requires the more fancy error message.
+    assert_eq!(
+        eval("4 + \"abc\""),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Add,
+            vec![ValueType::Int, ValueType::String]
+        ))
+    );
+    assert_eq!(
+        eval("\"abc\" + 4"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Add,
+            vec![ValueType::String, ValueType::Int]
+        ))
+    );
+}
+
+#[test]
+fn test_empty_context() {
+    let mut context = EmptyContext::<DefaultNumericTypes>::default();
+    assert_eq!(context.get_value("abc"), None);
+    assert_eq!(
+        context.call_function("abc", &Value::Empty),
+        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(
+        eval_with_context("max(1,3)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
+            "max"
+        )))
+    );
+    assert_eq!(context.set_builtin_functions_disabled(true), Ok(()));
+    assert_eq!(
+        context.set_builtin_functions_disabled(false),
+        Err(EvalexprError::BuiltinFunctionsCannotBeEnabled)
+    )
+}
+
+#[test]
+fn test_empty_context_with_builtin_functions() {
+    let mut context = EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default();
+    assert_eq!(context.get_value("abc"), None);
+    assert_eq!(
+        context.call_function("abc", &Value::Empty),
+        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(eval_with_context("max(1,3)", &context), Ok(Value::Int(3)));
+    assert_eq!(context.set_builtin_functions_disabled(false), Ok(()));
+    assert_eq!(
+        context.set_builtin_functions_disabled(true),
+        Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
+    );
+}
+
+#[test]
+fn test_hashmap_context_type_safety() {
+    let mut context: HashMapContext<DefaultNumericTypes> =
+        context_map! {"a" => int 5, "b" => float 5.0}.unwrap();
+    assert_eq!(
+        eval_with_context_mut("a = 4", &mut context),
+        Ok(Value::from_int(4))
+    );
+    assert_eq!(
+        eval_with_context_mut("a = 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(4.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a += 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(8.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a -= 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(0.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a *= 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(16.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a /= 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(1.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a %= 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(0.0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a ^= 4.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(256.0)
+        })
+    );
+
+    assert_eq!(
+        eval_with_context_mut("b = 4.0", &mut context),
+        Ok(Value::from_float(4.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b = 4", &mut context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("b += 4", &mut context),
+        Ok(Value::from_float(8.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b -= 4", &mut context),
+        Ok(Value::from_float(4.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b *= 4", &mut context),
+        Ok(Value::from_float(16.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b /= 4", &mut context),
+        Ok(Value::from_float(4.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b %= 4", &mut context),
+        Ok(Value::from_float(0.0))
+    );
+    assert_eq!(
+        eval_with_context_mut("b ^= 4", &mut context),
+        Ok(Value::from_float(0.0))
+    );
+}
+
+#[test]
+fn test_hashmap_context_clone_debug() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    // this variable is captured by the function
+    let three = 3;
+    context
+        .set_function(
+            "mult_3".into(),
+            Function::new(move |argument| {
+                if let Value::Int(int) = argument {
+                    Ok(Value::Int(int * three))
+                } else if let Value::Float(float) = argument {
+                    Ok(Value::Float(
+                        float * three as <DefaultNumericTypes as EvalexprNumericTypes>::Float,
+                    ))
+                } else {
+                    Err(EvalexprError::expected_number(argument.clone()))
+                }
+            }),
+        )
+        .unwrap();
+
+    let four = 4;
+    context
+        .set_function(
+            "function_four".into(),
+            Function::new(move |_| Ok(Value::Int(four))),
+        )
+        .unwrap();
+    context
+        .set_value("variable_five".into(), Value::from_int(5))
+        .unwrap();
+    let context = context;
+    #[allow(clippy::redundant_clone)]
+    let cloned_context = context.clone();
+
+    assert_eq!(format!("{:?}", &context), format!("{:?}", &cloned_context));
+    assert_eq!(
+        cloned_context.get_value("variable_five"),
+        Some(&Value::from_int(5))
+    );
+    assert_eq!(
+        eval_with_context("mult_3 2", &cloned_context),
+        Ok(Value::Int(6))
+    );
+    assert_eq!(
+        eval_with_context("mult_3(3)", &cloned_context),
+        Ok(Value::Int(9))
+    );
+    assert_eq!(
+        eval_with_context("mult_3(function_four())", &cloned_context),
+        Ok(Value::Int(12))
+    );
+}
+
+#[test]
+fn test_error_constructors() {
+    assert_eq!(
+        eval("a = () + \"4\""),
+        Err(EvalexprError::ExpectedNumberOrString {
+            actual: Value::Empty
+        })
+    );
+    assert_eq!(
+        eval("a = true && \"4\""),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::from("4")
+        })
+    );
+    assert_eq!(
+        eval_tuple("4"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        Value::Tuple(vec![Value::<DefaultNumericTypes>::Int(4), Value::Int(5)])
+            .as_fixed_len_tuple(3),
+        Err(EvalexprError::ExpectedFixedLengthTuple {
+            expected_length: 3,
+            actual: Value::Tuple(vec![Value::Int(4), Value::Int(5)])
+        })
+    );
+    assert_eq!(
+        eval_empty("4"),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval("&"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 0
+        })
+    );
+
+    assert_eq!(
+        expect_function_argument_amount::<DefaultNumericTypes>(2, 2),
+        Ok(())
+    );
+    assert_eq!(
+        expect_function_argument_amount::<DefaultNumericTypes>(2, 3),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 3..=3,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn test_iterators() {
+    let tree =
+        build_operator_tree::<DefaultNumericTypes>("writevar = 5 + 3 + fun(4) + var").unwrap();
+    let mut iter = tree.iter_identifiers();
+    assert_eq!(iter.next(), Some("writevar"));
+    assert_eq!(iter.next(), Some("fun"));
+    assert_eq!(iter.next(), Some("var"));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = tree.iter_variable_identifiers();
+    assert_eq!(iter.next(), Some("writevar"));
+    assert_eq!(iter.next(), Some("var"));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = tree.iter_read_variable_identifiers();
+    assert_eq!(iter.next(), Some("var"));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = tree.iter_write_variable_identifiers();
+    assert_eq!(iter.next(), Some("writevar"));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = tree.iter_function_identifiers();
+    assert_eq!(iter.next(), Some("fun"));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_operator_counts() {
+    let tree = build_operator_tree::<DefaultNumericTypes>(
+        "x = (a + b) * 2; y = -x; x >= y && !f(x, 3.5) || typeof(y) == \"int\"",
+    )
+    .unwrap();
+    let counts = tree.operator_counts();
+
+    let expected = HashMap::from([
+        ("RootNode", 9),
+        ("Chain", 1),
+        ("Assign", 2),
+        ("VariableIdentifierWrite", 2),
+        ("VariableIdentifierRead", 7),
+        ("Add", 1),
+        ("Mul", 1),
+        ("Neg", 1),
+        ("Geq", 1),
+        ("And", 1),
+        ("Or", 1),
+        ("Not", 1),
+        ("Eq", 1),
+        ("FunctionIdentifier", 2),
+        ("Tuple", 1),
+        ("Const", 3),
+    ]);
+    assert_eq!(counts, expected);
+    assert_eq!(counts.values().sum::<usize>(), tree.iter().count() + 1);
+
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("")
+            .unwrap()
+            .operator_counts(),
+        HashMap::from([("RootNode", 1)])
+    );
+}
+
+#[test]
+fn test_same_operator_chains() {
+    #![allow(clippy::eq_op)]
+    assert_eq!(
+        eval("3.0 / 3.0 / 3.0 / 3.0"),
+        Ok(Value::from_float(3.0 / 3.0 / 3.0 / 3.0))
+    );
+    assert_eq!(
+        eval("3.0 - 3.0 - 3.0 - 3.0"),
+        Ok(Value::from_float(3.0 - 3.0 - 3.0 - 3.0))
+    );
+}
+
+#[test]
+fn test_long_expression_i89() {
+    let tree = build_operator_tree::<DefaultNumericTypes>(
+        "x*0.2*5/4+x*2*4*1*1*1*1*1*1*1+7*math::sin(y)-z/math::sin(3.0/2.0/(1-x*4*1*1*1*1))",
+    )
+    .unwrap();
+    let x = 0.0;
+    let y: <DefaultNumericTypes as EvalexprNumericTypes>::Float = 3.0;
+    let z = 4.0;
+    let context = context_map! {
+        "x" => float 0.0,
+        "y" => float 3.0,
+        "z" => float 4.0
+    }
+    .unwrap();
+    let expected = x * 0.2 * 5.0 / 4.0
+        + x * 2.0 * 4.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0 * 1.0
+        + 7.0 * y.sin()
+        - z / (3.0 / 2.0 / (1.0 - x * 4.0 * 1.0 * 1.0 * 1.0 * 1.0)).sin();
+    let actual: <DefaultNumericTypes as EvalexprNumericTypes>::Float =
+        tree.eval_float_with_context(&context).unwrap();
+    assert!(
+        (expected - actual).abs() < expected.abs().min(actual.abs()) * 1e-12,
+        "expected: {}, actual: {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_value_type() {
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::String(String::new())),
+        ValueType::String
+    );
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::Float(0.0)),
+        ValueType::Float
+    );
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::Int(0)),
+        ValueType::Int
+    );
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::Boolean(true)),
+        ValueType::Boolean
+    );
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::Tuple(Vec::new())),
+        ValueType::Tuple
+    );
+    assert_eq!(
+        ValueType::from(&Value::<DefaultNumericTypes>::Empty),
+        ValueType::Empty
+    );
+
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::String(String::new())),
+        ValueType::String
+    );
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::Float(0.0)),
+        ValueType::Float
+    );
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::Int(0)),
+        ValueType::Int
+    );
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::Boolean(true)),
+        ValueType::Boolean
+    );
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::Tuple(Vec::new())),
+        ValueType::Tuple
+    );
+    assert_eq!(
+        ValueType::from(&mut Value::<DefaultNumericTypes>::Empty),
+        ValueType::Empty
+    );
+
+    assert!(!Value::<DefaultNumericTypes>::String(String::new()).is_number());
+    assert!(Value::<DefaultNumericTypes>::Float(0.0).is_number());
+    assert!(Value::<DefaultNumericTypes>::Int(0).is_number());
+    assert!(!Value::<DefaultNumericTypes>::Boolean(true).is_number());
+    assert!(!Value::<DefaultNumericTypes>::Tuple(Vec::new()).is_number());
+    assert!(!Value::<DefaultNumericTypes>::Empty.is_number());
+
+    assert!(!Value::<DefaultNumericTypes>::String(String::new()).is_empty());
+    assert!(!Value::<DefaultNumericTypes>::Float(0.0).is_empty());
+    assert!(!Value::<DefaultNumericTypes>::Int(0).is_empty());
+    assert!(!Value::<DefaultNumericTypes>::Boolean(true).is_empty());
+    assert!(!Value::<DefaultNumericTypes>::Tuple(Vec::new()).is_empty());
+    assert!(Value::<DefaultNumericTypes>::Empty.is_empty());
+
+    assert_eq!(
+        Value::<DefaultNumericTypes>::String(String::new()).as_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::String(String::new())
+        })
+    );
+    assert_eq!(Value::<DefaultNumericTypes>::Float(0.0).as_float(), Ok(0.0));
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Int(0).as_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Boolean(true).as_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Tuple(Vec::new())
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Empty.as_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Empty
+        })
+    );
+
+    assert_eq!(
+        Value::<DefaultNumericTypes>::String(String::new()).as_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::String(String::new())
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Float(0.0).as_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Float(0.0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Int(0).as_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Boolean(true).as_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_tuple(),
+        Ok(Vec::new())
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Empty.as_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Empty
+        })
+    );
+
+    assert_eq!(
+        Value::<DefaultNumericTypes>::String(String::new()).as_fixed_len_tuple(0),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::String(String::new())
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Float(0.0).as_fixed_len_tuple(0),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Float(0.0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Int(0).as_fixed_len_tuple(0),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Boolean(true).as_fixed_len_tuple(0),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_fixed_len_tuple(0),
+        Ok(Vec::new())
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Empty.as_fixed_len_tuple(0),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Empty
+        })
+    );
+
+    assert_eq!(
+        Value::<DefaultNumericTypes>::String(String::new()).as_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::String(String::new())
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Float(0.0).as_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Float(0.0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Int(0).as_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Boolean(true).as_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(Vec::new())
+        })
+    );
+    assert_eq!(Value::<DefaultNumericTypes>::Empty.as_empty(), Ok(()));
+
+    assert_eq!(
+        Result::from(Value::<DefaultNumericTypes>::String(String::new())),
+        Ok(Value::String(String::new()))
+    );
+}
+
+#[test]
+fn test_parse_error_spans() {
+    assert_eq!(eval("1 + (2 * 3").unwrap_err().span(), Some(4..5));
+    assert_eq!(eval("max(1, 2))").unwrap_err().span(), Some(9..10));
+    assert_eq!(eval("ä ? 1").unwrap_err().span(), Some(3..4));
+    assert_eq!(eval("1 + ").unwrap_err().span(), None);
+    assert_eq!(
+        eval("1 + (2 * 3").unwrap_err().to_string(),
+        "Found an unmatched opening parenthesis '(' at position 4..5."
+    );
+    assert_eq!(
+        eval("(1, 2]").unwrap_err().to_string(),
+        "Found an unmatched closing bracket ']' at position 5..6."
+    );
+}
+
+#[test]
+fn test_parenthese_combinations() {
+    // These are from issue #94
+    assert_eq!(
+        eval("123(1*2)"),
+        Err(EvalexprError::MissingOperatorOutsideOfBrace { span: Some(7..8) })
+    );
+    assert_eq!(
+        eval("1()"),
+        Err(EvalexprError::MissingOperatorOutsideOfBrace { span: Some(2..3) })
+    );
+    assert_eq!(
+        eval("1()()()()"),
+        Err(EvalexprError::MissingOperatorOutsideOfBrace { span: Some(2..3) })
+    );
+    assert_eq!(
+        eval("1()()()(9)()()"),
+        Err(EvalexprError::MissingOperatorOutsideOfBrace { span: Some(2..3) })
+    );
+    assert_eq!(
+        eval_with_context("a+100(a*2)", &context_map! {"a" => int 4}.unwrap()),
+        Err(
+            EvalexprError::<DefaultNumericTypes>::MissingOperatorOutsideOfBrace {
+                span: Some(9..10)
+            }
+        )
+    );
+    assert_eq!(eval_int("(((1+2)*(3+4)+(5-(6)))/((7-8)))"), Ok(-20));
+    assert_eq!(eval_int("(((((5)))))"), Ok(5));
+}
+
+#[test]
+fn test_try_from() {
+    #![allow(clippy::redundant_clone)]
+
+    let value = Value::<DefaultNumericTypes>::String("abc".to_string());
+    assert_eq!(String::try_from(value.clone()), Ok("abc".to_string()));
+    assert_eq!(
+        bool::try_from(value.clone()),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedTuple {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        EmptyType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: value.clone()
+        })
+    );
+
+    let value = Value::<DefaultNumericTypes>::Float(1.3);
+    assert_eq!(
+        String::try_from(value.clone()),
+        Err(EvalexprError::ExpectedString {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        bool::try_from(value.clone()),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedTuple {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        EmptyType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: value.clone()
+        })
+    );
+
+    let value = Value::<DefaultNumericTypes>::Int(13);
+    assert_eq!(
+        String::try_from(value.clone()),
+        Err(EvalexprError::ExpectedString {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        bool::try_from(value.clone()),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedTuple {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        EmptyType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: value.clone()
+        })
+    );
+
+    let value = Value::<DefaultNumericTypes>::Boolean(true);
+    assert_eq!(
+        String::try_from(value.clone()),
+        Err(EvalexprError::ExpectedString {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(bool::try_from(value.clone()), Ok(true));
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedTuple {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        EmptyType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: value.clone()
+        })
+    );
+
+    let value =
+        Value::<DefaultNumericTypes>::Tuple(vec![Value::Int(1), Value::String("abc".to_string())]);
+    assert_eq!(
+        String::try_from(value.clone()),
+        Err(EvalexprError::ExpectedString {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        bool::try_from(value.clone()),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Ok(vec![Value::Int(1), Value::String("abc".to_string())])
+    );
+    assert_eq!(
+        EmptyType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: value.clone()
+        })
+    );
+
+    let value = Value::<DefaultNumericTypes>::Empty;
+    assert_eq!(
+        String::try_from(value.clone()),
+        Err(EvalexprError::ExpectedString {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        bool::try_from(value.clone()),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(
+        TupleType::try_from(value.clone()),
+        Err(EvalexprError::ExpectedTuple {
+            actual: value.clone()
+        })
+    );
+    assert_eq!(EmptyType::try_from(value.clone()), Ok(()));
+}
+
+#[test]
+fn assignment_lhs_is_identifier() {
+    let tree = build_operator_tree("a = 1").unwrap();
+    let operators: Vec<_> = tree.iter().map(|node| node.operator().clone()).collect();
+
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    tree.eval_with_context_mut(&mut context).unwrap();
+    assert_eq!(context.get_value("a"), Some(&Value::Int(1)));
+
+    assert!(
+        matches!(
+            operators.as_slice(),
+            [
+                Operator::Assign,
+                Operator::VariableIdentifierWrite { identifier: value },
+                Operator::Const {
+                    value: Value::Int(1)
+                }
+            ] if value == "a"
+        ),
+        "actual: {:#?}",
+        operators
+    );
+}
+
+#[test]
+fn test_variable_assignment_and_iteration() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    eval_with_context_mut("a = 5; b = 5.0", &mut context).unwrap();
+
+    let mut variables: Vec<_> = context.iter_variables().collect();
+    variables.sort_unstable_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    assert_eq!(
+        variables,
+        vec![
+            ("a".to_string(), Value::from_int(5)),
+            ("b".to_string(), Value::from_float(5.0))
+        ],
+    );
+
+    let mut variables: Vec<_> = context.iter_variable_names().collect();
+    variables.sort_unstable();
+    assert_eq!(variables, vec!["a".to_string(), "b".to_string()],);
+}
+
+#[test]
+fn test_function_iteration() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(context.iter_function_names().next(), None);
+    context
+        .set_function("f".into(), Function::new(|argument| Ok(argument.clone())))
+        .unwrap();
+    context
+        .set_function("g".into(), Function::new(|_| Ok(Value::Empty)))
+        .unwrap();
+    context.set_value("h".into(), Value::from_int(1)).unwrap();
+
+    let mut functions: Vec<_> = context.iter_function_names().collect();
+    functions.sort_unstable();
+    assert_eq!(functions, vec!["f".to_string(), "g".to_string()]);
+    assert!(context.contains_function("f"));
+    assert!(!context.contains_function("h"));
+    assert!(!context.contains_function("min"));
+
+    assert_eq!(
+        EmptyContext::<DefaultNumericTypes>::default()
+            .iter_function_names()
+            .next(),
+        None
+    );
+    assert_eq!(
+        EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
+            .iter_function_names()
+            .next(),
+        None
+    );
+}
+
+#[test]
+fn test_negative_power() {
+    println!(
+        "{:?}",
+        build_operator_tree::<DefaultNumericTypes>("3^-2").unwrap()
+    );
+    assert_eq!(eval("3^-2"), Ok(Value::Float(1.0 / 9.0)));
+    assert_eq!(eval("3^(-2)"), Ok(Value::Float(1.0 / 9.0)));
+    assert_eq!(eval("-3^2"), Ok(Value::Float(-9.0)));
+    assert_eq!(eval("-(3)^2"), Ok(Value::Float(-9.0)));
+    assert_eq!(eval("(-3)^-2"), Ok(Value::Float(1.0 / 9.0)));
+    assert_eq!(eval("-(3^-2)"), Ok(Value::Float(-1.0 / 9.0)));
+}
+
+#[test]
+fn test_builtin_functions_context() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    // Builtin functions are enabled by default for HashMapContext.
+    assert_eq!(
+        eval_with_context("max(1,3)", &context),
+        Ok(Value::from_int(3))
+    );
+    // Disabling builtin function in Context.
+    context.set_builtin_functions_disabled(true).unwrap();
+    // Builtin functions are disabled and using them returns an error.
+    assert_eq!(
+        eval_with_context("max(1,3)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
+            "max"
+        )))
+    );
+}
+
+#[test]
+fn test_builtin_functions_resolved_in_tree() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    let mut tree = build_operator_tree::<DefaultNumericTypes>("math::sin(x)").unwrap();
+
+    for i in 0..10 {
+        let x = i as f64 / 2.0;
+        context.set_value("x".into(), Value::Float(x)).unwrap();
+        assert_eq!(tree.eval_with_context(&context), Ok(Value::Float(x.sin())));
+        assert_eq!(
+            tree.eval_with_context_mut(&mut context),
+            Ok(Value::Float(x.sin()))
+        );
+    }
+
+    // Functions in the context still take precedence over resolved builtin functions.
+    context
+        .set_function(
+            "math::sin".into(),
+            Function::new(|_| Ok(Value::from_int(7))),
+        )
+        .unwrap();
+    assert_eq!(tree.eval_with_context(&context), Ok(Value::from_int(7)));
+
+    // Disabling builtin functions also applies to resolved builtin functions.
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("x".into(), Value::Float(1.0)).unwrap();
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        tree.eval_with_context(&context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "math::sin".into()
+        ))
+    );
+    context.set_builtin_functions_disabled(false).unwrap();
+
+    // Changing the identifier of a function resolves the new builtin function.
+    for operator in tree.iter_operators_mut() {
+        if let Operator::FunctionIdentifier { identifier } = operator {
+            *identifier = "math::cos".into();
+        }
+    }
+    assert_eq!(
+        tree.eval_with_context(&context),
+        Ok(Value::Float(1.0f64.cos()))
+    );
+}
+
+#[test]
+fn test_variable_identifier_suggestions() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_value("client".into(), Value::from_int(1))
+        .unwrap();
+    context
+        .set_value("server".into(), Value::from_int(2))
+        .unwrap();
+
+    assert_eq!(
+        context.known_identifiers().len(),
+        2,
+        "HashMapContext should know its variables"
+    );
+    let error = eval_with_context("clinet + 1", &context).unwrap_err();
+    assert_eq!(
+        error,
+        EvalexprError::VariableIdentifierNotFound("clinet".into())
+    );
+    assert_eq!(error.suggestion(&context), Some("client".into()));
+    assert_eq!(
+        eval_with_context_mut("serer", &mut context)
+            .unwrap_err()
+            .suggestion(&context),
+        Some("server".into())
+    );
+    // Identifiers that are not similar to any known identifier get no suggestion.
+    assert_eq!(
+        eval_with_context("database", &context)
+            .unwrap_err()
+            .suggestion(&context),
+        None
+    );
+    // Contexts without known identifiers never suggest anything.
+    let empty_context = EmptyContext::<DefaultNumericTypes>::default();
+    assert_eq!(
+        eval_with_context("clinet", &empty_context)
+            .unwrap_err()
+            .suggestion(&empty_context),
+        None
+    );
+    // Missing variables that are handled by the null-coalescing operator are not reported at all.
+    assert_eq!(
+        eval_with_context("clinet ?? 0", &context),
+        Ok(Value::from_int(0))
+    );
+}
+
+#[test]
+fn test_function_identifier_suggestions() {
+    let builtin_context = HashMapContext::<DefaultNumericTypes>::new();
+    let error = eval("maxx(1, 2)").unwrap_err();
+    assert_eq!(
+        error,
+        EvalexprError::FunctionIdentifierNotFound("maxx".into())
+    );
+    assert_eq!(error.suggestion(&builtin_context), Some("max".into()));
+    assert_eq!(
+        eval("math::sine(1)")
+            .unwrap_err()
+            .suggestion(&builtin_context),
+        Some("math::sin".into())
+    );
+    assert_eq!(
+        eval("frobnicate(1)")
+            .unwrap_err()
+            .suggestion(&builtin_context),
+        None
+    );
+
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_function(
+            "frobnicate".into(),
+            Function::new(|argument| Ok(argument.clone())),
+        )
+        .unwrap();
+    assert_eq!(
+        context.known_function_identifiers(),
+        vec!["frobnicate".to_string()]
+    );
+    let error = eval_with_context("frobnicat(1)", &context).unwrap_err();
+    assert_eq!(
+        error,
+        EvalexprError::FunctionIdentifierNotFound("frobnicat".into())
+    );
+    assert_eq!(error.suggestion(&context), Some("frobnicate".into()));
+
+    // Builtin functions are not suggested if they are disabled.
+    context.set_builtin_functions_disabled(true).unwrap();
+    let error = eval_with_context("maxx(1, 2)", &context).unwrap_err();
+    assert_eq!(
+        error,
+        EvalexprError::FunctionIdentifierNotFound("maxx".into())
+    );
+    assert_eq!(error.suggestion(&context), None);
+    assert_eq!(
+        eval_with_context("frobnicat(1)", &context)
+            .unwrap_err()
+            .suggestion(&context),
+        Some("frobnicate".into())
+    );
+}
+
+#[test]
+fn test_newline_as_semicolon() {
+    let config = ParseConfig::new().with_newline_as_semicolon(true);
+    let build =
+        |string: &str| build_operator_tree_with_config::<DefaultNumericTypes>(string, &config);
+
+    assert_eq!(
+        build("a = 1\na + 1").unwrap().eval(),
+        Ok(Value::from_int(2))
+    );
+    assert_eq!(
+        build("a = 1\na + 1").unwrap(),
+        build_operator_tree::<DefaultNumericTypes>("a = 1; a + 1").unwrap()
+    );
+    // Trailing and repeated newlines do not create empty expressions.
+    assert_eq!(
+        build("\n\na = 1\n\n\r\nb = 2;\n\na + b\n\n")
+            .unwrap()
+            .eval(),
+        Ok(Value::from_int(3))
+    );
+    assert_eq!(build("a = 1\na + 1;\n").unwrap().eval(), Ok(Value::Empty));
+    // Newlines after incomplete expressions and within parentheses are ignored.
+    assert_eq!(
+        build("a = 1 +\n 2\nb = max(\n a,\n 5\n)\n(a,\nb)")
+            .unwrap()
+            .eval(),
+        Ok(Value::from(vec![3, 5]))
+    );
+    // Newlines in strings and comments are not affected.
+    assert_eq!(
+        build("a = \"x\ny\" // comment\n/* multi\nline */ a")
+            .unwrap()
+            .eval(),
+        Ok(Value::from("x\ny"))
+    );
+
+    // Without the flag, newlines are whitespace.
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("a = 1\na + 1"),
+        Err(EvalexprError::AppendedToLeafNode)
+    );
+    assert_eq!(eval("1\n+ 2"), Ok(Value::from_int(3)));
+    assert_eq!(
+        build_operator_tree_with_config::<DefaultNumericTypes>("1\n+ 2", &ParseConfig::new())
+            .unwrap()
+            .eval(),
+        Ok(Value::from_int(3))
+    );
+    assert_eq!(build("1\n-2").unwrap().eval(), Ok(Value::from_int(-2)));
+}
+
+#[test]
+fn test_configurable_boolean_literals() {
+    let config = ParseConfig::new()
+        .with_boolean_literal("yes", true)
+        .with_boolean_literal("no", false)
+        .with_boolean_literal("TRUE", true);
+    assert_eq!(config.boolean_literal("yes"), Some(true));
+    assert_eq!(config.boolean_literal("no"), Some(false));
+    assert_eq!(config.boolean_literal("Yes"), None);
+    assert_eq!(config.boolean_literal("true"), None);
+
+    let eval_with_config = |string: &str| {
+        build_operator_tree_with_config::<DefaultNumericTypes>(string, &config)
+            .and_then(|tree| tree.eval())
+    };
+    assert_eq!(eval_with_config("yes"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("no"), Ok(Value::from(false)));
+    assert_eq!(eval_with_config("yes && !no"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("if(no, 1, 2)"), Ok(Value::from_int(2)));
+    assert_eq!(eval_with_config("TRUE == true"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("true || false"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("\"yes\""), Ok(Value::from("yes")));
+    assert_eq!(
+        eval_with_config("Yes"),
+        Err(EvalexprError::VariableIdentifierNotFound("Yes".into()))
+    );
+
+    // Without the configuration, the literals are identifiers.
+    let context: HashMapContext<DefaultNumericTypes> = context_map! { "yes" => int 1 }.unwrap();
+    assert_eq!(eval_with_context("yes", &context), Ok(Value::from_int(1)));
+    assert_eq!(
+        eval("no"),
+        Err(EvalexprError::VariableIdentifierNotFound("no".into()))
+    );
+}
+
+#[test]
+fn test_keyword_operators() {
+    let config = ParseConfig::new().with_keyword_operators(true);
+    let context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "a" => true,
+        "b" => false,
+        "x" => int 5,
+    }
+    .unwrap();
+    let eval_with_config = |string: &str| {
+        build_operator_tree_with_config::<DefaultNumericTypes>(string, &config)
+            .and_then(|tree| tree.eval_with_context(&context))
+    };
+
+    assert_eq!(eval_with_config("a and b"), Ok(Value::from(false)));
+    assert_eq!(eval_with_config("a or b"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("not b"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("not a or not b"), Ok(Value::from(true)));
+    assert_eq!(
+        eval_with_config("x > 3 and x < 10 and not (x == 7)"),
+        Ok(Value::from(true))
+    );
+    assert_eq!(eval_with_config("a && not b || b"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("\"and\""), Ok(Value::from("and")));
+    assert_eq!(
+        eval_with_config("android"),
+        Err(EvalexprError::VariableIdentifierNotFound("android".into()))
+    );
+
+    // Keywords at the end of a line continue the expression on the next line.
+    let config = config.with_newline_as_semicolon(true);
+    assert_eq!(
+        build_operator_tree_with_config::<DefaultNumericTypes>(
+            "y = true and\n false\ny or\nnot y",
+            &config
+        )
+        .unwrap()
+        .eval(),
+        Ok(Value::from(true))
+    );
+
+    // Without the flag, the keywords are identifiers.
+    let context: HashMapContext<DefaultNumericTypes> = context_map! { "and" => int 1 }.unwrap();
+    assert_eq!(
+        eval_with_context("and + 1", &context),
+        Ok(Value::from_int(2))
+    );
+    assert!(eval_with_context("a and b", &context).is_err());
+}
+
+#[test]
+fn test_percent_literals() {
+    let config = ParseConfig::new().with_percent_literals(true);
+    let mut context: HashMapContext<DefaultNumericTypes> = context_map! { "x" => int 7 }.unwrap();
+    let mut eval_with_config = |string: &str| {
+        build_operator_tree_with_config::<DefaultNumericTypes>(string, &config)
+            .and_then(|tree| tree.eval_with_context_mut(&mut context))
+    };
+
+    assert_eq!(eval_with_config("5%"), Ok(Value::from_float(0.05)));
+    assert_eq!(eval_with_config("50% * 200"), Ok(Value::from_float(100.0)));
+    assert_eq!(eval_with_config("12.5%"), Ok(Value::from_float(0.125)));
+    assert_eq!(
+        eval_with_config("(10%, 20%)"),
+        Ok(Value::from(vec![
+            Value::from_float(0.1),
+            Value::from_float(0.2)
+        ]))
+    );
+    assert_eq!(eval_with_config("10%-3"), Ok(Value::from_float(0.1 - 3.0)));
+    assert_eq!(eval_with_config("5% == 0.05"), Ok(Value::from(true)));
+    // Modulo
+    assert_eq!(eval_with_config("10 % 3"), Ok(Value::from_int(1)));
+    assert_eq!(eval_with_config("10%3"), Ok(Value::from_int(1)));
+    assert_eq!(eval_with_config("10% x"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("x % 4"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("x %= 5; x"), Ok(Value::from_int(2)));
+
+    // Without the flag, the percent sign is always the modulo operator.
+    assert_eq!(
+        eval("5%"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn test_integer_division_operator() {
+    let config = ParseConfig::new().with_integer_division_operator(true);
+    let eval_with_config = |string: &str| {
+        build_operator_tree_with_config::<DefaultNumericTypes>(string, &config)
+            .and_then(|tree| tree.eval())
+    };
+
+    assert_eq!(eval_with_config("7.5 // 2 == 3"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("7.5 // 2"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("-7 // 2 == -4"), Ok(Value::from(true)));
+    assert_eq!(eval_with_config("-7 // 2"), Ok(Value::from_int(-4)));
+    assert_eq!(eval_with_config("7 // -2"), Ok(Value::from_int(-4)));
+    assert_eq!(eval_with_config("-7 // -2"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("6 // 2"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("-7.5 // 2.5"), Ok(Value::from_int(-3)));
+    assert_eq!(eval_with_config("1 // 0.5"), Ok(Value::from_int(2)));
+    // Same precedence as `*` and `/`, evaluated left to right.
+    assert_eq!(eval_with_config("2 * 7 // 4"), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_config("7 // 2 * 2"), Ok(Value::from_int(6)));
+    assert_eq!(eval_with_config("1 + 7 // 2"), Ok(Value::from_int(4)));
+    // Inline comments are still supported.
+    assert_eq!(eval_with_config("7 /* c */ // 2"), Ok(Value::from_int(3)));
+
+    assert_eq!(
+        eval_with_config("7 // 0"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from_int(7),
+            divisor: Value::from_int(0)
+        })
+    );
+    assert_eq!(
+        eval_with_config("7.5 // 0"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from_float(7.5),
+            divisor: Value::from_int(0)
+        })
+    );
+    assert_eq!(
+        eval_with_config("1e300 // 1"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from_float(1e300),
+            divisor: Value::from_int(1)
+        })
+    );
+    assert_eq!(
+        eval_with_config("-9223372036854775808.0 // 1"),
+        Ok(Value::from_int(i64::MIN))
+    );
+    assert_eq!(
+        eval_with_config("9223372036854775808.0 // 1"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from_float(9223372036854775808.0),
+            divisor: Value::from_int(1)
+        })
+    );
+    assert_eq!(
+        eval_with_config("\"a\" // 2"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+
+    let tree = build_operator_tree_with_config::<DefaultNumericTypes>("a // b", &config).unwrap();
+    assert_eq!(tree.children()[0].operator(), &Operator::IntDiv);
+    assert_eq!(tree.to_string(), "a // b");
+    // The displayed tree only parses back with the integer division operator enabled.
+    assert_eq!(
+        build_operator_tree_with_config::<DefaultNumericTypes>(&tree.to_string(), &config),
+        Ok(tree.clone())
+    );
+    assert_ne!(
+        build_operator_tree::<DefaultNumericTypes>(&tree.to_string()),
+        Ok(tree)
+    );
+    assert_eq!(
+        build_operator_tree_with_config::<DefaultNumericTypes>("1.5 // 1", &config)
+            .unwrap()
+            .result_type(),
+        Some(ValueType::Int)
+    );
+
+    // Without the flag, a double slash starts an end-of-line comment.
+    assert_eq!(eval("7 // 2"), Ok(Value::from_int(7)));
+}
+
+#[test]
+fn test_context_stack() {
+    let globals: HashMapContext<DefaultNumericTypes> = context_map! {
+        "a" => int 1,
+        "b" => int 2,
+        "f" => Function::new(|argument| Ok(Value::from_int(argument.as_int()? + 10))),
+        "g" => Function::new(|_| Ok(Value::from("global"))),
+    }
+    .unwrap();
+    let locals: HashMapContext<DefaultNumericTypes> = context_map! {
+        "b" => float 0.5,
+        "g" => Function::new(|_| Ok(Value::from("local"))),
+    }
+    .unwrap();
+
+    let mut stack = ContextStack::new();
+    assert!(stack.is_empty());
+    stack.push(globals);
+    assert_eq!(eval_with_context("a + b", &stack), Ok(Value::from_int(3)));
+    assert_eq!(eval_with_context("g()", &stack), Ok(Value::from("global")));
+
+    // Locals shadow globals.
+    stack.push(locals);
+    assert_eq!(stack.len(), 2);
+    assert_eq!(
+        eval_with_context("a + b", &stack),
+        Ok(Value::from_float(1.5))
+    );
+    assert_eq!(
+        eval_with_context("f(b)", &stack),
+        Err(EvalexprError::expected_int(Value::from_float(0.5)))
+    );
+    assert_eq!(eval_with_context("f(a)", &stack), Ok(Value::from_int(11)));
+    assert_eq!(eval_with_context("g()", &stack), Ok(Value::from("local")));
+    assert_eq!(
+        eval_with_context("max(a, b)", &stack),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(
+        eval_with_context("c", &stack),
+        Err(EvalexprError::VariableIdentifierNotFound("c".into()))
+    );
+    assert_eq!(
+        eval_with_context("h()", &stack),
+        Err(EvalexprError::FunctionIdentifierNotFound("h".into()))
+    );
+    assert_eq!(
+        eval_with_context("a = 2", &stack),
+        Err(EvalexprError::ContextNotMutable)
+    );
+
+    // Builtin functions can be disabled for the whole stack.
+    stack.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        eval_with_context("max(a, b)", &stack),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".into()))
+    );
+
+    // Removing the locals makes the globals visible again.
+    assert!(stack.pop().is_some());
+    assert_eq!(eval_with_context("b", &stack), Ok(Value::from_int(2)));
+}
+
+#[test]
+fn test_context_snapshot_restore() {
+    let mut context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "a" => int 1,
+        "s" => "text",
+    }
+    .unwrap();
+    let snapshot = context.snapshot();
+
+    assert_eq!(
+        eval_with_context_mut("a = 2; s = s + \"!\"; b = 3; a = \"x\"", &mut context),
+        Err(EvalexprError::expected_int(Value::from("x")))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::from_int(2)));
+    assert_eq!(context.get_value("b"), Some(&Value::from_int(3)));
+
+    context.restore(snapshot.clone());
+    assert_eq!(context.get_value("a"), Some(&Value::from_int(1)));
+    assert_eq!(context.get_value("s"), Some(&Value::from("text")));
+    assert_eq!(context.get_value("b"), None);
+    assert_eq!(context.snapshot(), snapshot);
+
+    // A snapshot can be restored multiple times.
+    assert_eq!(
+        eval_with_context_mut("a += 5; a", &mut context),
+        Ok(Value::from_int(6))
+    );
+    context.restore(snapshot);
+    assert_eq!(context.get_value("a"), Some(&Value::from_int(1)));
+}
+
+#[test]
+fn test_case_insensitive_context() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new_case_insensitive();
+    context
+        .set_value("Price".into(), Value::from_int(5))
+        .unwrap();
+    assert_eq!(context.get_value("price"), Some(&Value::from_int(5)));
+    assert_eq!(context.get_value("PRICE"), Some(&Value::from_int(5)));
+    assert_eq!(
+        eval_with_context_mut("price = PRICE + 1; pRiCe", &mut context),
+        Ok(Value::from_int(6))
+    );
+    assert_eq!(
+        context.iter_variable_names().collect::<Vec<_>>(),
+        vec!["Price".to_string()]
+    );
+
+    // Type safety holds across different casings.
+    assert_eq!(
+        eval_with_context_mut("PRICE = 1.5", &mut context),
+        Err(EvalexprError::expected_int(Value::from_float(1.5)))
+    );
+    assert_eq!(context.get_value("Price"), Some(&Value::from_int(6)));
+
+    context
+        .set_function(
+            "Double".into(),
+            Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
+        )
+        .unwrap();
+    assert!(context.contains_function("DOUBLE"));
+    assert_eq!(
+        eval_with_context("double(price)", &context),
+        Ok(Value::from_int(12))
+    );
+
+    let snapshot = context.snapshot();
+    context.clear_variables();
+    assert_eq!(context.get_value("price"), None);
+    context.restore(snapshot);
+    assert_eq!(context.get_value("price"), Some(&Value::from_int(6)));
+
+    // Contexts constructed with `new` stay case-sensitive.
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context
+        .set_value("Price".into(), Value::from_int(5))
+        .unwrap();
+    assert_eq!(context.get_value("price"), None);
+    assert_eq!(
+        eval_with_context_mut("price = 1.5; Price", &mut context),
+        Ok(Value::from_int(5))
+    );
+}
+
+#[test]
+fn test_dry_run_context() {
+    let mut context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "a" => int 3,
+        "f" => Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 10))),
+    }
+    .unwrap();
+
+    let mut dry_run = DryRunContext::new(&context);
+    assert_eq!(
+        eval_with_context_mut("x = 1; x + 2", &mut dry_run),
+        Ok(Value::from_int(3))
+    );
+    assert_eq!(dry_run.writes(), &[("x".to_string(), Value::from_int(1))]);
+
+    // Assigned values shadow the values of the wrapped context.
+    assert_eq!(
+        eval_with_context_mut("a += 1; a = f(a); a", &mut dry_run),
+        Ok(Value::from_int(40))
+    );
+    assert_eq!(
+        dry_run.into_writes(),
+        vec![
+            ("x".to_string(), Value::from_int(1)),
+            ("a".to_string(), Value::from_int(4)),
+            ("a".to_string(), Value::from_int(40)),
+        ]
+    );
+
+    // Failing expressions report the writes executed before the error.
+    let mut dry_run = DryRunContext::new(&context);
+    assert_eq!(
+        eval_with_context_mut("b = 2; b + c", &mut dry_run),
+        Err(EvalexprError::VariableIdentifierNotFound("c".into()))
+    );
+    assert_eq!(dry_run.writes(), &[("b".to_string(), Value::from_int(2))]);
+
+    assert_eq!(context.get_value("x"), None);
+    assert_eq!(context.get_value("a"), Some(&Value::from_int(3)));
+    assert_eq!(context.get_value("b"), None);
+    context.set_builtin_functions_disabled(true).unwrap();
+    let mut dry_run = DryRunContext::new(&context);
+    assert_eq!(
+        dry_run.set_builtin_functions_disabled(false),
+        Err(EvalexprError::ContextNotMutable)
+    );
+}
+
+#[test]
+fn test_infer_type() {
+    let context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "i" => int 1,
+        "f" => float 1.5,
+        "s" => "abc",
+        "b" => true,
+        "g" => Function::new(|argument| Ok(argument.clone())),
+    }
+    .unwrap();
+    let infer = |expression: &str| {
+        build_operator_tree::<DefaultNumericTypes>(expression)
+            .unwrap()
+            .infer_type(&context)
+    };
+
+    assert_eq!(infer(""), Ok(ValueType::Empty));
+    assert_eq!(infer("i + i"), Ok(ValueType::Int));
+    assert_eq!(infer("i * (i - 3) % 2"), Ok(ValueType::Int));
+    assert_eq!(infer("i + f"), Ok(ValueType::Float));
+    assert_eq!(infer("-f / i"), Ok(ValueType::Float));
+    assert_eq!(infer("i ^ i"), Ok(ValueType::Float));
+    assert_eq!(infer("s + \"def\""), Ok(ValueType::String));
+    assert_eq!(infer("i < f && s >= \"a\" || !b"), Ok(ValueType::Boolean));
+    assert_eq!(infer("s == i"), Ok(ValueType::Boolean));
+    assert_eq!(infer("(i, s)"), Ok(ValueType::Tuple));
+    assert_eq!(infer("b ? i : i * 2"), Ok(ValueType::Int));
+    assert_eq!(infer("{i, s}"), Ok(ValueType::Array));
+    assert_eq!(infer("s[i]"), Ok(ValueType::String));
+
+    // Type errors are detected without evaluating.
+    assert_eq!(
+        infer("i - s"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Sub,
+            vec![ValueType::Int, ValueType::String]
+        ))
+    );
+    assert_eq!(
+        infer("s > 1"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Gt,
+            vec![ValueType::String, ValueType::Int]
+        ))
+    );
+    assert_eq!(
+        infer("!i"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Not,
+            vec![ValueType::Int]
+        ))
+    );
+    assert_eq!(
+        infer("x + 1"),
+        Err(EvalexprError::VariableIdentifierNotFound("x".into()))
+    );
+    assert_eq!(
+        infer("i ? 1 : 2"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Conditional,
+            vec![ValueType::Int]
+        ))
+    );
+    assert_eq!(
+        infer("i[0]"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Index,
+            vec![ValueType::Int]
+        ))
+    );
+    assert_eq!(
+        infer("s[f]"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Index,
+            vec![ValueType::String, ValueType::Float]
+        ))
+    );
+
+    // Function calls and assignments have unknown types.
+    assert_eq!(
+        infer("g(i) + 1"),
+        Err(EvalexprError::TypeUnknown {
+            operator: Operator::FunctionIdentifier {
+                identifier: "g".into()
+            }
+        })
+    );
+    assert_eq!(
+        infer("i = 2"),
+        Err(EvalexprError::TypeUnknown {
+            operator: Operator::Assign
+        })
+    );
+    // The type of a conditional is only known if both branches have the same type.
+    assert_eq!(
+        infer("b ? i : s"),
+        Err(EvalexprError::TypeUnknown {
+            operator: Operator::Conditional
+        })
+    );
+    // The same holds for the elements of tuples and arrays.
+    assert_eq!(
+        infer("(i, s)[0]"),
+        Err(EvalexprError::TypeUnknown {
+            operator: Operator::Index
+        })
+    );
+    // Unless the operator determines the type regardless of its arguments.
+    assert_eq!(infer("g(i) > 1"), Ok(ValueType::Boolean));
+    assert_eq!(infer("!g(b)"), Ok(ValueType::Boolean));
+    assert_eq!(infer("g(i) ^ 2"), Ok(ValueType::Float));
+    // Inference does not evaluate the expression.
+    assert_eq!(infer("i / 0"), Ok(ValueType::Int));
+}
+
+#[test]
+fn test_result_type() {
+    let result_type = |expression: &str| {
+        build_operator_tree::<DefaultNumericTypes>(expression)
+            .unwrap()
+            .result_type()
+    };
+
+    assert_eq!(result_type("1 < 2"), Some(ValueType::Boolean));
+    assert_eq!(result_type("42"), Some(ValueType::Int));
+    assert_eq!(result_type("\"abc\""), Some(ValueType::String));
+    assert_eq!(result_type("()"), Some(ValueType::Empty));
+    assert_eq!(result_type("1 + 2 * 3"), Some(ValueType::Int));
+    assert_eq!(result_type("1 + 2.5"), Some(ValueType::Float));
+    assert_eq!(result_type("2 ^ 2"), Some(ValueType::Float));
+    assert_eq!(result_type("\"a\" + \"b\""), Some(ValueType::String));
+    assert_eq!(result_type("-(1.5)"), Some(ValueType::Float));
+    assert_eq!(result_type("true && !false"), Some(ValueType::Boolean));
+
+    // Comparisons are booleans even if they depend on variables.
+    assert_eq!(result_type("a < 2"), Some(ValueType::Boolean));
+    assert_eq!(result_type("a == b"), Some(ValueType::Boolean));
+
+    // Types that depend on variables or function calls are unknown.
+    assert_eq!(result_type("a"), None);
+    assert_eq!(result_type("a + 1"), None);
+    assert_eq!(result_type("a ?? 1"), None);
+    assert_eq!(result_type("a ? 1 : 2"), Some(ValueType::Int));
+    assert_eq!(result_type("a ? 1 : 2.0"), None);
+    assert_eq!(result_type("max(1, 2)"), None);
+    assert_eq!(result_type("a = 1"), None);
+
+    // Ill-typed expressions have no type.
+    assert_eq!(result_type("1 + true"), None);
+    assert_eq!(result_type("\"a\" < 1"), None);
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(eval("0x3"), Ok(Value::Int(3)));
+    assert_eq!(eval("0xFF"), Ok(Value::Int(255)));
+    assert_eq!(eval("-0xFF"), Ok(Value::Int(-255)));
+    assert_eq!(
+        eval("0x"),
+        // The "VariableIdentifierNotFound" error is what evalexpr currently returns,
+        // but ideally it would return more specific errors for "illegal" literals.
+        Err(EvalexprError::VariableIdentifierNotFound("0x".into()))
+    );
+}
+
+#[test]
+fn test_radix_and_separated_int_literals() {
+    assert_eq!(eval("0X1f"), Ok(Value::Int(31)));
+    assert_eq!(eval("0o755"), Ok(Value::Int(0o755)));
+    assert_eq!(eval("0O17"), Ok(Value::Int(15)));
+    assert_eq!(eval("0b1010"), Ok(Value::Int(10)));
+    assert_eq!(eval("0B11"), Ok(Value::Int(3)));
+    assert_eq!(eval("-0b101"), Ok(Value::Int(-5)));
+    assert_eq!(eval("0xFF_FF"), Ok(Value::Int(0xFFFF)));
+    assert_eq!(eval("0b1111_0000"), Ok(Value::Int(0b1111_0000)));
+    assert_eq!(eval("1_000_000"), Ok(Value::Int(1_000_000)));
+    assert_eq!(eval("1_000.5"), Ok(Value::Float(1_000.5)));
+    assert_eq!(eval("1_000.0_5"), Ok(Value::Float(1_000.05)));
+    assert_eq!(eval("1_000e-3"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("1_0e1_0"), Ok(Value::Float(1e11)));
+    assert_eq!(eval("0b1010 + 0o10 + 0x10"), Ok(Value::Int(34)));
+    assert_eq!(eval("bitand(0xFF, 0b1010)"), Ok(Value::Int(10)));
+    assert_eq!(
+        <i64 as EvalexprInt<DefaultNumericTypes>>::from_hex_str("1f"),
+        Ok(31)
+    );
+    for literal in ["0b", "0o", "0b2", "0o8", "0xG", "0b_"] {
+        assert_eq!(
+            eval(literal),
+            Err(EvalexprError::VariableIdentifierNotFound(literal.into()))
+        );
+    }
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("_1".into(), Value::from_int(7)).unwrap();
+    assert_eq!(eval_with_context("_1", &context), Ok(Value::Int(7)));
+}
+
+#[test]
+fn test_broken_string() {
+    assert_eq!(
+        eval(r#""abc" == "broken string"#),
+        Err(EvalexprError::UnmatchedDoubleQuote)
+    );
+}
+
+#[test]
+fn test_comments() {
+    assert_eq!(
+        eval(
+            "
+            // input
+            a = 1;  // assignment
+            // output
+            a + 2  // add"
+        ),
+        Ok(Value::Int(3))
+    );
+
+    assert_eq!(
+        eval("0 /*"),
+        Err(EvalexprError::CustomMessage(
+            "unmatched inline comment".into()
+        ))
+    );
+
+    assert_eq!(
+        eval("1 % 4 + /*inline comment*/ 6 /*END*/"),
+        Ok(Value::Int(7))
+    );
+
+    assert_eq!(
+        eval("/* begin */ 10 /* middle */ + 5 /* end */ + 6 // DONE"),
+        Ok(Value::Int(21))
+    );
+}
+
+#[test]
+fn test_clear() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("abc".into(), "def".into()).unwrap();
+    assert_eq!(context.get_value("abc"), Some(&("def".into())));
+    context.clear_functions();
+    assert_eq!(context.get_value("abc"), Some(&("def".into())));
+    context.clear_variables();
+    assert_eq!(context.get_value("abc"), None);
+
+    context
+        .set_function(
+            "abc".into(),
+            Function::new(|input| Ok(Value::String(format!("{input}")))),
+        )
+        .unwrap();
+    assert_eq!(
+        eval_with_context("abc(5)", &context).unwrap(),
+        Value::String("5".into())
+    );
+    context.clear_variables();
+    assert_eq!(
+        eval_with_context("abc(5)", &context).unwrap(),
+        Value::String("5".into())
+    );
+    context.clear_functions();
+    assert!(eval_with_context("abc(5)", &context).is_err());
+
+    context
+        .set_value("five".into(), Value::from_int(5))
+        .unwrap();
+    context
+        .set_function(
+            "abc".into(),
+            Function::new(|input| Ok(Value::String(format!("{input}")))),
+        )
+        .unwrap();
+    assert_eq!(
+        eval_with_context("abc(five)", &context).unwrap(),
+        Value::String("5".into())
+    );
+    context.clear();
+    assert!(context.get_value("five").is_none());
+    assert!(eval_with_context("abc(5)", &context).is_err());
+}
+
+#[test]
+fn test_iter_empty_contexts() {
+    assert_eq!(
+        EmptyContext::<DefaultNumericTypes>::default()
+            .iter_variables()
+            .next(),
+        None
+    );
+    assert_eq!(
+        EmptyContext::<DefaultNumericTypes>::default()
+            .iter_variable_names()
+            .next(),
+        None
+    );
+    assert_eq!(
+        EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
+            .iter_variables()
+            .next(),
+        None
+    );
+    assert_eq!(
+        EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
+            .iter_variable_names()
+            .next(),
+        None
+    );
+}
+
+#[test]
+fn test_empty_context_builtin_functions() {
+    assert!(EmptyContext::<DefaultNumericTypes>::default().are_builtin_functions_disabled());
+    assert!(
+        !EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
+            .are_builtin_functions_disabled()
+    );
+}
+
+#[test]
+fn test_compare_different_numeric_types() {
+    assert_eq!(eval("1 < 2.0"), Ok(true.into()));
+    assert_eq!(eval("1 >= 2"), Ok(false.into()));
+    assert_eq!(eval("1 >= 2.0"), Ok(false.into()));
+}
+
+#[test]
+fn test_escape_sequences() {
+    assert_eq!(
+        eval("\"\\x\""),
+        Err(EvalexprError::IllegalEscapeSequence("\\x".to_string()))
+    );
+    assert_eq!(
+        eval("\"\\"),
+        Err(EvalexprError::IllegalEscapeSequence("\\".to_string()))
+    );
+    assert_eq!(eval("\"a\\\"b\\\\c\""), Ok(Value::from("a\"b\\c")));
+    assert_eq!(
+        eval("\"a\\nb\\tc\\rd\\0\""),
+        Ok(Value::from("a\nb\tc\rd\0"))
+    );
+    assert_eq!(eval("\"\\u{41}\\u{e4}\""), Ok(Value::from("Aä")));
+    assert_eq!(eval("\"\\u{1F600}\""), Ok(Value::from("😀")));
+    assert_eq!(
+        eval("\"\\u{0010FFFF}\""),
+        Err(EvalexprError::IllegalEscapeSequence(
+            "\\u{0010FFFF}".to_string()
+        ))
+    );
+    for (expression, sequence) in [
+        ("\"\\u{110000}\"", "\\u{110000}"),
+        ("\"\\u{D800}\"", "\\u{D800}"),
+        ("\"\\u{}\"", "\\u{}"),
+        ("\"\\u{41\"", "\\u{41\""),
+        ("\"\\u{4g}\"", "\\u{4g"),
+        ("\"\\u{+41}\"", "\\u{+"),
+        ("\"\\u41\"", "\\u4"),
+        ("\"\\u", "\\u"),
+    ] {
+        assert_eq!(
+            eval(expression),
+            Err(EvalexprError::IllegalEscapeSequence(sequence.to_string())),
+            "{expression}"
+        );
+    }
+}
+
+#[test]
+fn test_single_quoted_string_literals() {
+    assert_eq!(eval("'foo'"), Ok(Value::from("foo")));
+    assert_eq!(eval("'foo' == \"foo\""), Ok(Value::from(true)));
+    assert_eq!(eval("''"), Ok(Value::from("")));
+    assert_eq!(eval("\"don't\""), Ok(Value::from("don't")));
+    assert_eq!(eval("'say \"hi\"'"), Ok(Value::from("say \"hi\"")));
+    assert_eq!(eval("'don\\'t'"), Ok(Value::from("don't")));
+    assert_eq!(eval("\"don\\'t\""), Ok(Value::from("don't")));
+    assert_eq!(
+        eval("'a\\tb\\\\c\\u{e4}'"),
+        Ok(Value::from("a\tb\\c\u{e4}"))
+    );
+    assert_eq!(eval("str::to_uppercase('a') + 'b'"), Ok(Value::from("Ab")));
+    assert_eq!(eval("'abc"), Err(EvalexprError::UnmatchedSingleQuote));
+    assert_eq!(eval("'abc\""), Err(EvalexprError::UnmatchedSingleQuote));
+    assert_eq!(eval("\"abc'"), Err(EvalexprError::UnmatchedDoubleQuote));
+}
+
+#[test]
+fn test_multi_line_string_literals() {
+    assert_eq!(
+        eval("\"Dear {name},\n  thanks!\""),
+        Ok(Value::from("Dear {name},\n  thanks!"))
+    );
+    assert_eq!(eval("\"a\nb\" + \"\n\""), Ok(Value::from("a\nb\n")));
+    assert_eq!(eval("\"a long \\\nline\""), Ok(Value::from("a long line")));
+    assert_eq!(eval("\"a\\\n\\\nb\""), Ok(Value::from("ab")));
+    assert_eq!(
+        eval("\"a long \\\r\nline\""),
+        Ok(Value::from("a long line"))
+    );
+    assert_eq!(
+        eval("\"a\\\rb\""),
+        Err(EvalexprError::IllegalEscapeSequence("\\\rb".into()))
+    );
+    assert_eq!(eval("\"a\\\\\nb\""), Ok(Value::from("a\\\nb")));
+    assert_eq!(eval("\"a\nb"), Err(EvalexprError::UnmatchedDoubleQuote));
+}
+
+#[test]
+fn test_coalesce_operator() {
+    let mut context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "present" => int 1,
+        "nothing" => Value::Empty,
+        "fail" => Function::new(|_| Err(EvalexprError::CustomMessage("fail".into()))),
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval_with_context("present ?? 0", &context),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(
+        eval_with_context("missing ?? 0", &context),
+        Ok(Value::from_int(0))
+    );
+    assert_eq!(
+        eval_with_context("nothing ?? 0", &context),
+        Ok(Value::from_int(0))
+    );
+    assert_eq!(
+        eval_with_context("missing ?? nothing ?? present", &context),
+        Ok(Value::from_int(1))
+    );
+    // Suggestions of similar identifiers do not prevent the fallback.
+    assert_eq!(
+        eval_with_context("presnt ?? 2", &context),
+        Ok(Value::from_int(2))
+    );
+    assert_eq!(
+        eval_with_context("missing + 1 ?? 0", &context),
+        Ok(Value::from_int(0))
+    );
+
+    // The coalescing operator binds weaker than the logical operators, but stronger than the conditional.
+    assert_eq!(
+        eval_with_context("missing ?? 1 + 2", &context),
+        Ok(Value::from_int(3))
+    );
+    assert_eq!(
+        eval_with_context("missing ?? false || true ? 1 : 2", &context),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(
+        eval_with_context("true ? missing ?? 1 : 2", &context),
+        Ok(Value::from_int(1))
+    );
+
+    // The fallback is only evaluated if it is needed.
+    assert_eq!(
+        eval_with_context_mut("a = 0; present ?? (a = 1); a", &mut context),
+        Ok(Value::from_int(0))
+    );
+    assert_eq!(
+        eval_with_context_mut("a = 0; missing ?? (a = 1); a", &mut context),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(
+        eval_with_context("present ?? 1 / 0", &context),
+        Ok(Value::from_int(1))
+    );
+
+    // Other errors are propagated.
+    assert_eq!(
+        eval_with_context("fail() ?? 0", &context),
+        Err(EvalexprError::CustomMessage("fail".into()))
+    );
+    assert_eq!(
+        eval_with_context("unknown() ?? 0", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("unknown".into()))
+    );
+    assert_eq!(
+        eval_with_context("missing ?? also_missing", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "also_missing".into()
+        ))
+    );
+
+    assert_eq!(
+        tokenize::<DefaultNumericTypes>("a??b"),
+        Ok(vec![
+            Token::Identifier("a".into()),
+            Token::Coalesce,
+            Token::Identifier("b".into())
+        ])
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("missing ?? 1.5")
+            .unwrap()
+            .infer_type(&context),
+        Ok(ValueType::Float)
+    );
+}
+
+#[test]
+fn test_conditional_operator() {
+    assert_eq!(eval("true ? 1 : 2"), Ok(Value::from_int(1)));
+    assert_eq!(eval("false ? 1 : 2"), Ok(Value::from_int(2)));
+    assert_eq!(eval("x = -3; x > 0 ? x : -x"), Ok(Value::from_int(3)));
+    assert_eq!(eval("true?\"a\":\"b\""), Ok(Value::from("a")));
+    assert_eq!(
+        eval("math::abs(-1) == 1 ? str::to_uppercase(\"a\") : \"b\""),
+        Ok(Value::from("A"))
+    );
+
+    // The conditional binds weaker than the logical operators, but stronger than assignments and sequences.
+    assert_eq!(
+        eval("1 < 2 && 2 < 1 || true ? 1 : 2"),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(eval("false ? 1 : 2 + 3"), Ok(Value::from_int(5)));
+    assert_eq!(eval("x = false ? 1 : 2; x"), Ok(Value::from_int(2)));
+    assert_eq!(
+        eval("true ? 1 : 2, 3"),
+        Ok(Value::from(vec![Value::from_int(1), Value::from_int(3)]))
+    );
+    assert_eq!(
+        eval("true ? 1, 2 : 3"),
+        Ok(Value::from(vec![Value::from_int(1), Value::from_int(2)]))
+    );
+    assert_eq!(eval("max(1, false ? 5 : 4, 3)"), Ok(Value::from_int(4)));
+
+    // Chained conditionals nest to the right.
+    for (x, expected) in [(-5, "negative"), (0, "zero"), (5, "positive")] {
+        let context: HashMapContext<DefaultNumericTypes> = context_map! { "x" => int x }.unwrap();
+        assert_eq!(
+            eval_with_context(
+                "x < 0 ? \"negative\" : x == 0 ? \"zero\" : \"positive\"",
+                &context
+            ),
+            Ok(Value::from(expected))
+        );
+    }
+    assert_eq!(eval("true ? false ? 1 : 2 : 3"), Ok(Value::from_int(2)));
+    assert_eq!(eval("false ? false ? 1 : 2 : 3"), Ok(Value::from_int(3)));
+
+    // Only the selected branch is evaluated.
+    assert_eq!(
+        eval("a = 0; b = 0; true ? (a = 1) : (b = 1); (a, b)"),
+        Ok(Value::from(vec![Value::from_int(1), Value::from_int(0)]))
+    );
+    assert_eq!(
+        eval("a = 0; b = 0; false ? (a = 1) : (b = 1); (a, b)"),
+        Ok(Value::from(vec![Value::from_int(0), Value::from_int(1)]))
+    );
+    assert_eq!(eval("true ? 1 : 1 / 0"), Ok(Value::from_int(1)));
+    assert_eq!(eval("false ? unknown() : 2"), Ok(Value::from_int(2)));
+    let context = HashMapContext::<DefaultNumericTypes>::new();
+    assert_eq!(
+        eval_with_context("true ? 1 : unknown", &context),
+        Ok(Value::from_int(1))
+    );
+
+    assert_eq!(
+        eval("1 ? 2 : 3"),
+        Err(EvalexprError::expected_boolean(Value::from_int(1)))
+    );
+    assert_eq!(
+        eval("true ? 1"),
+        Err(EvalexprError::UnmatchedQuestionMark { span: Some(5..6) })
+    );
+    assert_eq!(
+        eval("(true ? 1) : 2"),
+        Err(EvalexprError::UnmatchedQuestionMark { span: Some(6..7) })
+    );
+    assert_eq!(
+        eval("1 : 2"),
+        Err(EvalexprError::UnmatchedColon { span: Some(2..3) })
+    );
+    assert_eq!(
+        eval("true ? (1 : 2)"),
+        Err(EvalexprError::UnmatchedColon { span: Some(10..11) })
+    );
+}
+
+#[test]
+fn test_arrays() {
+    let int = Value::<DefaultNumericTypes>::from_int;
+    assert_eq!(
+        eval("{1, 2, 3}"),
+        Ok(Value::Array(vec![int(1), int(2), int(3)]))
+    );
+    assert_eq!(eval("{1}"), Ok(Value::Array(vec![int(1)])));
+    assert_eq!(eval("{}"), Ok(Value::Array(vec![])));
+    assert_eq!(
+        eval("{1 + 2, \"a\"}"),
+        Ok(Value::Array(vec![int(3), Value::from("a")]))
+    );
+    assert_eq!(eval_array("{true}"), Ok(vec![Value::from(true)]));
+    assert_eq!(
+        eval_array("(1, 2)"),
+        Err(EvalexprError::expected_array(Value::from(vec![
+            int(1),
+            int(2)
+        ])))
+    );
+
+    // Tuples and arrays within an array are not flattened into it.
+    assert_eq!(
+        eval("{(1, 2)}"),
+        Ok(Value::Array(vec![Value::from(vec![int(1), int(2)])]))
+    );
+    assert_eq!(
+        eval("{{1, 2}, {}}"),
+        Ok(Value::Array(vec![
+            Value::Array(vec![int(1), int(2)]),
+            Value::Array(vec![])
+        ]))
+    );
+    assert_eq!(
+        eval("{1, 2}, 3"),
+        Ok(Value::from(vec![
+            Value::Array(vec![int(1), int(2)]),
+            int(3)
+        ]))
+    );
+
+    // Arrays are passed to functions as a single argument.
+    assert_eq!(eval("len({1, 2, 3})"), Ok(int(3)));
+    assert_eq!(eval("len({(1, 2)})"), Ok(int(1)));
+    assert_eq!(eval("typeof({1, 2})"), Ok(Value::from("array")));
+    assert_eq!(
+        eval("max({1, 2})"),
+        Err(EvalexprError::expected_tuple(Value::Array(vec![
+            int(1),
+            int(2)
+        ])))
+    );
+
+    assert_eq!(eval("a = {1, 2}; a == {1, 2}"), Ok(Value::from(true)));
+    assert_eq!(eval("{1, 2} == (1, 2)"), Ok(Value::from(false)));
+    assert_eq!(eval("true ? {1} : {}"), Ok(Value::Array(vec![int(1)])));
+    assert_eq!(
+        eval("{1, 2}").map(|value| value.to_string()),
+        Ok("{1, 2}".to_string())
+    );
+
+    assert_eq!(
+        eval("{1, 2"),
+        Err(EvalexprError::UnmatchedLCurlyBrace { span: Some(0..1) })
+    );
+    assert_eq!(
+        eval("1, 2}"),
+        Err(EvalexprError::UnmatchedRCurlyBrace { span: Some(4..5) })
+    );
+    assert_eq!(
+        eval("{1, 2)"),
+        Err(EvalexprError::UnmatchedRBrace { span: Some(5..6) })
+    );
+    assert_eq!(
+        eval("(1, 2}"),
+        Err(EvalexprError::UnmatchedRCurlyBrace { span: Some(5..6) })
+    );
+    assert_eq!(
+        eval("{true ? 1}"),
+        Err(EvalexprError::UnmatchedQuestionMark { span: Some(6..7) })
+    );
+}
+
+#[test]
+fn test_index_operator() {
+    let int = Value::<DefaultNumericTypes>::from_int;
+    let context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "t" => Value::from(vec![int(1), Value::from("b"), Value::from(vec![int(3), int(4)])]),
+        "s" => "häll",
+        "i" => int 1
+    }
+    .unwrap();
+
+    assert_eq!(eval_with_context("t[0]", &context), Ok(int(1)));
+    assert_eq!(eval_with_context("t[i]", &context), Ok(Value::from("b")));
+    assert_eq!(eval_with_context("t[i + 1][1]", &context), Ok(int(4)));
+    assert_eq!(eval_with_context("s[1]", &context), Ok(Value::from("ä")));
+    assert_eq!(eval_with_context("s[3]", &context), Ok(Value::from("l")));
+    assert_eq!(eval("{5, 6, 7}[2]"), Ok(int(7)));
+    assert_eq!(eval("(5, 6)[1]"), Ok(int(6)));
+    assert_eq!(eval("\"abc\"[0]"), Ok(Value::from("a")));
+    assert_eq!(eval("str::to_uppercase(\"abc\")[2]"), Ok(Value::from("C")));
+
+    // Indexing binds as strongly as function application.
+    assert_eq!(eval_with_context("t[0] + 2 * t[0]", &context), Ok(int(3)));
+    assert_eq!(eval_with_context("-t[0]", &context), Ok(int(-1)));
+    assert_eq!(eval_with_context("len(t[2])", &context), Ok(int(2)));
+    assert_eq!(
+        eval_with_context("t[2][0], t[2][1]", &context),
+        Ok(Value::from(vec![int(3), int(4)]))
+    );
+
+    assert_eq!(
+        eval_with_context("t[3]", &context),
+        Err(EvalexprError::OutOfBoundsAccess)
+    );
+    assert_eq!(
+        eval_with_context("s[4]", &context),
+        Err(EvalexprError::OutOfBoundsAccess)
+    );
+    assert_eq!(
+        eval_with_context("t[-1]", &context),
+        Err(EvalexprError::IntIntoUsize { int: -1 })
+    );
+    assert_eq!(
+        eval_with_context("t[1.0]", &context),
+        Err(EvalexprError::expected_int(Value::from_float(1.0)))
+    );
+    assert_eq!(
+        eval("5[0]"),
+        Err(EvalexprError::type_error(
+            int(5),
+            vec![ValueType::Tuple, ValueType::Array, ValueType::String]
+        ))
+    );
+
+    assert_eq!(
+        eval("(1, 2)[0"),
+        Err(EvalexprError::UnmatchedLBracket { span: Some(6..7) })
+    );
+    assert_eq!(
+        eval("(1, 2) + 0]"),
+        Err(EvalexprError::UnmatchedRBracket { span: Some(10..11) })
+    );
+    assert_eq!(
+        eval("(1, 2)[(0]"),
+        Err(EvalexprError::UnmatchedRBracket { span: Some(9..10) })
+    );
+    assert_eq!(
+        eval("(1, 2)[0)"),
+        Err(EvalexprError::UnmatchedRBrace { span: Some(8..9) })
+    );
+    assert_eq!(
+        eval("{1, 2[0}"),
+        Err(EvalexprError::UnmatchedRCurlyBrace { span: Some(7..8) })
+    );
+    assert_eq!(
+        eval("t[true ? 0]"),
+        Err(EvalexprError::UnmatchedQuestionMark { span: Some(7..8) })
+    );
+}
+
+#[test]
+fn test_bitwise_operators() {
+    assert_eq!(eval("6 & 5"), Ok(Value::from_int(4)));
+    assert_eq!(eval("6 | 5"), Ok(Value::from_int(7)));
+    assert_eq!(eval("5 << 1"), Ok(Value::from_int(10)));
+    assert_eq!(eval("-6 >> 5"), Ok(Value::from_int(-1)));
+    assert_eq!(eval("-1 >> 63"), Ok(Value::from_int(-1)));
+    assert_eq!(
+        eval("1 << 64"),
+        Err(EvalexprError::ShiftError {
+            value: Value::from_int(1),
+            amount: Value::from_int(64)
         })
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Boolean(true).as_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Boolean(true)
+        eval("1 >> -1"),
+        Err(EvalexprError::ShiftError {
+            value: Value::from_int(1),
+            amount: Value::from_int(-1)
         })
     );
     assert_eq!(
-        Value::<DefaultNumericTypes>::Tuple(Vec::new()).as_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(Vec::new())
+        eval("1 << 9223372036854775807"),
+        Err(EvalexprError::ShiftError {
+            value: Value::from_int(1),
+            amount: Value::from_int(i64::MAX)
         })
     );
-    assert_eq!(Value::<DefaultNumericTypes>::Empty.as_empty(), Ok(()));
+    assert_eq!(eval("6&5|8"), Ok(Value::from_int(12)));
+
+    // Single and double characters are distinguished.
+    assert_eq!(eval("true && false || true"), Ok(Value::from(true)));
+    assert_eq!(eval("a = true; a &&= false; a"), Ok(Value::from(false)));
+    assert_eq!(eval("a = false; a ||= true; a"), Ok(Value::from(true)));
+    assert_eq!(eval("3 & 1 == 1 && 3 | 4 == 7"), Ok(Value::from(true)));
+    assert_eq!(eval("1 <= 2 && 2 >= 1"), Ok(Value::from(true)));
+
+    // Shifts bind weaker than arithmetic, and bitwise operators bind stronger than comparisons.
+    assert_eq!(eval("1 << 2 + 1"), Ok(Value::from_int(8)));
+    assert_eq!(eval("16 >> 1 * 2"), Ok(Value::from_int(4)));
+    assert_eq!(eval("1 | 2 << 1"), Ok(Value::from_int(5)));
+    assert_eq!(eval("1 | 6 & 3"), Ok(Value::from_int(3)));
+    assert_eq!(eval("5 & 3 == 1"), Ok(Value::from(true)));
+    assert_eq!(eval("1 << 2 > 3"), Ok(Value::from(true)));
 
     assert_eq!(
-        Result::from(Value::<DefaultNumericTypes>::String(String::new())),
-        Ok(Value::String(String::new()))
+        eval("1 & 2.0"),
+        Err(EvalexprError::expected_int(Value::from_float(2.0)))
+    );
+    assert_eq!(
+        eval("true | false"),
+        Err(EvalexprError::expected_int(Value::from(true)))
+    );
+    assert_eq!(
+        eval("|"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 0
+        })
     );
 }
 
 #[test]
-fn test_parenthese_combinations() {
-    // These are from issue #94
-    assert_eq!(
-        eval("123(1*2)"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
-    );
+fn test_node_mutable_access() {
+    let mut node = build_operator_tree::<DefaultNumericTypes>("5").unwrap();
+    assert_eq!(node.children_mut().len(), 1);
+    assert_eq!(*node.operator_mut(), Operator::RootNode);
+}
+
+#[test]
+fn test_eval_or() {
+    let context: HashMapContext<DefaultNumericTypes> = context_map! {
+        "a" => int 3,
+        "s" => "abc",
+    }
+    .unwrap();
+
     assert_eq!(
-        eval("1()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        eval_or("a + 4", &context, Value::from_int(0)),
+        Value::from_int(7)
     );
     assert_eq!(
-        eval("1()()()()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        eval_or("len(s)", &context, Value::Empty),
+        Value::from_int(3)
     );
     assert_eq!(
-        eval("1()()()(9)()()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        eval_or("a / 0", &context, Value::from_int(-1)),
+        Value::from_int(-1)
     );
+    assert_eq!(eval_or("a + s", &context, "n/a".into()), Value::from("n/a"));
     assert_eq!(
-        eval_with_context("a+100(a*2)", &context_map! {"a" => int 4}.unwrap()),
-        Err(EvalexprError::<DefaultNumericTypes>::MissingOperatorOutsideOfBrace)
+        eval_or("b", &context, Value::from_float(0.5)),
+        Value::from_float(0.5)
     );
-    assert_eq!(eval_int("(((1+2)*(3+4)+(5-(6)))/((7-8)))"), Ok(-20));
-    assert_eq!(eval_int("(((((5)))))"), Ok(5));
+    assert_eq!(eval_or("(a", &context, Value::Empty), Value::Empty);
 }
 
 #[test]
-fn test_try_from() {
-    #![allow(clippy::redundant_clone)]
+fn test_function_with_context() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("a".into(), Value::from_int(2)).unwrap();
+    context.set_value("b".into(), Value::from_int(3)).unwrap();
+    context
+        .set_function(
+            "sum_of".into(),
+            Function::new_with_context(|argument, context| {
+                let mut sum = 0;
+                for identifier in argument.as_tuple()? {
+                    let identifier = identifier.as_string()?;
+                    sum += context
+                        .get_value(&identifier)
+                        .ok_or(EvalexprError::VariableIdentifierNotFound(identifier))?
+                        .as_int()?;
+                }
+                Ok(Value::from_int(sum))
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "factorial".into(),
+            Function::new_with_context(|argument, context| {
+                let n = argument.as_int()?;
+                if n <= 1 {
+                    Ok(Value::from_int(1))
+                } else {
+                    let rest = context.call_function("factorial", &Value::from_int(n - 1))?;
+                    Ok(Value::from_int(n * rest.as_int()?))
+                }
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "double".into(),
+            Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
+        )
+        .unwrap();
 
-    let value = Value::<DefaultNumericTypes>::String("abc".to_string());
-    assert_eq!(String::try_from(value.clone()), Ok("abc".to_string()));
     assert_eq!(
-        bool::try_from(value.clone()),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: value.clone()
-        })
+        eval_with_context("sum_of(\"a\", \"b\")", &context),
+        Ok(Value::from_int(5))
     );
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedTuple {
-            actual: value.clone()
-        })
+        eval_with_context("sum_of(\"a\", \"c\")", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("c".into()))
     );
     assert_eq!(
-        EmptyType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: value.clone()
-        })
+        eval_with_context("factorial(5)", &context),
+        Ok(Value::from_int(120))
+    );
+    assert_eq!(
+        eval_with_context("double(factorial(3))", &context),
+        Ok(Value::from_int(12))
     );
 
-    let value = Value::<DefaultNumericTypes>::Float(1.3);
+    let cloned_context = context.clone();
+    eval_with_context_mut("a = 10", &mut context).unwrap();
     assert_eq!(
-        String::try_from(value.clone()),
-        Err(EvalexprError::ExpectedString {
-            actual: value.clone()
-        })
+        eval_with_context("sum_of(\"a\", \"b\")", &context),
+        Ok(Value::from_int(13))
     );
     assert_eq!(
-        bool::try_from(value.clone()),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: value.clone()
-        })
+        eval_with_context("sum_of(\"a\", \"b\")", &cloned_context),
+        Ok(Value::from_int(5))
     );
+
+    // Functions receive the context the expression is evaluated with, not the one they are stored in.
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedTuple {
-            actual: value.clone()
-        })
+        eval_with_context("map((1, 2), a -> sum_of(\"a\", \"b\"))", &cloned_context),
+        Ok(Value::Tuple(vec![Value::from_int(4), Value::from_int(5)]))
     );
+    let mut dry_run = DryRunContext::new(&cloned_context);
     assert_eq!(
-        EmptyType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: value.clone()
-        })
+        eval_with_context_mut("b = 7; sum_of(\"a\", \"b\")", &mut dry_run),
+        Ok(Value::from_int(9))
+    );
+    let mut stack = ContextStack::new();
+    stack.push(cloned_context);
+    let mut scope = HashMapContext::<DefaultNumericTypes>::new();
+    scope.set_value("c".into(), Value::from_int(4)).unwrap();
+    stack.push(scope);
+    assert_eq!(
+        eval_with_context("sum_of(\"a\", \"c\")", &stack),
+        Ok(Value::from_int(6))
     );
+}
+
+#[test]
+fn test_i128_numeric_types() {
+    let eval = |expression: &str| {
+        build_operator_tree::<I128NumericTypes>(expression).and_then(|node| node.eval())
+    };
 
-    let value = Value::<DefaultNumericTypes>::Int(13);
     assert_eq!(
-        String::try_from(value.clone()),
-        Err(EvalexprError::ExpectedString {
-            actual: value.clone()
-        })
+        eval("9223372036854775807 + 1"),
+        Ok(Value::from_int(9_223_372_036_854_775_808))
     );
     assert_eq!(
-        bool::try_from(value.clone()),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: value.clone()
-        })
+        eval("170141183460469231731687303715884105727"),
+        Ok(Value::from_int(i128::MAX))
     );
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedTuple {
-            actual: value.clone()
-        })
+        eval("-9223372036854775808 * 1000 / 7 % 1000"),
+        Ok(Value::from_int(i64::MIN as i128 * 1000 / 7 % 1000))
     );
     assert_eq!(
-        EmptyType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: value.clone()
-        })
+        eval("0x7fffffffffffffffffff"),
+        Ok(Value::from_int(0x7fff_ffff_ffff_ffff_ffff))
+    );
+    assert_eq!(
+        eval("2 ^ 64"),
+        Ok(Value::from_float(18_446_744_073_709_551_616.0))
+    );
+    assert_eq!(eval("1 / 2.0"), Ok(Value::from_float(0.5)));
+    assert_eq!(
+        eval("len(\"abc\") * 100000000000000000000"),
+        Ok(Value::from_int(300_000_000_000_000_000_000))
     );
 
-    let value = Value::<DefaultNumericTypes>::Boolean(true);
+    let max = Value::<I128NumericTypes>::from_int(i128::MAX);
+    let min = Value::<I128NumericTypes>::from_int(i128::MIN);
     assert_eq!(
-        String::try_from(value.clone()),
-        Err(EvalexprError::ExpectedString {
-            actual: value.clone()
+        eval("170141183460469231731687303715884105727 + 1"),
+        Err(EvalexprError::AdditionError {
+            augend: max.clone(),
+            addend: Value::from_int(1)
         })
     );
-    assert_eq!(bool::try_from(value.clone()), Ok(true));
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedTuple {
-            actual: value.clone()
+        eval("-170141183460469231731687303715884105727 - 2"),
+        Err(EvalexprError::SubtractionError {
+            minuend: Value::from_int(-i128::MAX),
+            subtrahend: Value::from_int(2)
         })
     );
     assert_eq!(
-        EmptyType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: value.clone()
+        eval("170141183460469231731687303715884105727 * 2"),
+        Err(EvalexprError::MultiplicationError {
+            multiplicand: max,
+            multiplier: Value::from_int(2)
         })
     );
-
-    let value =
-        Value::<DefaultNumericTypes>::Tuple(vec![Value::Int(1), Value::String("abc".to_string())]);
     assert_eq!(
-        String::try_from(value.clone()),
-        Err(EvalexprError::ExpectedString {
-            actual: value.clone()
+        eval("(-170141183460469231731687303715884105727 - 1) / -1"),
+        Err(EvalexprError::DivisionError {
+            dividend: min.clone(),
+            divisor: Value::from_int(-1)
         })
     );
     assert_eq!(
-        bool::try_from(value.clone()),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: value.clone()
+        eval("5 % 0"),
+        Err(EvalexprError::ModulationError {
+            dividend: Value::from_int(5),
+            divisor: Value::from_int(0)
         })
     );
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Ok(vec![Value::Int(1), Value::String("abc".to_string())])
+        eval("-(-170141183460469231731687303715884105727 - 1)"),
+        Err(EvalexprError::NegationError {
+            argument: min.clone()
+        })
     );
+    assert_eq!(eval("1 << 127"), Ok(min));
     assert_eq!(
-        EmptyType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: value.clone()
+        eval("1 << 128"),
+        Err(EvalexprError::ShiftError {
+            value: Value::from_int(1),
+            amount: Value::from_int(128)
         })
     );
 
-    let value = Value::<DefaultNumericTypes>::Empty;
+    let mut context = HashMapContext::<I128NumericTypes>::new();
+    context
+        .set_value("cents".into(), Value::from_int(10_i128.pow(30)))
+        .unwrap();
     assert_eq!(
-        String::try_from(value.clone()),
-        Err(EvalexprError::ExpectedString {
-            actual: value.clone()
-        })
+        eval_with_context_mut("cents += 5; cents / 100", &mut context),
+        Ok(Value::from_int(10_i128.pow(28)))
     );
+}
+
+#[test]
+fn test_eval_with_context_and_buffer() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("a".into(), Value::from_int(3)).unwrap();
+    let mut buffer = EvalBuffer::new();
+
+    for expression in [
+        "a * 2 + max(a, 4, 1)",
+        "if(a > 2, (a, 1), \"no\")",
+        "a > 2 ? {a, (1, 2)} : 0",
+        "str::to_uppercase(\"x\" + \"y\")",
+        "a + b",
+        "a / 0",
+        "(1, (2, 3 + a))",
+    ] {
+        let tree = build_operator_tree::<DefaultNumericTypes>(expression).unwrap();
+        assert_eq!(
+            tree.eval_with_context_and_buffer(&context, &mut buffer),
+            tree.eval_with_context(&context),
+            "{expression}"
+        );
+    }
+}
+
+#[test]
+fn test_node_diff() {
+    let diff = |a: &str, b: &str| {
+        build_operator_tree::<DefaultNumericTypes>(a)
+            .unwrap()
+            .diff(&build_operator_tree(b).unwrap())
+    };
+
+    assert_eq!(diff("a + f(b, 2)", "a + f(b, 2)"), vec![]);
+
+    // Trees differing in one leaf
     assert_eq!(
-        bool::try_from(value.clone()),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: value.clone()
-        })
+        diff("a * 2 + 1", "a * 3 + 1"),
+        vec![NodeDiff::Replaced {
+            path: vec![0, 0, 1]
+        }]
     );
     assert_eq!(
-        TupleType::try_from(value.clone()),
-        Err(EvalexprError::ExpectedTuple {
-            actual: value.clone()
-        })
+        diff("a + f(b, 2)", "a + f(c, 2)"),
+        vec![NodeDiff::Replaced {
+            path: vec![0, 1, 0, 0, 0, 0]
+        }]
     );
-    assert_eq!(EmptyType::try_from(value.clone()), Ok(()));
+    assert_eq!(diff("1", "1.0"), vec![NodeDiff::Replaced { path: vec![0] }]);
+
+    // Trees differing in structure
+    assert_eq!(
+        diff("a + b", "a - b"),
+        vec![NodeDiff::Replaced { path: vec![0] }]
+    );
+    assert_eq!(
+        diff("a + b * c", "(a + b) * c"),
+        vec![NodeDiff::Replaced { path: vec![0] }]
+    );
+    assert_eq!(
+        diff("(1, 2, 3)", "(1, 2)"),
+        vec![NodeDiff::Removed {
+            path: vec![0, 0, 2]
+        }]
+    );
+    assert_eq!(
+        diff("(1, 2)", "(1, 5, 3)"),
+        vec![
+            NodeDiff::Replaced {
+                path: vec![0, 0, 1, 0]
+            },
+            NodeDiff::Added {
+                path: vec![0, 0, 2]
+            },
+        ]
+    );
+    let diffs = diff("x = 1; y", "x = 1; y; z");
+    assert_eq!(diffs, vec![NodeDiff::Added { path: vec![0, 2] }]);
+    assert_eq!(diffs[0].path(), &[0, 2]);
 }
 
 #[test]
-fn assignment_lhs_is_identifier() {
-    let tree = build_operator_tree("a = 1").unwrap();
-    let operators: Vec<_> = tree.iter().map(|node| node.operator().clone()).collect();
-
+fn test_eval_with_context_limited() {
     let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    tree.eval_with_context_mut(&mut context).unwrap();
-    assert_eq!(context.get_value("a"), Some(&Value::Int(1)));
+    context.set_value("a".into(), Value::from_int(3)).unwrap();
 
-    assert!(
-        matches!(
-            operators.as_slice(),
-            [
-                Operator::Assign,
-                Operator::VariableIdentifierWrite { identifier: value },
-                Operator::Const {
-                    value: Value::Int(1)
-                }
-            ] if value == "a"
-        ),
-        "actual: {:#?}",
-        operators
+    for expression in [
+        "a * 2 + max(a, 4, 1)",
+        "a > 2 ? {a, (1, 2)} : 0",
+        "b ?? a",
+        "map((1, 2), x -> x * a)",
+        "a / 0",
+    ] {
+        let tree = build_operator_tree::<DefaultNumericTypes>(expression).unwrap();
+        assert_eq!(
+            tree.eval_with_context_limited(&context, 100),
+            tree.eval_with_context(&context),
+            "{expression}"
+        );
+    }
+
+    let tree = build_operator_tree::<DefaultNumericTypes>("1").unwrap();
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 2),
+        Ok(Value::from_int(1))
+    );
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 1),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 0),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+
+    // The bodies of lambdas are limited as well.
+    let tree = build_operator_tree::<DefaultNumericTypes>("map((1, 2), x -> -(-(-(-x))))").unwrap();
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 9),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 10),
+        Ok(Value::Tuple(vec![Value::from_int(1), Value::from_int(2)]))
+    );
+
+    let nested = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+    let tree = build_operator_tree::<DefaultNumericTypes>(&nested).unwrap();
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 100),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        tree.eval_with_context_limited(&context, 300),
+        Ok(Value::from_int(1))
     );
 }
 
 #[test]
-fn test_variable_assignment_and_iteration() {
-    let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    eval_with_context_mut("a = 5; b = 5.0", &mut context).unwrap();
+fn test_max_nesting_depth() {
+    let config = ParseConfig::new().with_max_nesting_depth(Some(2));
+    assert_eq!(config.max_nesting_depth(), Some(2));
+    assert_eq!(ParseConfig::new().max_nesting_depth(), None);
 
-    let mut variables: Vec<_> = context.iter_variables().collect();
-    variables.sort_unstable_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    let build = |expression: &str, config: &ParseConfig| {
+        build_operator_tree_with_config::<DefaultNumericTypes>(expression, config).map(|_| ())
+    };
+    assert_eq!(build("((1))", &config), Ok(()));
+    assert_eq!(build("f(x[0])", &config), Ok(()));
+    assert_eq!(build("{(1), 2}", &config), Ok(()));
+    assert_eq!(
+        build("(1) + ((2)) + (((3)))[0]", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        build("max((1, (2)))", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+
+    // Operators count towards the nesting depth as well, e.g. `-(1 + 2)` has a depth of two.
+    assert_eq!(build("--1", &config), Ok(()));
+    assert_eq!(
+        build("---1", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(build("1 + 2 + 3", &config), Ok(()));
     assert_eq!(
-        variables,
-        vec![
-            ("a".to_string(), Value::from_int(5)),
-            ("b".to_string(), Value::from_float(5.0))
-        ],
+        build("1 + 2 + 3 + 4", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(build("2 ^ 3 ^ 4", &config), Ok(()));
+    assert_eq!(
+        build("2 ^ 3 ^ 4 ^ 5", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(build("a ? b : c ? d : e", &config), Ok(()));
+    assert_eq!(
+        build("a ? b : c ? d : e ? f : g", &config),
+        Err(EvalexprError::RecursionLimitExceeded)
     );
 
-    let mut variables: Vec<_> = context.iter_variable_names().collect();
-    variables.sort_unstable();
-    assert_eq!(variables, vec!["a".to_string(), "b".to_string()],);
+    // Deeply nested untrusted input fails gracefully instead of overflowing the stack.
+    let config = ParseConfig::new().with_max_nesting_depth(Some(64));
+    for nested in [
+        format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000)),
+        format!("{}true", "!".repeat(100_000)),
+        format!("{}1", "-".repeat(100_000)),
+        vec!["1"; 100_000].join("^"),
+        vec!["1"; 100_000].join("+"),
+        format!("{}1", "true ? 1 : ".repeat(100_000)),
+        format!("{}1", "a = ".repeat(100_000)),
+        format!("{}1", "x -> ".repeat(100_000)),
+        format!("a{}", "[0]".repeat(100_000)),
+        format!(
+            "{}+{}true",
+            vec!["1"; 100_000].join("+"),
+            "!".repeat(100_000)
+        ),
+    ] {
+        assert_eq!(
+            build(&nested, &config),
+            Err(EvalexprError::RecursionLimitExceeded)
+        );
+    }
+    assert_eq!(build(&format!("{}true", "!".repeat(64)), &config), Ok(()));
 }
 
 #[test]
-fn test_negative_power() {
-    println!(
-        "{:?}",
-        build_operator_tree::<DefaultNumericTypes>("3^-2").unwrap()
+fn test_get_value_cow() {
+    struct RowContext {
+        columns: Vec<&'static str>,
+        row: Vec<i64>,
+    }
+
+    impl Context for RowContext {
+        type NumericTypes = DefaultNumericTypes;
+
+        fn get_value(&self, _identifier: &str) -> Option<&Value> {
+            None
+        }
+
+        fn get_value_cow(&self, identifier: &str) -> Option<Cow<'_, Value>> {
+            let column = self
+                .columns
+                .iter()
+                .position(|column| *column == identifier)?;
+            Some(Cow::Owned(Value::from_int(self.row[column])))
+        }
+
+        fn call_function(&self, identifier: &str, _argument: &Value) -> EvalexprResultValue {
+            Err(EvalexprError::FunctionIdentifierNotFound(
+                identifier.to_string(),
+            ))
+        }
+
+        fn are_builtin_functions_disabled(&self) -> bool {
+            false
+        }
+
+        fn set_builtin_functions_disabled(&mut self, _disabled: bool) -> EvalexprResult<()> {
+            Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
+        }
+    }
+
+    let tree = build_operator_tree::<DefaultNumericTypes>("a * 10 + b").unwrap();
+    for (row, expected) in [(vec![1, 2], 12), (vec![3, 4], 34)] {
+        let context = RowContext {
+            columns: vec!["a", "b"],
+            row,
+        };
+        assert_eq!(
+            tree.eval_with_context(&context),
+            Ok(Value::from_int(expected))
+        );
+        assert_eq!(tree.infer_type(&context), Ok(ValueType::Int));
+    }
+
+    // Wrapping contexts read the owned values of the contexts they wrap.
+    let context = RowContext {
+        columns: vec!["a", "b"],
+        row: vec![5, 6],
+    };
+    let mut dry_run = DryRunContext::new(&context);
+    assert_eq!(
+        eval_with_context_mut("b = 7; a * 10 + b", &mut dry_run),
+        Ok(Value::from_int(57))
+    );
+    let mut stack = ContextStack::new();
+    stack.push(context);
+    stack.push(HashMapContext::<DefaultNumericTypes>::new());
+    assert_eq!(tree.eval_with_context(&stack), Ok(Value::from_int(56)));
+
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
+    context.set_value("a".into(), Value::from_int(5)).unwrap();
+    assert_eq!(
+        context.get_value_cow("a"),
+        Some(Cow::Borrowed(&Value::from_int(5)))
     );
-    assert_eq!(eval("3^-2"), Ok(Value::Float(1.0 / 9.0)));
-    assert_eq!(eval("3^(-2)"), Ok(Value::Float(1.0 / 9.0)));
-    assert_eq!(eval("-3^2"), Ok(Value::Float(-9.0)));
-    assert_eq!(eval("-(3)^2"), Ok(Value::Float(-9.0)));
-    assert_eq!(eval("(-3)^-2"), Ok(Value::Float(1.0 / 9.0)));
-    assert_eq!(eval("-(3^-2)"), Ok(Value::Float(-1.0 / 9.0)));
+    assert_eq!(context.get_value_cow("b"), None);
 }
 
 #[test]
-fn test_builtin_functions_context() {
+fn test_validate_against_context() {
     let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    // Builtin functions are enabled by default for HashMapContext.
+    context
+        .set_function(
+            "avg".into(),
+            Function::new(|argument| {
+                let arguments = argument.as_tuple()?;
+                let mut sum = 0.0;
+                for argument in &arguments {
+                    sum += argument.as_number()?;
+                }
+                Ok(Value::from_float(sum / arguments.len() as f64))
+            })
+            .with_argument_amount(2..=3),
+        )
+        .unwrap();
+    context
+        .set_function("id".into(), Function::new(|argument| Ok(argument.clone())))
+        .unwrap();
+    context
+        .set_value(
+            "t".into(),
+            Value::Tuple(vec![Value::from_int(1), Value::from_int(2)]),
+        )
+        .unwrap();
+
+    fn validate(expression: &str, context: &HashMapContext) -> EvalexprResult<()> {
+        build_operator_tree::<DefaultNumericTypes>(expression)
+            .unwrap()
+            .validate_against_context(context)
+    }
+    assert_eq!(validate("avg(1, 2)", &context), Ok(()));
+    assert_eq!(validate("avg(1, 2, 3)", &context), Ok(()));
+    assert_eq!(validate("avg((1, 2))", &context), Ok(()));
+    assert_eq!(validate("avg(t)", &context), Ok(()));
+    assert_eq!(validate("avg(id(1))", &context), Ok(()));
+    assert_eq!(validate("id()", &context), Ok(()));
+    assert_eq!(validate("id(1, 2, 3, 4)", &context), Ok(()));
+    assert_eq!(validate("max(avg(1, 2), 3)", &context), Ok(()));
+    assert_eq!(
+        validate("avg(1)", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            1,
+            2..=3
+        ))
+    );
     assert_eq!(
-        eval_with_context("max(1,3)", &context),
-        Ok(Value::from_int(3))
+        validate("avg()", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            0,
+            2..=3
+        ))
     );
-    // Disabling builtin function in Context.
-    context.set_builtin_functions_disabled(true).unwrap();
-    // Builtin functions are disabled and using them returns an error.
     assert_eq!(
-        eval_with_context("max(1,3)", &context),
-        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
-            "max"
-        )))
+        validate("1 + id(avg(1, 2, 3, 4))", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            4,
+            2..=3
+        ))
+    );
+    assert_eq!(
+        validate("avg 5", &context),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            1,
+            2..=3
+        ))
+    );
+    assert_eq!(
+        validate("avgg(1, 2)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "avgg".to_string()
+        ))
+    );
+    assert_eq!(
+        validate("avgg(1, 2)", &context)
+            .unwrap_err()
+            .suggestion(&context),
+        Some("avg".to_string())
+    );
+    assert_eq!(
+        validate("qqqqqqqq(1)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "qqqqqqqq".to_string()
+        ))
     );
-}
 
-#[test]
-fn test_hex() {
-    assert_eq!(eval("0x3"), Ok(Value::Int(3)));
-    assert_eq!(eval("0xFF"), Ok(Value::Int(255)));
-    assert_eq!(eval("-0xFF"), Ok(Value::Int(-255)));
+    context.set_builtin_functions_disabled(true).unwrap();
     assert_eq!(
-        eval("0x"),
-        // The "VariableIdentifierNotFound" error is what evalexpr currently returns,
-        // but ideally it would return more specific errors for "illegal" literals.
-        Err(EvalexprError::VariableIdentifierNotFound("0x".into()))
+        validate("max(1, 2)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_string()))
     );
-}
 
-#[test]
-fn test_broken_string() {
+    let mut stack = ContextStack::new();
+    stack.push(context.clone());
     assert_eq!(
-        eval(r#""abc" == "broken string"#),
-        Err(EvalexprError::UnmatchedDoubleQuote)
+        build_operator_tree::<DefaultNumericTypes>("avg(1)")
+            .unwrap()
+            .validate_against_context(&stack),
+        Err(EvalexprError::wrong_function_argument_amount_range(
+            1,
+            2..=3
+        ))
     );
 }
 
 #[test]
-fn test_comments() {
+fn test_eval_with_raw_context() {
+    let mut hash_map_context = HashMapContext::<DefaultNumericTypes>::new();
+    hash_map_context
+        .set_value("a".into(), Value::from_int(3))
+        .unwrap();
+    hash_map_context
+        .set_function(
+            "double".into(),
+            Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
+        )
+        .unwrap();
+    hash_map_context.set_overflow_mode(OverflowMode::Saturating);
+    let mut stack = ContextStack::new();
+    stack.push(hash_map_context.clone());
+
+    let contexts: Vec<Box<dyn Context<NumericTypes = DefaultNumericTypes>>> = vec![
+        Box::new(hash_map_context),
+        Box::new(stack),
+        Box::new(EmptyContext::default()),
+    ];
+    let tree = build_operator_tree::<DefaultNumericTypes>("double(a) + 1").unwrap();
     assert_eq!(
-        eval(
-            "
-            // input
-            a = 1;  // assignment
-            // output
-            a + 2  // add"
-        ),
-        Ok(Value::Int(3))
+        tree.eval_with_raw_context(contexts[0].as_ref()),
+        Ok(Value::from_int(7))
     );
-
     assert_eq!(
-        eval("0 /*"),
-        Err(EvalexprError::CustomMessage(
-            "unmatched inline comment".into()
-        ))
+        tree.eval_with_raw_context(contexts[1].as_ref()),
+        Ok(Value::from_int(7))
     );
 
+    // The overflow mode is taken from the wrapped context.
+    let tree = build_operator_tree::<DefaultNumericTypes>("math::int_max() + a").unwrap();
     assert_eq!(
-        eval("1 % 4 + /*inline comment*/ 6 /*END*/"),
-        Ok(Value::Int(7))
+        tree.eval_with_raw_context(contexts[0].as_ref()),
+        Ok(Value::from_int(i64::MAX))
     );
+    assert!(matches!(
+        tree.eval_with_raw_context(contexts[1].as_ref()),
+        Err(EvalexprError::AdditionError { .. })
+    ));
 
+    let tree = build_operator_tree::<DefaultNumericTypes>("a").unwrap();
     assert_eq!(
-        eval("/* begin */ 10 /* middle */ + 5 /* end */ + 6 // DONE"),
-        Ok(Value::Int(21))
+        tree.eval_with_raw_context(contexts[2].as_ref()),
+        Err(EvalexprError::VariableIdentifierNotFound("a".to_string()))
     );
 }
 
 #[test]
-fn test_clear() {
+fn test_eval_with_context_catching() {
     let mut context = HashMapContext::<DefaultNumericTypes>::new();
-    context.set_value("abc".into(), "def".into()).unwrap();
-    assert_eq!(context.get_value("abc"), Some(&("def".into())));
-    context.clear_functions();
-    assert_eq!(context.get_value("abc"), Some(&("def".into())));
-    context.clear_variables();
-    assert_eq!(context.get_value("abc"), None);
+    context.set_value("a".into(), Value::from_int(4)).unwrap();
+    let catching = |expression: &str| {
+        build_operator_tree::<DefaultNumericTypes>(expression)
+            .unwrap()
+            .eval_with_context_catching(&context)
+    };
 
-    context
-        .set_function(
-            "abc".into(),
-            Function::new(|input| Ok(Value::String(format!("{input}")))),
+    assert_eq!(
+        catching("a + 1, b, a * 2"),
+        (
+            Some(Value::Tuple(vec![
+                Value::from_int(5),
+                Value::Empty,
+                Value::from_int(8)
+            ])),
+            vec![EvalexprError::VariableIdentifierNotFound("b".to_string())]
         )
-        .unwrap();
+    );
     assert_eq!(
-        eval_with_context("abc(5)", &context).unwrap(),
-        Value::String("5".into())
+        catching("(1 / 0, \"x\" - 1, (1, 2))"),
+        (
+            Some(Value::Tuple(vec![
+                Value::Empty,
+                Value::Empty,
+                Value::Tuple(vec![Value::from_int(1), Value::from_int(2)])
+            ])),
+            vec![
+                EvalexprError::DivisionError {
+                    dividend: Value::from_int(1),
+                    divisor: Value::from_int(0),
+                },
+                EvalexprError::expected_number(Value::from("x")),
+            ]
+        )
     );
-    context.clear_variables();
     assert_eq!(
-        eval_with_context("abc(5)", &context).unwrap(),
-        Value::String("5".into())
+        catching("b; a"),
+        (
+            Some(Value::from_int(4)),
+            vec![EvalexprError::VariableIdentifierNotFound("b".to_string())]
+        )
     );
-    context.clear_functions();
-    assert!(eval_with_context("abc(5)", &context).is_err());
+    assert_eq!(
+        catching("a; b"),
+        (
+            None,
+            vec![EvalexprError::VariableIdentifierNotFound("b".to_string())]
+        )
+    );
+    assert_eq!(catching("a * 3"), (Some(Value::from_int(12)), vec![]));
+    assert_eq!(
+        catching("a + b"),
+        (
+            None,
+            vec![EvalexprError::VariableIdentifierNotFound("b".to_string())]
+        )
+    );
+}
 
+#[test]
+fn test_lambdas() {
+    let mut context = HashMapContext::<DefaultNumericTypes>::new();
     context
-        .set_value("five".into(), Value::from_int(5))
+        .set_value("offset".into(), Value::from_int(10))
         .unwrap();
     context
         .set_function(
-            "abc".into(),
-            Function::new(|input| Ok(Value::String(format!("{input}")))),
+            "double".into(),
+            Function::new(|argument| Ok(Value::from_int(argument.as_int()? * 2))),
         )
         .unwrap();
+
     assert_eq!(
-        eval_with_context("abc(five)", &context).unwrap(),
-        Value::String("5".into())
+        eval_with_context("map((1, 2, 3), x -> x + 1)", &context),
+        Ok(Value::from(vec![
+            Value::from_int(2),
+            Value::from_int(3),
+            Value::from_int(4)
+        ]))
+    );
+    assert_eq!(
+        eval_with_context("map((1, 2), x -> double(x) + offset)", &context),
+        Ok(Value::from(vec![Value::from_int(12), Value::from_int(14)]))
+    );
+    assert_eq!(
+        eval_with_context("map(((1, 2), (3, 4)), (a, b) -> a * b)", &context),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(12)]))
+    );
+    assert_eq!(
+        eval_with_context("filter((1, 2, 3, 4), (x) -> x % 2 == 0)", &context),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(4)]))
+    );
+    assert_eq!(
+        eval_with_context("all((1, 2), x -> x > 0)", &context),
+        Ok(Value::from(true))
+    );
+    assert_eq!(
+        eval_with_context("any((1, 2), x -> x > 2)", &context),
+        Ok(Value::from(false))
+    );
+    // Parameters shadow variables of the context, and nested lambdas see the parameters of enclosing ones.
+    assert_eq!(
+        eval_with_context("map((1, 2), offset -> offset * 2)", &context),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(4)]))
+    );
+    assert_eq!(
+        eval_with_context("map((1, 2), x -> map((10, 20), y -> x + y))", &context),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from_int(11), Value::from_int(21)]),
+            Value::from(vec![Value::from_int(12), Value::from_int(22)])
+        ]))
+    );
+    assert_eq!(
+        eval_with_context("t = (1, 2); map(t, x -> x * offset)", &context.clone()),
+        Err(EvalexprError::ContextNotMutable)
+    );
+    assert_eq!(
+        eval_with_context_mut("t = (1, 2); map(t, x -> x * offset)", &mut context),
+        Ok(Value::from(vec![Value::from_int(10), Value::from_int(20)]))
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("map((1, 2), x -> x + 1)")
+            .unwrap()
+            .eval_with_context_and_buffer(&context, &mut EvalBuffer::new()),
+        Ok(Value::from(vec![Value::from_int(2), Value::from_int(3)]))
     );
-    context.clear();
-    assert!(context.get_value("five").is_none());
-    assert!(eval_with_context("abc(5)", &context).is_err());
-}
 
-#[test]
-fn test_iter_empty_contexts() {
+    // Errors
     assert_eq!(
-        EmptyContext::<DefaultNumericTypes>::default()
-            .iter_variables()
-            .next(),
-        None
+        eval_with_context("map((1, 2), x -> y)", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("y".into()))
     );
     assert_eq!(
-        EmptyContext::<DefaultNumericTypes>::default()
-            .iter_variable_names()
-            .next(),
-        None
+        eval_with_context("map((1, 2), (a, b) -> a)", &context),
+        Err(EvalexprError::expected_tuple(Value::from_int(1)))
     );
+    assert_eq!(eval("x -> x"), Err(EvalexprError::UnappliedLambda));
     assert_eq!(
-        EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
-            .iter_variables()
-            .next(),
-        None
+        eval("double((1, 2), x -> x)"),
+        Err(EvalexprError::UnappliedLambda)
     );
     assert_eq!(
-        EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
-            .iter_variable_names()
-            .next(),
-        None
+        build_operator_tree::<DefaultNumericTypes>("1 -> 2"),
+        Err(EvalexprError::IllegalLambdaParameters)
+    );
+    assert_eq!(
+        build_operator_tree::<DefaultNumericTypes>("(a, b + 1) -> a"),
+        Err(EvalexprError::IllegalLambdaParameters)
+    );
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        eval_with_context("map((1, 2), x -> x)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("map".into()))
     );
-}
 
-#[test]
-fn test_empty_context_builtin_functions() {
-    assert!(EmptyContext::<DefaultNumericTypes>::default().are_builtin_functions_disabled());
-    assert!(
-        !EmptyContextWithBuiltinFunctions::<DefaultNumericTypes>::default()
-            .are_builtin_functions_disabled()
+    let tree = build_operator_tree::<DefaultNumericTypes>("(a, b) -> a * b").unwrap();
+    assert_eq!(
+        tree.children()[0].operator(),
+        &Operator::Lambda {
+            params: vec!["a".into(), "b".into()]
+        }
     );
 }
 
 #[test]
-fn test_compare_different_numeric_types() {
-    assert_eq!(eval("1 < 2.0"), Ok(true.into()));
-    assert_eq!(eval("1 >= 2"), Ok(false.into()));
-    assert_eq!(eval("1 >= 2.0"), Ok(false.into()));
-}
+fn test_value_formatter() {
+    let value = eval("(1234567, 0.125, -9876.5, \"text\", {1000, true}, ())").unwrap();
+    let default = ValueFormatter::new();
+    let report = ValueFormatter::new()
+        .with_float_decimals(Some(2))
+        .with_thousands_separator(Some(','))
+        .with_tuple_delimiters("[", "]")
+        .with_element_separator(" | ");
 
-#[test]
-fn test_escape_sequences() {
+    assert_eq!(value.format_with(&default), value.str_from());
     assert_eq!(
-        eval("\"\\x\""),
-        Err(EvalexprError::IllegalEscapeSequence("\\x".to_string()))
+        value.format_with(&default),
+        "(1234567, 0.125, -9876.5, \"text\", {1000, true}, ())"
     );
     assert_eq!(
-        eval("\"\\"),
-        Err(EvalexprError::IllegalEscapeSequence("\\".to_string()))
+        value.format_with(&report),
+        "[1,234,567 | 0.12 | -9,876.50 | \"text\" | {1,000 | true} | ()]"
     );
+
+    for (value, default_string, report_string) in [
+        (
+            Value::<DefaultNumericTypes>::from_int(-1000),
+            "-1000",
+            "-1,000",
+        ),
+        (Value::from_int(999), "999", "999"),
+        (Value::from_float(1234.5678), "1234.5678", "1,234.57"),
+        (Value::from_float(2.0), "2", "2.00"),
+        (Value::from_float(f64::INFINITY), "inf", "inf"),
+        (Value::from("1000"), "1000", "1000"),
+        (Value::from(false), "false", "false"),
+    ] {
+        assert_eq!(value.format_with(&default), default_string);
+        assert_eq!(value.format_with(&report), report_string);
+    }
+
+    assert_eq!(report.float_decimals(), Some(2));
+    assert_eq!(report.thousands_separator(), Some(','));
+    assert_eq!(report.tuple_delimiters(), ("[", "]"));
+    assert_eq!(report.element_separator(), " | ");
 }
 
 #[test]
-fn test_unmatched_partial_tokens() {
+fn test_display_round_trip() {
+    for (expression, expected) in [
+        ("1 + 2 * 3", "1 + 2 * 3"),
+        ("((1 + 2)) * 3", "(1 + 2) * 3"),
+        ("1 - (2 - 3) - 4", "1 - (2 - 3) - 4"),
+        ("2 ^ (3 ^ 2)", "2 ^ (3 ^ 2)"),
+        ("-a ^ 2 + (-a) ^ 2", "-a ^ 2 + (-a) ^ 2"),
+        ("!(a && b) || !c", "!(a && b) || !c"),
+        ("a = b = 3; c += (d = 1); c;", "a = b = 3; c += (d = 1); c;"),
+        ("f(1, (2, 3)) + g() + h 4", "f(1, (2, 3)) + g() + h(4)"),
+        ("a[i + 1]", "a[i + 1]"),
+        ("(c ? 1 : 2) ? d ? 3 : 4 : 5", "(c ? 1 : 2) ? d ? 3 : 4 : 5"),
+        ("{1, (2, 3)}", "{1, (2, 3)}"),
+        ("{(1, 2)}", "{(1, 2)}"),
+        ("{((1, 2))}", "{(1, 2)}"),
+        ("len(((1, 2)))", "len((1, 2))"),
+        ("len(1, 2)", "len(1, 2)"),
+        ("max(1, 2)[0]", "max(1, 2)[0]"),
+        ("a[0][1]", "a[0][1]"),
+        ("(-a)[0]", "(-a)[0]"),
+        ("\"a\\\"b\\\\\" + 1.0 + 0.5", "\"a\\\"b\\\\\" + 1.0 + 0.5"),
+        (
+            "map((1, 2), (a, b) -> a * b)",
+            "map((1, 2), (a, b) -> a * b)",
+        ),
+        ("", ""),
+    ] {
+        let tree = build_operator_tree::<DefaultNumericTypes>(expression).unwrap();
+        assert_eq!(tree.to_string(), expected);
+        assert_eq!(
+            build_operator_tree::<DefaultNumericTypes>(expected)
+                .unwrap()
+                .to_string(),
+            expected
+        );
+    }
+
+    // Parenthesized tuple arguments and elements as well as indexed calls are parsed back into equal trees.
+    for expression in [
+        "{(1, 2)}",
+        "len((1, 2))",
+        "max(1, 2)[0]",
+        "f(1)[0][1]",
+        "{(1, 2)}[0]",
+    ] {
+        let tree = build_operator_tree::<DefaultNumericTypes>(expression).unwrap();
+        assert_eq!(
+            build_operator_tree::<DefaultNumericTypes>(&tree.to_string()),
+            Ok(tree)
+        );
+    }
     assert_eq!(
-        eval("|"),
-        Err(EvalexprError::UnmatchedPartialToken {
-            first: PartialToken::VerticalBar,
-            second: None
-        })
+        eval("{(1, 2)}"),
+        eval(
+            &build_operator_tree::<DefaultNumericTypes>("{(1, 2)}")
+                .unwrap()
+                .to_string()
+        )
     );
-}
 
-#[test]
-fn test_node_mutable_access() {
-    let mut node = build_operator_tree::<DefaultNumericTypes>("5").unwrap();
-    assert_eq!(node.children_mut().len(), 1);
-    assert_eq!(*node.operator_mut(), Operator::RootNode);
+    // Rewritten trees get the parentheses they need.
+    let mut tree = build_operator_tree::<DefaultNumericTypes>("a * b ^ c").unwrap();
+    *tree.children_mut()[0].children_mut()[1].operator_mut() = Operator::Add;
+    assert_eq!(tree.to_string(), "a * (b + c)");
+    *tree.children_mut()[0].children_mut()[0].operator_mut() = Operator::Const {
+        value: Value::from_int(-2),
+    };
+    assert_eq!(tree.to_string(), "-2 * (b + c)");
+    *tree.children_mut()[0].operator_mut() = Operator::Exp;
+    assert_eq!(tree.to_string(), "(-2) ^ (b + c)");
+
+    let context = context_map! { "b" => int 1, "c" => int 2 }.unwrap();
+    let reparsed = build_operator_tree(&tree.to_string()).unwrap();
+    assert_eq!(
+        reparsed.eval_with_context(&context),
+        tree.eval_with_context(&context)
+    );
+
+    // Floats are displayed such that they are parsed as floats again.
+    for value in [
+        Value::from_float(2.0),
+        Value::from_float(f64::INFINITY),
+        Value::from_float(f64::NEG_INFINITY),
+        Value::from(vec![Value::from_float(-1.0), Value::from("x")]),
+    ] {
+        let mut tree = build_operator_tree::<DefaultNumericTypes>("0").unwrap();
+        *tree.children_mut()[0].operator_mut() = Operator::Const {
+            value: value.clone(),
+        };
+        assert_eq!(eval(&tree.to_string()), Ok(value));
+    }
+    let mut tree = build_operator_tree::<DefaultNumericTypes>("0").unwrap();
+    *tree.children_mut()[0].operator_mut() = Operator::Const {
+        value: Value::from_float(f64::NAN),
+    };
+    assert_eq!(tree.to_string(), "math::nan()");
 }
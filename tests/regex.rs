@@ -28,4 +28,15 @@ fn test_regex_functions() {
         eval("str::regex_replace(\"foobar\", \".*?(i+)\", \"b$1\")"),
         Ok(Value::String("foobar".to_owned()))
     );
+    match eval("str::regex_replace(\"foo\", \"(\", \"b\")") {
+        Err(EvalexprError::InvalidRegex { regex, message }) => {
+            assert_eq!(regex, "(");
+            assert!(message.contains("unclosed group"));
+        },
+        v => panic!("{:?}", v),
+    };
+    assert_eq!(
+        eval("str::regex_matches(1, \"1\")"),
+        Err(EvalexprError::expected_string(Value::from_int(1)))
+    );
 }
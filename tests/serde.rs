@@ -1,7 +1,7 @@
 #![cfg(not(tarpaulin_include))]
 #![cfg(feature = "serde")]
 
-use evalexpr::{build_operator_tree, Node};
+use evalexpr::{build_operator_tree, DefaultNumericTypes, Node, Value};
 
 #[test]
 fn test_serde() {
@@ -24,11 +24,10 @@ fn test_serde_errors() {
         })
     );
     assert_eq!(
-        ron::de::from_str::<Node>("\"&\""),
+        ron::de::from_str::<Node>("\")\""),
         Err(ron::de::SpannedError {
             code: ron::Error::Message(
-                "Found a partial token '&' that should be followed by another partial token."
-                    .to_owned()
+                "Found an unmatched closing parenthesis ')' at position 0..1.".to_owned()
             ),
             position: ron::de::Position { line: 1, col: 4 }
         })
@@ -41,3 +40,27 @@ fn test_serde_errors() {
         ""
     );
 }
+
+#[test]
+fn test_value_bincode_round_trip() {
+    let value = Value::<DefaultNumericTypes>::from(vec![
+        Value::from_int(-3),
+        Value::from_float(2.5),
+        Value::from("abc"),
+        Value::from(vec![
+            Value::from(true),
+            Value::Empty,
+            Value::from(vec![Value::from_int(i64::MAX), Value::from_float(f64::MIN)]),
+        ]),
+        Value::Tuple(vec![]),
+    ]);
+
+    let bytes = bincode::serialize(&value).unwrap();
+    let deserialized: Value<DefaultNumericTypes> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, value);
+
+    let nan = Value::<DefaultNumericTypes>::from_float(f64::NAN);
+    let bytes = bincode::serialize(&nan).unwrap();
+    let deserialized: Value<DefaultNumericTypes> = bincode::deserialize(&bytes).unwrap();
+    assert!(deserialized.as_float().unwrap().is_nan());
+}